@@ -0,0 +1,298 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+
+// Embeds the build identity so bug reports can name the exact build that was running, locates the
+// mappings file to embed with the `embed-mappings` feature and lists the classes and members the
+// sources look up by name, which the startup checks the mappings against.
+fn main() {
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| String::from("unknown"));
+    println!("cargo:rustc-env=LB_GIT_HASH={}", git_hash);
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|feature| feature.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=LB_FEATURES={}", features.join(","));
+
+    if std::env::var_os("CARGO_FEATURE_EMBED_MAPPINGS").is_some() {
+        embed_mappings();
+    }
+    println!("cargo:rerun-if-env-changed=LB_MAPPINGS");
+
+    required_members();
+    println!("cargo:rerun-if-changed=src");
+
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
+
+// Writes the list of mapping sets to embed. `LB_MAPPINGS` (default `mappings.tiny`) is either a single
+// file used for every game version, or a directory of `<version>.tiny` files, one per game version.
+fn embed_mappings() {
+    let path = std::env::var("LB_MAPPINGS").unwrap_or_else(|_| String::from("mappings.tiny"));
+    let path = std::fs::canonicalize(&path).unwrap_or_else(|e| panic!("embed-mappings is enabled but {} cannot be read: {}", path, e));
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let mut sets = Vec::new();
+    if path.is_dir() {
+        for entry in std::fs::read_dir(&path).expect("mappings directory cannot be read") {
+            let file = entry.expect("mappings directory cannot be read").path();
+            if let Some(version) = file.file_name().and_then(|name| name.to_str()).and_then(|name| name.strip_suffix(".tiny")) {
+                sets.push((version.to_string(), file.clone()));
+            }
+        }
+        sets.sort();
+    } else {
+        sets.push((String::new(), path));
+    }
+
+    let entries: Vec<String> = sets
+        .iter()
+        .map(|(version, file)| format!("({:?}, include_bytes!({:?}))", version, file.display().to_string()))
+        .collect();
+    let out = std::path::PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    std::fs::write(out.join("embedded_mappings.rs"), format!("&[{}]", entries.join(", "))).expect("OUT_DIR is writable");
+}
+
+// A call looking up a class or member by name, with the positions of its class, name and descriptor
+// arguments.
+struct Lookup {
+    call: &'static str,
+    kind: &'static str,
+    class: usize,
+    name: Option<usize>,
+    descriptor: Option<usize>,
+}
+
+const LOOKUPS: &[Lookup] = &[
+    Lookup { call: "remap::call_method(", kind: "Method", class: 2, name: Some(3), descriptor: Some(4) },
+    Lookup { call: "remap::call_static_method(", kind: "Method", class: 1, name: Some(2), descriptor: Some(3) },
+    Lookup { call: "remap::new_object(", kind: "Class", class: 1, name: None, descriptor: None },
+    Lookup { call: "remap::get_field(", kind: "Field", class: 2, name: Some(3), descriptor: Some(4) },
+    Lookup { call: "remap::set_field(", kind: "Field", class: 2, name: Some(3), descriptor: Some(4) },
+    Lookup { call: "remap::get_static_field(", kind: "Field", class: 1, name: Some(2), descriptor: Some(3) },
+    Lookup { call: "remap::is_instance_of(", kind: "Class", class: 2, name: None, descriptor: None },
+    Lookup { call: "cache::class(", kind: "Class", class: 1, name: None, descriptor: None },
+    Lookup { call: "cache::method_id(", kind: "Method", class: 1, name: Some(2), descriptor: Some(3) },
+    Lookup { call: "cache::static_method_id(", kind: "Method", class: 1, name: Some(2), descriptor: Some(3) },
+    Lookup { call: "cache::field_id(", kind: "Field", class: 1, name: Some(2), descriptor: Some(3) },
+    Lookup { call: "cache::static_field_id(", kind: "Field", class: 1, name: Some(2), descriptor: Some(3) },
+    Lookup { call: "call_primitive(", kind: "Method", class: 2, name: Some(3), descriptor: None },
+    Lookup { call: "get_primitive_field(", kind: "Field", class: 2, name: Some(3), descriptor: None },
+    Lookup { call: "set_primitive_field(", kind: "Field", class: 2, name: Some(3), descriptor: None },
+];
+
+// Writes the `sdk::mappings::Requirement`s of every Minecraft class and member the sources look up
+// with literal names: the `remap` and `cache` calls, the primitive accessors and the `java_class!`
+// members. Lookups with computed names cannot be listed and are left out.
+fn required_members() {
+    let mut sources = Vec::new();
+    collect_sources(Path::new("src"), &mut sources);
+    let sources: Vec<String> = sources.iter().map(|text| strip_comment_lines(text)).collect();
+
+    // Wrapper types and their classes, for `Self::CLASS` and `Type::CLASS` arguments.
+    let mut classes = HashMap::new();
+    for text in &sources {
+        for (name, class) in declared_classes(text) {
+            classes.insert(name, class);
+        }
+    }
+
+    let mut required = BTreeSet::new();
+    for text in &sources {
+        for lookup in LOOKUPS {
+            for (position, _) in text.match_indices(lookup.call) {
+                let args = call_arguments(&text[position + lookup.call.len()..]);
+                let Some(class) = args.get(lookup.class).and_then(|arg| resolve_class(arg, text, position, &classes)) else {
+                    continue;
+                };
+                let name = lookup.name.map(|index| args.get(index).and_then(|arg| string_literal(arg)));
+                let descriptor = lookup.descriptor.map(|index| args.get(index).and_then(|arg| string_literal(arg)));
+                match (name, descriptor) {
+                    (Some(None), _) | (_, Some(None)) => continue,
+                    (None, _) => required.insert(format!("Requirement::Class({:?})", class)),
+                    (Some(Some(name)), descriptor) => required.insert(format!(
+                        "Requirement::{} {{ class: {:?}, name: {:?}, descriptor: {:?} }}",
+                        lookup.kind,
+                        class,
+                        name,
+                        descriptor.flatten()
+                    )),
+                };
+            }
+        }
+        for requirement in java_class_members(text) {
+            required.insert(requirement);
+        }
+    }
+
+    let entries: Vec<String> = required.into_iter().collect();
+    let out = std::path::PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    std::fs::write(out.join("required_members.rs"), format!("&[{}]", entries.join(", "))).expect("OUT_DIR is writable");
+}
+
+fn collect_sources(dir: &Path, sources: &mut Vec<String>) {
+    let mut entries: Vec<_> = std::fs::read_dir(dir).expect("src is readable").map(|entry| entry.expect("src is readable").path()).collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_sources(&path, sources);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            sources.push(std::fs::read_to_string(&path).expect("sources are readable"));
+        }
+    }
+}
+
+// Blanks out comment lines, which contain examples of the calls that are looked for.
+fn strip_comment_lines(text: &str) -> String {
+    text.lines().map(|line| if line.trim_start().starts_with("//") { "" } else { line }).collect::<Vec<_>>().join("\n")
+}
+
+// The arguments of a call, given the text after its opening parenthesis, split at the commas
+// outside of nested parentheses, brackets and strings.
+fn call_arguments(text: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => {
+                args.push(text[start..index].trim());
+                break;
+            }
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(text[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    args
+}
+
+fn string_literal(arg: &str) -> Option<&str> {
+    arg.strip_prefix('"')?.strip_suffix('"').filter(|literal| !literal.contains('"'))
+}
+
+// The class of a class argument, if it is one of the game's.
+fn resolve_class(arg: &str, text: &str, position: usize, classes: &HashMap<String, String>) -> Option<String> {
+    let class = match arg.strip_suffix("::CLASS") {
+        Some("Self") => classes.get(&impl_type(&text[..position])?)?.clone(),
+        Some(name) => classes.get(name)?.clone(),
+        None => string_literal(arg)?.to_string(),
+    };
+    is_game_class(&class).then_some(class)
+}
+
+// Classes of the game itself, the mappings cover nothing else. Mojang's libraries such as authlib
+// keep their names.
+fn is_game_class(class: &str) -> bool {
+    class.starts_with("net/minecraft/") || class.starts_with("com/mojang/blaze3d/")
+}
+
+// Type of the last `impl` block started in `text`, e.g. `Player` for `impl<'a> Player<'a> {`.
+fn impl_type(text: &str) -> Option<String> {
+    let line = text.lines().rev().find(|line| line.starts_with("impl"))?;
+    let line = line.strip_prefix("impl")?;
+    let line = match line.strip_prefix('<') {
+        Some(rest) => &rest[rest.find('>')? + 1..],
+        None => line,
+    };
+    let line = line.rsplit_once(" for ").map_or(line, |(_, ty)| ty).trim_start();
+    let name: String = line.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+    (!name.is_empty()).then_some(name)
+}
+
+// Types declaring their class, either as `pub const CLASS` or through `java_class!`.
+fn declared_classes(text: &str) -> Vec<(String, String)> {
+    let mut classes = Vec::new();
+    for (position, _) in text.match_indices("pub const CLASS: &'static str = ") {
+        let rest = &text[position + "pub const CLASS: &'static str = ".len()..];
+        if let (Some(name), Some(class)) = (impl_type(&text[..position]), rest.split(';').next().and_then(string_literal)) {
+            classes.push((name, class.to_string()));
+        }
+    }
+    for (name, class, _) in java_classes(text) {
+        classes.push((name, class));
+    }
+    classes
+}
+
+// The wrappers of the `java_class!` invocations in `text`: type name, class and member lines.
+fn java_classes(text: &str) -> Vec<(String, String, Vec<String>)> {
+    let mut wrappers = Vec::new();
+    for (position, _) in text.match_indices("java_class! {") {
+        let rest = &text[position..];
+        let Some(struct_start) = rest.find("struct ") else {
+            continue;
+        };
+        let rest = &rest[struct_start + "struct ".len()..];
+        let (Some(open), Some(body_start)) = (rest.find('('), rest.find('{')) else {
+            continue;
+        };
+        let Some(class) = rest[open + 1..].split(')').next().and_then(string_literal) else {
+            continue;
+        };
+        let body = &rest[body_start + 1..];
+        let body = &body[..body.find('}').unwrap_or(body.len())];
+        let members = body.split(';').map(|line| line.trim().to_string()).filter(|line| !line.is_empty()).collect();
+        wrappers.push((rest[..open].trim().to_string(), class.to_string(), members));
+    }
+    wrappers
+}
+
+// Requirements of the `java_class!` members, e.g. `method is_flying: "isFlying" -> bool`.
+fn java_class_members(text: &str) -> Vec<String> {
+    let mut required = Vec::new();
+    for (_, class, members) in java_classes(text) {
+        if !is_game_class(&class) {
+            continue;
+        }
+        required.push(format!("Requirement::Class({:?})", class));
+        for member in members {
+            let words: Vec<&str> = member.split_whitespace().collect();
+            let [kind, _, name, "->", ty] = words[..] else {
+                continue;
+            };
+            let Some(name) = string_literal(name) else {
+                continue;
+            };
+            let signature = match ty {
+                "bool" => "Z",
+                "i32" => "I",
+                "i64" => "J",
+                "f32" => "F",
+                "f64" => "D",
+                _ => continue,
+            };
+            required.push(match kind {
+                "method" => format!("Requirement::Method {{ class: {:?}, name: {:?}, descriptor: Some(\"(){}\") }}", class, name, signature),
+                _ => format!("Requirement::Field {{ class: {:?}, name: {:?}, descriptor: Some({:?}) }}", class, name, signature),
+            });
+        }
+    }
+    required
+}
@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::{info, warn};
+
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::text::Text;
+use crate::sdk::mappings;
+use crate::world_change::WorldChange;
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const GIT_HASH: &str = env!("LB_GIT_HASH");
+// Comma separated cargo features the crate was built with, empty without any.
+pub const FEATURES: &str = env!("LB_FEATURES");
+
+static CHAT_BANNER_SHOWN: AtomicBool = AtomicBool::new(false);
+
+// Identity of this build, e.g. `0.1.0 (e1475ef)`.
+pub fn build_id() -> String {
+    format!("{} ({})", VERSION, GIT_HASH)
+}
+
+// Logs the build identity and the environment it runs in. A game version that cannot be read is
+// logged as unknown instead of failing the startup.
+pub fn log_startup(env: &mut JNIEnv) {
+    let game_version = MinecraftClient::get_game_version(env).unwrap_or_else(|e| {
+        let e = e.with_exception(env);
+        warn!("Failed to read the game version: {}", e);
+        String::from("unknown")
+    });
+    let features = if FEATURES.is_empty() { "none" } else { FEATURES };
+    let mappings = match mappings::installed() {
+        Some(mappings) => format!("{} ({})", mappings.namespace, mappings.source),
+        None => String::from("none (yarn names)"),
+    };
+
+    info!("LiquidBounce Lite {}", build_id());
+    info!("Game version: {}, mappings: {}, features: {}", game_version, mappings, features);
+}
+
+// Shows a one line banner in the chat when the player first joins a world. It is shown once per
+// injection, later world changes do nothing.
+pub fn on_world_change(env: &mut JNIEnv, client: &MinecraftClient, change: WorldChange) -> Result<()> {
+    if change != WorldChange::Joined || CHAT_BANNER_SHOWN.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+
+    let result = env.with_local_frame(8, |env| -> Result<()> {
+        let chat_hud = client.get_chat_hud(env)?;
+        let text = Text::literal(env, &format!("§bLiquidBounce Lite §f{}", build_id()))?;
+        chat_hud.add_message(env, &text)?;
+        Ok(())
+    });
+    if result.is_err() {
+        take_java_exception(env);
+    }
+
+    // Marked as shown even on failure, retrying on every world change would only repeat the error.
+    CHAT_BANNER_SHOWN.store(true, Ordering::Relaxed);
+    result
+}
@@ -0,0 +1,95 @@
+use std::collections::VecDeque;
+
+use anyhow::Result;
+use jni::objects::GlobalRef;
+use jni::JNIEnv;
+
+use crate::sdk::game::client::MinecraftClient;
+
+// Lines read from the chat per poll. The game keeps at most 100 lines itself.
+const POLL_LIMIT: usize = 100;
+
+// Collects received chat lines by polling the chat HUD every tick. New lines are found by looking
+// for the newest line seen in the previous poll, which compares line objects rather than text, so
+// a message repeated word for word is still picked up.
+pub struct ChatLog {
+    lines: VecDeque<String>,
+    capacity: usize,
+    last_newest: Option<GlobalRef>,
+    // Whether the first poll has happened.
+    primed: bool,
+}
+
+impl Default for ChatLog {
+    fn default() -> Self {
+        Self::new(200)
+    }
+}
+
+impl ChatLog {
+    // `capacity` is how many lines are kept.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+            last_newest: None,
+            primed: false,
+        }
+    }
+
+    // Returns the lines received since the last poll, oldest first, and adds them to the log. The
+    // first poll only takes note of the chat as it is, so old lines are not reported as new.
+    pub fn poll(&mut self, env: &mut JNIEnv, client: &MinecraftClient) -> Result<Vec<String>> {
+        let (new_lines, newest) = env.with_local_frame(POLL_LIMIT as i32 + 16, |env| -> Result<(Vec<String>, Option<GlobalRef>)> {
+            let chat_hud = client.get_chat_hud(env)?;
+            let messages = chat_hud.get_messages(env, POLL_LIMIT)?;
+
+            let Some(newest) = messages.first() else {
+                return Ok((Vec::new(), None));
+            };
+            let newest = env.new_global_ref(&newest.inner)?;
+
+            if !self.primed {
+                return Ok((Vec::new(), Some(newest)));
+            }
+
+            // Without a last seen line (the chat was empty or cleared) every line is new.
+            let mut new_lines = Vec::new();
+            for message in &messages {
+                if let Some(last_newest) = &self.last_newest {
+                    if env.is_same_object(&message.inner, last_newest)? {
+                        break;
+                    }
+                }
+                let content = message.get_content(env)?;
+                new_lines.push(content.get_string(env)?);
+            }
+            new_lines.reverse();
+
+            Ok((new_lines, Some(newest)))
+        })?;
+
+        self.last_newest = newest;
+        self.primed = true;
+        for line in &new_lines {
+            if self.lines.len() == self.capacity {
+                self.lines.pop_front();
+            }
+            self.lines.push_back(line.clone());
+        }
+
+        Ok(new_lines)
+    }
+
+    // Logged lines, oldest first.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(String::as_str)
+    }
+
+    // Forgets the log and the last seen line, must be called when the player changes worlds or servers.
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.last_newest = None;
+        self.primed = false;
+    }
+}
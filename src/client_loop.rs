@@ -0,0 +1,239 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+use anyhow::Result;
+use jni::objects::GlobalRef;
+use jni::JNIEnv;
+use tracing::{error, info};
+
+use crate::death::DeathDetector;
+use crate::event::{self, KeyEvent, LagbackEvent, TickEvent};
+use crate::keys::KeyPoller;
+use crate::lagback::LagbackDetector;
+use crate::{command, config, hook, keybind, modules, rotation};
+use crate::handles::handles;
+use crate::profiles::{self, profiles, ProfileSelector};
+use crate::sdk::error::take_java_exception;
+use crate::sdk::TransientRetry;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::living_entity::LivingEntity;
+use crate::session_info::{self, sessions};
+use crate::snapshot::TickSnapshot;
+use crate::tps::{self, tps};
+use crate::timing::GameTickDetector;
+use crate::tracking::tracker;
+use crate::world_change::{WorldChange, WorldChangeDetector};
+use crate::{banner, dispatch, notifications, packet_interceptor, render, restoration, timing};
+
+// Local references one tick may hold at once before the frame has to grow.
+const TICK_FRAME_CAPACITY: i32 = 64;
+
+// Ticks the snapshot may fail transiently in a row before the failure is reported, two seconds at
+// 20 TPS. Switching worlds normally resolves within a few ticks.
+const TRANSIENT_TICKS: u64 = 40;
+
+// State the loop carries from one tick to the next.
+struct ClientLoop {
+    client: GlobalRef,
+    world_changes: WorldChangeDetector,
+    keys: KeyPoller,
+    deaths: DeathDetector,
+    game_ticks: GameTickDetector,
+    lagbacks: LagbackDetector,
+    snapshot_retry: TransientRetry,
+    // The last failure that was logged, the same failure on the following ticks is not logged again.
+    last_failure: Option<String>,
+}
+
+// Runs the client on the calling thread, which becomes the tick thread, polling the game every
+// `timing::poll_interval`. Each poll runs the dispatched jobs and reacts to world changes, and the
+// polls that fall on a new game tick post the tick and key events everything else hooks into. Only returns if the client cannot be reached.
+// However the loop ends, by returning or panicking, the registered restorations run before.
+pub fn run(env: &mut JNIEnv) -> Result<()> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| run_loop(env)));
+    // A panic may have left an exception pending, which would fail every undo action.
+    if let Some(exception) = take_java_exception(env) {
+        error!("Exception pending after the client loop stopped: {}", exception);
+    }
+    let restored = restoration::restore_all(env);
+    info!("Client loop stopped, restored {} changes", restored);
+    match result {
+        Ok(result) => result,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+fn run_loop(env: &mut JNIEnv) -> Result<()> {
+    let client = MinecraftClient::get_instance(env)?;
+    let mut client_loop = ClientLoop {
+        client: env.new_global_ref(&client.inner)?,
+        world_changes: WorldChangeDetector::new(),
+        keys: KeyPoller::new(),
+        deaths: DeathDetector::new(),
+        game_ticks: GameTickDetector::new(),
+        lagbacks: LagbackDetector::default(),
+        snapshot_retry: TransientRetry::new(TRANSIENT_TICKS),
+        last_failure: None,
+    };
+    modules::manager::install();
+    rotation::install();
+    keybind::install();
+    command::install();
+    tps::install();
+    if let Err(e) = config::load_and_apply(env, &client) {
+        error!("Failed to load the config, starting with defaults: {:?}", e);
+    }
+    match ProfileSelector::load(&profiles::path()) {
+        Ok(selector) => *profiles() = selector,
+        Err(e) => error!("Failed to load the profile overrides: {:?}", e),
+    }
+    if let Err(e) = render::overlay::start(env, &client) {
+        error!("Failed to start the overlay: {:?}", e);
+    }
+    if let Err(e) = hook::swap_buffers::install(env) {
+        error!("Failed to hook the buffer swap, render events are unavailable: {:?}", e);
+    }
+    env.delete_local_ref(client.inner)?;
+    info!("Client loop started");
+
+    loop {
+        let body_start = Instant::now();
+        // Every tick gets its own frame, so references leaked by a handler are freed with it.
+        let result = env.with_local_frame(TICK_FRAME_CAPACITY, |env| client_loop.tick(env));
+        client_loop.report(env, result);
+        timing::sleep_remaining(body_start);
+    }
+}
+
+impl ClientLoop {
+    fn tick(&mut self, env: &mut JNIEnv) -> Result<()> {
+        let client = MinecraftClient::new(env.new_local_ref(&self.client)?);
+
+        dispatch::drain(env, &client);
+
+        if let Some(change) = self.world_changes.update(env, &client)? {
+            self.lagbacks.reset();
+            on_world_change(env, &client, change);
+            if let Err(e) = banner::on_world_change(env, &client, change) {
+                error!("Failed to show the chat banner: {:?}", e);
+            }
+            // Every connection has its own pipeline, a switched world may come with a new one.
+            if change != WorldChange::Left {
+                if let Err(e) = packet_interceptor::ensure_injected(env, &client) {
+                    let e = take_java_exception(env).unwrap_or(e);
+                    error!("Failed to intercept packets of the connection: {}", e);
+                }
+            }
+        }
+
+        let player_age = env.with_local_frame(4, |env| -> crate::sdk::Result<Option<i32>> {
+            match client.get_player(env)? {
+                Some(player) => Ok(Some(player.as_entity_ref().get_age(env)?)),
+                None => Ok(None),
+            }
+        })?;
+        if !self.game_ticks.update(player_age, Instant::now()) {
+            return Ok(());
+        }
+        let tick = timing::advance_tick();
+        timing::run_scheduled(env);
+
+        let Some(snapshot) = self.snapshot_retry.attempt(env, |env| TickSnapshot::capture(env, &client))? else {
+            // The world is being swapped, everything below needs a consistent snapshot.
+            return Ok(());
+        };
+        self.deaths.update(&snapshot);
+        if let Some(lagback) = self.lagbacks.update(&snapshot) {
+            event::post(env, &mut LagbackEvent { tick, lagback });
+        }
+        if let Err(e) = sample_world(env, &client, &snapshot) {
+            let e = take_java_exception(env).unwrap_or(e);
+            error!("Failed to sample the world: {}", e);
+        }
+
+        if snapshot.window_focused {
+            for (key, pressed) in self.keys.poll() {
+                event::post(env, &mut KeyEvent { key, pressed, screen_open: snapshot.screen_open });
+            }
+        } else {
+            self.keys.reset();
+        }
+
+        event::post(env, &mut TickEvent::new(tick, snapshot, self.client.clone()));
+
+        notifications::dispatch(env, &client)?;
+        config::autosave();
+        Ok(())
+    }
+
+    // Logs a failed tick, unless it failed the same way as the tick before.
+    fn report(&mut self, env: &mut JNIEnv, result: Result<()>) {
+        let failure = match result {
+            Ok(()) => {
+                if self.last_failure.take().is_some() {
+                    info!("Ticks are running again");
+                }
+                return;
+            }
+            Err(e) => match take_java_exception(env) {
+                Some(exception) => format!("{:?} ({})", e, exception),
+                None => format!("{:?}", e),
+            },
+        };
+
+        if self.last_failure.as_ref() != Some(&failure) {
+            error!("Tick {} failed, further identical failures are not logged: {}", timing::current_tick(), failure);
+            self.last_failure = Some(failure);
+        }
+    }
+}
+
+// Drops everything kept about the previous world, its entity ids and objects mean nothing in the new
+// one, and moves the session and profile along.
+fn on_world_change(env: &mut JNIEnv, client: &MinecraftClient, change: WorldChange) {
+    tracker().clear();
+    tps().reset();
+    handles().clear();
+
+    let mut sessions = sessions();
+    if let Err(e) = sessions.on_world_change(env, client, change) {
+        error!("Failed to track the session: {:?}", e);
+    }
+    if let Err(e) = sessions.save(&session_info::path()) {
+        error!("Failed to save the session times: {:?}", e);
+    }
+    drop(sessions);
+
+    let switch = profiles().on_world_change(env, client, change);
+    match switch {
+        Ok(Some(switch)) => {
+            if let Err(e) = profiles::apply_switch(env, client, &switch) {
+                error!("Failed to apply profile {}: {:?}", switch.to, e);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => error!("Failed to select the profile: {:?}", e),
+    }
+}
+
+// Tells the TPS estimate whether this is singleplayer and feeds the living entities' positions to the
+// tracker.
+fn sample_world(env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot) -> crate::sdk::Result<()> {
+    env.with_local_frame(8, |env| -> crate::sdk::Result<()> {
+        let Some(world) = client.get_world(env)? else {
+            return Ok(());
+        };
+
+        tps().set_singleplayer(snapshot.singleplayer);
+
+        let mut tracker = tracker();
+        world.for_each_entity(env, |env, entity| {
+            if LivingEntity::is_living(env, entity)? {
+                tracker.record(entity.get_id(env)?, entity.get_pos(env)?);
+            }
+            Ok(())
+        })?;
+        tracker.end_tick();
+        Ok(())
+    })
+}
@@ -0,0 +1,451 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{anyhow, bail, Result};
+use jni::JNIEnv;
+use tracing::{info, warn};
+
+use crate::event::{self, PacketDirection, PacketEvent};
+use crate::friends::friends;
+use crate::keybind::{self, keybinds};
+use crate::modules::{self, manager};
+use crate::profiles::{self, profiles};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::packet::{ChatMessageC2SPacket, MessageAcknowledgmentC2SPacket};
+use crate::sdk::game::packet_log;
+use crate::sdk::input;
+use crate::session_info::{self, sessions};
+use crate::{config, death, dispatch, dump, notifications, restoration, selftest, snapshot};
+
+// Chat messages starting with this are run as client commands and never reach the server.
+pub const PREFIX: char = '.';
+
+// Name, usage and description of every command, in the order `.help` lists them.
+pub const COMMANDS: &[(&str, &str, &str)] = &[
+    ("help", ".help", "Lists the commands"),
+    ("modules", ".modules", "Lists the modules and whether they are enabled"),
+    ("toggle", ".toggle <module>", "Enables or disables a module"),
+    ("bind", ".bind <module> <key|none>", "Sets the key that toggles a module"),
+    ("binds", ".binds", "Lists the keybinds"),
+    ("set", ".set <module>[.<setting>] [value]", "Shows or changes module settings"),
+    ("lastdeath", ".lastdeath", "Shows where you died last"),
+    ("dump", ".dump", "Writes a diagnostic dump of the client to a file"),
+    ("session", ".session", "Shows the current session and the play time on this server"),
+    ("packetlog", ".packetlog [on|off]", "Logs the packets the client sends"),
+    ("selftest", ".selftest", "Checks the game wrappers against the running game"),
+    ("profile", ".profile [list|save <name>|<server> <profile|none>]", "Saves, lists or assigns settings profiles to servers"),
+    ("friends", ".friends [add|remove|list] [name]", "Manages the players modules leave alone"),
+    ("press", ".press <key>", "Presses and releases a key in the game window"),
+    ("suspend", ".suspend [on|off]", "Pauses automation while the game is paused or unfocused"),
+    ("restoreall", ".restoreall", "Undoes every change modules made to the game"),
+];
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Help,
+    Modules,
+    Toggle { module: String },
+    // `key` is `None` to remove the bind.
+    Bind { module: String, key: Option<u32> },
+    Binds,
+    // Without a setting every setting is listed, without a value the setting is shown.
+    Set { module: String, setting: Option<String>, value: Option<String> },
+    LastDeath,
+    Dump,
+    Session,
+    // Without a value the packet log is flipped.
+    PacketLog { enabled: Option<bool> },
+    Selftest,
+    // Without arguments the active profile and the overrides are listed, `None` removes the override.
+    Profile { server_profile: Option<(String, Option<String>)> },
+    ProfileSave { name: String },
+    ProfileList,
+    FriendAdd { name: String },
+    FriendRemove { name: String },
+    Friends,
+    Press { key: u32 },
+    // Without a value the setting is flipped.
+    Suspend { enabled: Option<bool> },
+    RestoreAll,
+}
+
+impl Command {
+    // Parses the input without the prefix, e.g. `bind flight r`. Command names ignore case.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut words = input.split_whitespace();
+        let name = words.next().ok_or_else(|| anyhow!("Type {}help for a list of commands", PREFIX))?.to_ascii_lowercase();
+        let usage = || COMMANDS.iter().find(|(command, ..)| *command == name).map_or("", |(_, usage, _)| *usage);
+        let mut argument = |what: &str| words.next().map(str::to_string).ok_or_else(|| anyhow!("Missing {}, usage: {}", what, usage()));
+
+        let command = match name.as_str() {
+            "help" => Command::Help,
+            "modules" => Command::Modules,
+            "toggle" => Command::Toggle { module: argument("module")? },
+            "bind" => {
+                let module = argument("module")?;
+                let key = argument("key")?;
+                let key = match key.to_ascii_lowercase().as_str() {
+                    "none" => None,
+                    _ => Some(keybind::parse_key(&key).ok_or_else(|| anyhow!("Unknown key {}", key))?),
+                };
+                Command::Bind { module, key }
+            }
+            "binds" => Command::Binds,
+            "set" => {
+                let target = argument("module")?;
+                let (module, setting) = match target.split_once('.') {
+                    Some((module, setting)) => (module.to_string(), Some(setting.to_string())),
+                    None => (target, None),
+                };
+                // Text values may contain spaces.
+                let value = words.collect::<Vec<_>>().join(" ");
+                Command::Set { module, setting, value: (!value.is_empty()).then_some(value) }
+            }
+            "lastdeath" => Command::LastDeath,
+            "dump" => Command::Dump,
+            "session" => Command::Session,
+            "packetlog" => Command::PacketLog { enabled: parse_switch(words.next(), usage())? },
+            "suspend" => Command::Suspend { enabled: parse_switch(words.next(), usage())? },
+            "restoreall" => Command::RestoreAll,
+            "selftest" => Command::Selftest,
+            "profile" => match words.collect::<Vec<_>>()[..] {
+                [] => Command::Profile { server_profile: None },
+                [action] if action.eq_ignore_ascii_case("list") => Command::ProfileList,
+                [action, name] if action.eq_ignore_ascii_case("save") => Command::ProfileSave { name: name.to_string() },
+                [server, profile] => {
+                    let profile = (!profile.eq_ignore_ascii_case("none")).then(|| profile.to_string());
+                    Command::Profile { server_profile: Some((server.to_string(), profile)) }
+                }
+                _ => bail!("Expected a server and a profile, usage: {}", usage()),
+            },
+            "friends" => match argument("action").ok().map(|action| action.to_ascii_lowercase()).as_deref() {
+                None | Some("list") => Command::Friends,
+                Some("add") => Command::FriendAdd { name: argument("name")? },
+                Some("remove") => Command::FriendRemove { name: argument("name")? },
+                Some(action) => bail!("Unknown action {}, usage: {}", action, usage()),
+            },
+            "press" => {
+                let key = argument("key")?;
+                Command::Press { key: keybind::parse_key(&key).ok_or_else(|| anyhow!("Unknown key {}", key))? }
+            }
+            _ => bail!("Unknown command {}{}, type {}help for a list", PREFIX, name, PREFIX),
+        };
+        Ok(command)
+    }
+
+    // Runs the command on the tick thread and returns the lines to show in chat.
+    pub fn execute(self, env: &mut JNIEnv, client: &MinecraftClient) -> Result<Vec<String>> {
+        match self {
+            Command::Help => Ok(COMMANDS.iter().map(|(_, usage, description)| format!("§f{} §7- {}", usage, description)).collect()),
+            Command::Modules => {
+                let manager = manager();
+                Ok(modules::format_list(&manager.infos(), |info| manager.is_enabled(info.name)).lines().map(str::to_string).collect())
+            }
+            Command::Toggle { module } => {
+                let name = module_name(&module)?;
+                let enabled = manager().toggle(env, client, name)?;
+                Ok(vec![format!("§f{} {}", name, if enabled { "§aenabled" } else { "§cdisabled" })])
+            }
+            Command::Bind { module, key } => {
+                let name = module_name(&module)?;
+                match key {
+                    Some(key) => {
+                        keybinds().bind(name, key);
+                        Ok(vec![format!("§f{} §7is toggled by §f{}", name, keybind::key_name(key))])
+                    }
+                    None if keybinds().unbind(name) => Ok(vec![format!("§f{} §7has no key anymore", name)]),
+                    None => Ok(vec![format!("§f{} §7has no key", name)]),
+                }
+            }
+            Command::Binds => {
+                let binds = keybinds().list().into_iter().map(|(module, key)| format!("§f{} §7- §f{}", keybind::key_name(key), module)).collect::<Vec<_>>();
+                if binds.is_empty() {
+                    return Ok(vec![String::from("§7No keybinds")]);
+                }
+                Ok(binds)
+            }
+            Command::Set { module, setting, value } => set(&module, setting.as_deref(), value.as_deref()),
+            Command::LastDeath => {
+                let Some(death) = death::last_death() else {
+                    return Ok(vec![String::from("§7No death recorded")]);
+                };
+                let pos = death.pos;
+                let location = format!("§7You died at §fX: {:.1} Y: {:.1} Z: {:.1}", pos.x, pos.y, pos.z);
+                Ok(vec![match death.dimension {
+                    Some(dimension) => format!("{} §7in the §f{:?}", location, dimension),
+                    None => location,
+                }])
+            }
+            Command::Dump => {
+                let path = dump::write_dump(env, client, &config::client_dir())?;
+                Ok(vec![format!("§7Dump written to §f{}", path.display())])
+            }
+            Command::Session => session(),
+            Command::PacketLog { enabled } => {
+                let enabled = enabled.unwrap_or(!packet_log::is_enabled());
+                packet_log::set_enabled(enabled);
+                Ok(vec![format!("§7Packet log {}", if enabled { "§aenabled" } else { "§cdisabled" })])
+            }
+            Command::Selftest => Ok(selftest::format_report(&selftest::run(env)).lines().map(|line| format!("§7{}", line)).collect()),
+            Command::Profile { server_profile } => profile(server_profile),
+            Command::ProfileSave { name } => {
+                profiles::save_profile(&name)?;
+                Ok(vec![format!("§7Saved the modules as profile §f{}", name)])
+            }
+            Command::ProfileList => {
+                let active = profiles().active().to_string();
+                let saved = profiles::saved_profiles()?;
+                if saved.is_empty() {
+                    return Ok(vec![format!("§7No saved profiles, §f{} §7is active", active)]);
+                }
+                Ok(saved
+                    .into_iter()
+                    .map(|name| if name == active { format!("§a{} §7(active)", name) } else { format!("§f{}", name) })
+                    .collect())
+            }
+            Command::FriendAdd { name } => {
+                // The UUID is only known while the friend is online on the current server.
+                let added = env.with_local_frame(4, |env| -> Result<bool> {
+                    let Some(network_handler) = client.get_network_handler(env)? else {
+                        return Ok(false);
+                    };
+                    friends().add_by_name(env, &network_handler, &name)?;
+                    Ok(true)
+                })?;
+                if !added {
+                    friends().add(&name, None);
+                }
+                config::mark_dirty();
+                Ok(vec![format!("§f{} §7is a friend now", name)])
+            }
+            Command::FriendRemove { name } => {
+                if !friends().remove(&name) {
+                    return Ok(vec![format!("§f{} §7is not a friend", name)]);
+                }
+                config::mark_dirty();
+                Ok(vec![format!("§f{} §7is no longer a friend", name)])
+            }
+            Command::Press { key } => {
+                // Posted as window messages, so the key reaches whatever screen is open like a real one.
+                let window = client.get_window(env)?;
+                let hwnd = window.get_hwnd(env)?;
+                env.delete_local_ref(window.inner)?;
+                input::send_key(hwnd, key, true)?;
+                input::send_key(hwnd, key, false)?;
+                Ok(vec![format!("§7Pressed §f{}", keybind::key_name(key))])
+            }
+            Command::Suspend { enabled } => {
+                let enabled = enabled.unwrap_or(!snapshot::suspend_when_inactive());
+                snapshot::set_suspend_when_inactive(enabled);
+                let state = if enabled { "§apaused" } else { "§ckept running" };
+                Ok(vec![format!("§7Automation is {} §7while the game is paused or unfocused", state)])
+            }
+            Command::RestoreAll => {
+                let restored = restoration::restore_all(env);
+                Ok(vec![format!("§7Restored §f{} §7changes", restored)])
+            }
+            Command::Friends => {
+                let friends = friends();
+                if friends.list().is_empty() {
+                    return Ok(vec![String::from("§7No friends")]);
+                }
+                Ok(friends
+                    .list()
+                    .iter()
+                    .map(|friend| match &friend.uuid {
+                        Some(uuid) => format!("§f{} §7({})", friend.name, uuid),
+                        None => format!("§f{}", friend.name),
+                    })
+                    .collect())
+            }
+        }
+    }
+}
+
+// Reads an optional `on` or `off`.
+fn parse_switch(word: Option<&str>, usage: &str) -> Result<Option<bool>> {
+    match word.map(str::to_ascii_lowercase).as_deref() {
+        None => Ok(None),
+        Some("on") => Ok(Some(true)),
+        Some("off") => Ok(Some(false)),
+        Some(value) => bail!("Expected on or off, not {}, usage: {}", value, usage),
+    }
+}
+
+fn session() -> Result<Vec<String>> {
+    let sessions = sessions();
+    let Some(session) = sessions.current() else {
+        return Ok(vec![String::from("§7Not in a session")]);
+    };
+
+    let played = session.duration().as_secs();
+    let saved = session_info::load_totals(&session_info::path())?.get(&session.server).copied().unwrap_or(0);
+    Ok(vec![
+        format!("§7Playing as §f{} §7on §f{}", session.username, session.server),
+        format!("§7Session §f{} §7total on this server §f{}", format_duration(played), format_duration(saved + played)),
+    ])
+}
+
+fn format_duration(seconds: u64) -> String {
+    format!("{}h {:02}m {:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn profile(server_profile: Option<(String, Option<String>)>) -> Result<Vec<String>> {
+    let mut profiles = profiles();
+    let Some((server, profile)) = server_profile else {
+        let mut lines = vec![format!("§7Active profile §f{}", profiles.active())];
+        lines.extend(profiles.overrides().map(|(server, profile)| format!("§f{} §7uses §f{}", server, profile)));
+        return Ok(lines);
+    };
+
+    let line = match profile {
+        Some(profile) => {
+            profiles.set_override(&server, &profile);
+            format!("§f{} §7now uses §f{}", server, profile)
+        }
+        None if profiles.remove_override(&server) => format!("§f{} §7uses the default profile again", server),
+        None => return Ok(vec![format!("§f{} §7has no profile override", server)]),
+    };
+    // Takes effect on the next world change, like joining the server does.
+    profiles.save(&profiles::path())?;
+    Ok(vec![line])
+}
+
+fn set(module: &str, setting: Option<&str>, value: Option<&str>) -> Result<Vec<String>> {
+    let name = module_name(module)?;
+    let settings = manager().settings(name).unwrap_or_default();
+
+    let Some(setting) = setting else {
+        if settings.is_empty() {
+            return Ok(vec![format!("§f{} §7has no settings", name)]);
+        }
+        return Ok(settings.iter().map(|(setting, value)| format!("§f{}.{} §7= §f{}", name, setting, value)).collect());
+    };
+
+    let (setting, current) = settings
+        .into_iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(setting))
+        .ok_or_else(|| anyhow!("{} has no setting {}", name, setting))?;
+    let Some(value) = value else {
+        return Ok(vec![format!("§f{}.{} §7= §f{}", name, setting, current)]);
+    };
+
+    let parsed = current.parse_like(value).ok_or_else(|| anyhow!("{}.{} takes a {}, not {}", name, setting, current.kind(), value))?;
+    manager().set_setting(name, setting, parsed.clone())?;
+    Ok(vec![format!("§f{}.{} §7set to §f{}", name, setting, parsed)])
+}
+
+// Resolves a module name typed in any case to the registered one.
+fn module_name(module: &str) -> Result<&'static str> {
+    manager()
+        .infos()
+        .into_iter()
+        .find(|info| info.name.eq_ignore_ascii_case(module))
+        .map(|info| info.name)
+        .ok_or_else(|| anyhow!("Unknown module {}", module))
+}
+
+// Parses and runs a command, printing its output or error into the chat.
+pub fn run(env: &mut JNIEnv, client: &MinecraftClient, input: &str) -> Result<()> {
+    info!("Running command {}{}", PREFIX, input);
+    let lines = match Command::parse(input).and_then(|command| command.execute(env, client)) {
+        Ok(lines) => lines,
+        Err(e) => vec![format!("§c{}", e)],
+    };
+    for line in lines {
+        notifications::chat(env, client, &line)?;
+    }
+    Ok(())
+}
+
+// Completions for the word being typed at the end of `input`, which includes the prefix. Each
+// completion is the full word, for a chat suggestion list to offer.
+pub fn complete(input: &str) -> Vec<String> {
+    let Some(input) = input.strip_prefix(PREFIX) else {
+        return Vec::new();
+    };
+    let words: Vec<&str> = input.split(' ').collect();
+    let current = words.last().copied().unwrap_or_default().to_ascii_lowercase();
+    let matching = |candidates: Vec<String>| -> Vec<String> {
+        candidates.into_iter().filter(|candidate| candidate.to_ascii_lowercase().starts_with(&current)).collect()
+    };
+    let module_names = || manager().infos().iter().map(|info| info.name.to_string()).collect::<Vec<_>>();
+
+    match (words.len(), words[0].to_ascii_lowercase().as_str()) {
+        (1, _) => matching(COMMANDS.iter().map(|(name, ..)| name.to_string()).collect()),
+        (2, "toggle" | "bind") => matching(module_names()),
+        (2, "set") => {
+            let manager = manager();
+            let mut targets = Vec::new();
+            for info in manager.infos() {
+                targets.push(info.name.to_string());
+                for (setting, _) in manager.settings(info.name).unwrap_or_default() {
+                    targets.push(format!("{}.{}", info.name, setting));
+                }
+            }
+            drop(manager);
+            matching(targets)
+        }
+        (2, "friends") => matching(vec![String::from("add"), String::from("remove"), String::from("list")]),
+        (3, "friends") if words[1].eq_ignore_ascii_case("remove") => {
+            matching(friends().list().iter().map(|friend| friend.name.clone()).collect())
+        }
+        (2, "profile") => matching(vec![String::from("list"), String::from("save")]),
+        (3, "profile") if words[1].eq_ignore_ascii_case("save") => matching(profiles::saved_profiles().unwrap_or_default()),
+        (2, "packetlog" | "suspend") => matching(vec![String::from("on"), String::from("off")]),
+        (2, "press") => matching(keybind::key_names()),
+        (3, "bind") => {
+            let mut keys = keybind::key_names();
+            keys.push(String::from("none"));
+            matching(keys)
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Runs chat messages starting with the prefix as commands instead of sending them. Does nothing
+// when called again.
+pub fn install() {
+    if INSTALLED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    event::bus().subscribe::<PacketEvent, _>("Commands", intercept);
+}
+
+// Catches the chat packet on the Netty thread and hands the command to the tick thread.
+fn intercept(env: &mut JNIEnv, event: &mut PacketEvent) -> Result<()> {
+    if event.direction != PacketDirection::Outbound || !event.is(env, ChatMessageC2SPacket::CLASS)? {
+        return Ok(());
+    }
+
+    let packet = ChatMessageC2SPacket::new(env.new_local_ref(&event.packet)?);
+    let message = packet.get_chat_message(env)?;
+    let Some(input) = message.strip_prefix(PREFIX) else {
+        env.delete_local_ref(packet.inner)?;
+        return Ok(());
+    };
+    let acknowledged = packet.get_acknowledgment_offset(env)?;
+    env.delete_local_ref(packet.inner)?;
+    event.cancel();
+
+    let input = input.to_string();
+    let queued = dispatch::dispatch("Commands", move |context| {
+        // The dropped packet acknowledged messages the server now waits for, so they are
+        // acknowledged on their own. Otherwise servers enforcing secure chat kick the player.
+        if acknowledged > 0 {
+            context.env.with_local_frame(4, |env| -> Result<()> {
+                if let Some(network_handler) = context.client.get_network_handler(env)? {
+                    let packet = MessageAcknowledgmentC2SPacket::new(env, acknowledged)?;
+                    network_handler.send_packet(env, &packet.inner)?;
+                }
+                Ok(())
+            })?;
+        }
+        run(context.env, context.client, &input)
+    });
+    if let Err(e) = queued {
+        warn!("Dropped command {}: {}", message, e);
+    }
+    Ok(())
+}
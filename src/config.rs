@@ -0,0 +1,333 @@
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use jni::JNIEnv;
+use tracing::{error, info, warn};
+
+use crate::friends::{friends, Friend, FriendList};
+use crate::keybind::{self, keybinds};
+use crate::modules::{manager, SettingValue};
+use crate::profiles::profiles;
+use crate::sdk::game::client::MinecraftClient;
+
+// Keeps the module states, keybinds and module settings across injections in a TOML file, one table
+// per module, and the friends in a table of their own mapping names to UUIDs (empty if unresolved):
+//
+//   [Flight]
+//   enabled = true
+//   key = "f"
+//   speed = 0.5
+//
+//   [friends]
+//   Notch = "069a79f4-44e9-4726-a5be-fca90e38aaf5"
+//
+// Only this subset of TOML is written and read: tables, booleans, numbers and basic strings.
+
+pub const FILE_NAME: &str = "config.toml";
+
+// Name of the friends table. Module names start with an uppercase letter, so it cannot clash.
+const FRIENDS_TABLE: &str = "friends";
+
+// Changes are saved this long after the first unsaved one, so a burst of changes, e.g. dragging a
+// value through many steps, writes the file once.
+pub const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+// When the first unsaved change was made, `None` while everything is saved.
+static DIRTY_SINCE: Mutex<Option<Instant>> = Mutex::new(None);
+
+// Directory the client keeps its files in, `%APPDATA%\LiquidBounceLite` on Windows.
+pub fn client_dir() -> PathBuf {
+    std::env::var_os("APPDATA").map(PathBuf::from).unwrap_or_else(std::env::temp_dir).join("LiquidBounceLite")
+}
+
+pub fn path() -> PathBuf {
+    client_dir().join(FILE_NAME)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleConfig {
+    pub name: String,
+    pub enabled: bool,
+    pub key: Option<u32>,
+    pub settings: Vec<(String, SettingValue)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Config {
+    pub modules: Vec<ModuleConfig>,
+    pub friends: Vec<Friend>,
+}
+
+impl Config {
+    // The current state of every registered module.
+    pub fn capture() -> Self {
+        let manager = manager();
+        let keybinds = keybinds();
+        let modules = manager
+            .infos()
+            .into_iter()
+            .map(|info| ModuleConfig {
+                name: info.name.to_string(),
+                enabled: manager.is_enabled(info.name),
+                key: keybinds.key_of(info.name),
+                settings: manager
+                    .settings(info.name)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value))
+                    .collect(),
+            })
+            .collect();
+        Self { modules, friends: friends().list().to_vec() }
+    }
+
+    // Reads the config at `path`, `None` if there is none yet.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Some(Self::parse(&text)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // Writes the config to `path` through a temporary file, so a crash while saving cannot leave a
+    // truncated config behind.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temporary = path.with_extension("toml.tmp");
+        fs::write(&temporary, self.to_toml())?;
+        fs::rename(&temporary, path)?;
+        Ok(())
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modules: Vec<ModuleConfig> = Vec::new();
+        let mut friends = Vec::new();
+        // Whether the values belong to the friends table rather than the last module.
+        let mut in_friends = false;
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |reason: &str| anyhow!("Line {} of the config: {}", index + 1, reason);
+
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                in_friends = name.trim() == FRIENDS_TABLE;
+                if !in_friends {
+                    modules.push(ModuleConfig { name: name.trim().to_string(), enabled: false, key: None, settings: Vec::new() });
+                }
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| error("expected `name = value`"))?;
+            if in_friends {
+                let Some(SettingValue::Text(uuid)) = parse_value(value.trim()) else {
+                    return Err(error("a friend's UUID must be a string"));
+                };
+                friends.push(Friend { name: key.trim().to_string(), uuid: (!uuid.is_empty()).then_some(uuid) });
+                continue;
+            }
+            let module = modules.last_mut().ok_or_else(|| error("value outside of a module table"))?;
+            let value = parse_value(value.trim()).ok_or_else(|| error("unreadable value"))?;
+            match (key.trim(), value) {
+                ("enabled", SettingValue::Bool(enabled)) => module.enabled = enabled,
+                ("enabled", _) => return Err(error("`enabled` must be true or false")),
+                ("key", SettingValue::Text(name)) => {
+                    module.key = Some(keybind::parse_key(&name).ok_or_else(|| error(&format!("unknown key {}", name)))?)
+                }
+                ("key", _) => return Err(error("`key` must be a key name")),
+                (setting, value) => module.settings.push((setting.to_string(), value)),
+            }
+        }
+        Ok(Self { modules, friends })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::from("# Written by LiquidBounce Lite, changes made while the client runs are overwritten.\n");
+        for module in &self.modules {
+            let _ = writeln!(toml, "\n[{}]", module.name);
+            let _ = writeln!(toml, "enabled = {}", module.enabled);
+            if let Some(key) = module.key {
+                let _ = writeln!(toml, "key = {}", format_value(&SettingValue::Text(keybind::key_name(key))));
+            }
+            for (name, value) in &module.settings {
+                let _ = writeln!(toml, "{} = {}", name, format_value(value));
+            }
+        }
+        if !self.friends.is_empty() {
+            let _ = writeln!(toml, "\n[{}]", FRIENDS_TABLE);
+            for friend in &self.friends {
+                let uuid = SettingValue::Text(friend.uuid.clone().unwrap_or_default());
+                let _ = writeln!(toml, "{} = {}", friend.name, format_value(&uuid));
+            }
+        }
+        toml
+    }
+
+    // Replaces the friends and keybinds, then applies the modules, see `apply_modules`.
+    pub fn apply(&self, env: &mut JNIEnv, client: &MinecraftClient) {
+        let mut friend_list = FriendList::new();
+        for friend in &self.friends {
+            friend_list.add(&friend.name, friend.uuid.clone());
+        }
+        *friends() = friend_list;
+
+        for module in self.modules.iter().filter(|module| manager().contains(&module.name)) {
+            match module.key {
+                Some(key) => keybinds().bind(&module.name, key),
+                None => {
+                    keybinds().unbind(&module.name);
+                }
+            }
+        }
+
+        self.apply_modules(env, client);
+    }
+
+    // Applies settings, then enables and disables modules to match through the same path as a
+    // manual toggle, so their hooks run. This is all a settings profile holds. Unknown modules and
+    // settings, e.g. from an older version, are skipped with a warning.
+    pub fn apply_modules(&self, env: &mut JNIEnv, client: &MinecraftClient) {
+        for module in &self.modules {
+            if !manager().contains(&module.name) {
+                warn!("Skipping unknown module {} in the config", module.name);
+                continue;
+            }
+
+            for (setting, value) in &module.settings {
+                if let Err(e) = manager().set_setting(&module.name, setting, value.clone()) {
+                    warn!("Skipping setting {}.{} in the config: {}", module.name, setting, e);
+                }
+            }
+
+            if let Err(e) = manager().set_enabled(env, client, &module.name, module.enabled) {
+                error!("Failed to restore the state of {}: {:?}", module.name, e);
+            }
+        }
+    }
+}
+
+// Loads the config and applies it, called once at attach time. What was just loaded is not saved
+// back.
+pub fn load_and_apply(env: &mut JNIEnv, client: &MinecraftClient) -> Result<()> {
+    let path = path();
+    let Some(config) = Config::load(&path)? else {
+        info!("No config at {}, starting with defaults", path.display());
+        return Ok(());
+    };
+
+    config.apply(env, client);
+    *dirty_since() = None;
+    info!("Loaded the config of {} modules from {}", config.modules.len(), path.display());
+    Ok(())
+}
+
+// Records an unsaved change. Called by whatever changes module states, keybinds, settings or friends.
+// The active profile counts as changed too, until it is saved or another one is applied.
+pub fn mark_dirty() {
+    dirty_since().get_or_insert_with(Instant::now);
+    profiles().mark_dirty();
+}
+
+// Saves the config once the first unsaved change is `SAVE_DELAY` old. Called every tick.
+pub fn autosave() {
+    {
+        let mut dirty_since = dirty_since();
+        match *dirty_since {
+            Some(since) if since.elapsed() >= SAVE_DELAY => *dirty_since = None,
+            _ => return,
+        }
+    }
+
+    let path = path();
+    if let Err(e) = Config::capture().save(&path) {
+        error!("Failed to save the config to {}: {:?}", path.display(), e);
+    }
+}
+
+fn dirty_since() -> std::sync::MutexGuard<'static, Option<Instant>> {
+    DIRTY_SINCE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn format_value(value: &SettingValue) -> String {
+    match value {
+        SettingValue::Bool(value) => value.to_string(),
+        // Debug keeps the fractional part of whole numbers, so `1.0` stays a float in TOML.
+        SettingValue::Number(value) => format!("{:?}", value),
+        SettingValue::Text(value) => format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"")),
+    }
+}
+
+fn parse_value(value: &str) -> Option<SettingValue> {
+    match value {
+        "true" => return Some(SettingValue::Bool(true)),
+        "false" => return Some(SettingValue::Bool(false)),
+        _ => {}
+    }
+
+    if let Some(quoted) = value.strip_prefix('"').and_then(|value| value.strip_suffix('"')) {
+        return unescape(quoted).map(SettingValue::Text);
+    }
+    value.parse::<f64>().ok().filter(|number| number.is_finite()).map(SettingValue::Number)
+}
+
+fn unescape(quoted: &str) -> Option<String> {
+    let mut text = String::new();
+    let mut characters = quoted.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => match characters.next()? {
+                '\\' => text.push('\\'),
+                '"' => text.push('"'),
+                _ => return None,
+            },
+            '"' => return None,
+            character => text.push(character),
+        }
+    }
+    Some(text)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn friends_round_trip() {
+        let config = Config {
+            modules: vec![ModuleConfig {
+                name: "Flight".to_string(),
+                enabled: true,
+                key: None,
+                settings: vec![("speed".to_string(), SettingValue::Number(0.5))],
+            }],
+            friends: vec![
+                Friend { name: "Notch".to_string(), uuid: Some("069a79f4-44e9-4726-a5be-fca90e38aaf5".to_string()) },
+                Friend { name: "jeb_".to_string(), uuid: None },
+            ],
+        };
+        assert_eq!(Config::parse(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn friends_table_does_not_end_the_module_list() {
+        let config = Config::parse("[friends]\nNotch = \"\"\n\n[Flight]\nenabled = true\n").unwrap();
+        assert_eq!(config.friends, [Friend { name: "Notch".to_string(), uuid: None }]);
+        assert_eq!(config.modules.len(), 1);
+        assert!(config.modules[0].enabled);
+    }
+
+    #[test]
+    fn friend_without_a_string_is_rejected() {
+        assert!(Config::parse("[friends]\nNotch = true\n").is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use crate::sdk::math::coords::Dimension;
+use crate::sdk::math::DVec3;
+use crate::snapshot::TickSnapshot;
+
+// Consecutive ticks the health has to stay at zero before a death is reported. Lag can make the
+// health flicker to zero for a single tick without the player actually dying.
+const DEATH_CONFIRM_TICKS: u32 = 2;
+
+// Where the player died last, kept across world changes so it can be printed later.
+static LAST_DEATH: Mutex<Option<PlayerDeath>> = Mutex::new(None);
+
+pub fn last_death() -> Option<PlayerDeath> {
+    *LAST_DEATH.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerDeath {
+    pub pos: DVec3,
+    // `None` if the world could not be read when the death was detected.
+    pub dimension: Option<Dimension>,
+}
+
+// Detects the transition from alive to dead in the tick snapshots.
+#[derive(Debug, Default)]
+pub struct DeathDetector {
+    dead_ticks: u32,
+    reported: bool,
+}
+
+impl DeathDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Feeds the snapshot of the current tick, returns the death once it is confirmed. Only one death
+    // is reported until the player is alive again.
+    pub fn update(&mut self, snapshot: &TickSnapshot) -> Option<PlayerDeath> {
+        let Some(player) = snapshot.player else {
+            self.dead_ticks = 0;
+            return None;
+        };
+
+        if player.health > 0.0 {
+            self.dead_ticks = 0;
+            self.reported = false;
+            return None;
+        }
+
+        self.dead_ticks += 1;
+        if self.reported || self.dead_ticks < DEATH_CONFIRM_TICKS {
+            return None;
+        }
+        self.reported = true;
+
+        let death = PlayerDeath { pos: player.position, dimension: snapshot.dimension };
+        let pos = death.pos;
+        match death.dimension {
+            Some(dimension) => warn!("You died at X: {:.1} Y: {:.1} Z: {:.1} in the {:?}", pos.x, pos.y, pos.z, dimension),
+            None => warn!("You died at X: {:.1} Y: {:.1} Z: {:.1}", pos.x, pos.y, pos.z),
+        }
+        *LAST_DEATH.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(death);
+
+        Some(death)
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::{error, warn};
+
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+
+// Most jobs that can wait for the tick thread. Beyond this `dispatch` fails instead of letting a
+// stalled game pile up work without bound.
+pub const QUEUE_CAPACITY: usize = 256;
+
+// What a dispatched job gets to work with on the tick thread.
+pub struct TickContext<'a, 'local> {
+    pub env: &'a mut JNIEnv<'local>,
+    pub client: &'a MinecraftClient<'local>,
+}
+
+type Job = Box<dyn for<'a, 'local> FnOnce(&mut TickContext<'a, 'local>) -> Result<()> + Send>;
+
+struct QueuedJob {
+    source: &'static str,
+    job: Job,
+}
+
+static QUEUE: Mutex<VecDeque<QueuedJob>> = Mutex::new(VecDeque::new());
+static MAX_DEPTH: AtomicUsize = AtomicUsize::new(0);
+static REJECTED: AtomicU64 = AtomicU64::new(0);
+
+fn queue() -> MutexGuard<'static, VecDeque<QueuedJob>> {
+    QUEUE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum DispatchError {
+    #[error("The dispatch queue is full ({QUEUE_CAPACITY} jobs)")]
+    Full,
+    #[error("The tick thread did not run the job in time")]
+    Timeout,
+    #[error("The job failed or was dropped without a result")]
+    Dropped,
+}
+
+// Queues `job` to run at the start of the next tick on the tick thread, the only thread allowed to
+// use the game's JNI objects. This is the one way for other threads (console, IPC, key polling) to
+// act on the game. `source` names the caller in logs.
+pub fn dispatch<F>(source: &'static str, job: F) -> std::result::Result<(), DispatchError>
+where
+    F: for<'a, 'local> FnOnce(&mut TickContext<'a, 'local>) -> Result<()> + Send + 'static,
+{
+    let mut queue = queue();
+    if queue.len() >= QUEUE_CAPACITY {
+        REJECTED.fetch_add(1, Ordering::Relaxed);
+        warn!("Dispatch queue full, rejected a job from {}", source);
+        return Err(DispatchError::Full);
+    }
+
+    queue.push_back(QueuedJob { source, job: Box::new(job) });
+    MAX_DEPTH.fetch_max(queue.len(), Ordering::Relaxed);
+    Ok(())
+}
+
+// Like `dispatch`, but blocks until the tick thread ran the job and returns its result. Must not be
+// called from the tick thread itself, which would wait for itself until `timeout`.
+pub fn dispatch_and_wait<F, T>(source: &'static str, timeout: Duration, job: F) -> std::result::Result<T, DispatchError>
+where
+    F: for<'a, 'local> FnOnce(&mut TickContext<'a, 'local>) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::sync_channel(1);
+    dispatch(source, move |context| {
+        let result = job(context)?;
+        // The caller may have timed out and dropped the receiver, the result is then discarded.
+        let _ = sender.send(result);
+        Ok(())
+    })?;
+
+    receiver.recv_timeout(timeout).map_err(|e| match e {
+        mpsc::RecvTimeoutError::Timeout => DispatchError::Timeout,
+        mpsc::RecvTimeoutError::Disconnected => DispatchError::Dropped,
+    })
+}
+
+// Runs the jobs queued before this call, in the order they were queued. Jobs queued while draining
+// wait for the next tick so a job re-queueing itself cannot stall the tick. Errors are logged with
+// the source of the job and a Java exception left pending is cleared, like `Scheduler::run_due`.
+pub fn drain<'local>(env: &mut JNIEnv<'local>, client: &MinecraftClient<'local>) {
+    let jobs = std::mem::take(&mut *queue());
+    let mut context = TickContext { env, client };
+
+    for QueuedJob { source, job } in jobs {
+        if let Err(e) = job(&mut context) {
+            match take_java_exception(context.env) {
+                Some(exception) => error!("Dispatched job from {} failed: {:?} ({})", source, e, exception),
+                None => error!("Dispatched job from {} failed: {:?}", source, e),
+            }
+        }
+    }
+}
+
+// Jobs currently waiting for the tick thread.
+pub fn depth() -> usize {
+    queue().len()
+}
+
+// Highest number of jobs that were waiting at once since injection.
+pub fn max_depth() -> usize {
+    MAX_DEPTH.load(Ordering::Relaxed)
+}
+
+// Jobs rejected because the queue was full.
+pub fn rejected() -> u64 {
+    REJECTED.load(Ordering::Relaxed)
+}
@@ -0,0 +1,189 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, bail, Result};
+use jni::JNIEnv;
+use tracing::info;
+
+use crate::banner;
+use crate::friends;
+use crate::profiling;
+use crate::restoration;
+use crate::session_info;
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+use crate::snapshot::TickSnapshot;
+
+enum Value {
+    Str(String),
+    Num(f64),
+    Int(i64),
+    Bool(bool),
+    Null,
+}
+
+type Section = Vec<(&'static str, Value)>;
+
+// Writes a diagnostic snapshot of the client as JSON to `dump-<unix seconds>.json` in `dir` and
+// returns the path. Sections that cannot be gathered, e.g. the player while no world is loaded,
+// are written with their error instead of aborting the dump.
+pub fn write_dump(env: &mut JNIEnv, client: &MinecraftClient, dir: &Path) -> Result<PathBuf> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    let snapshot = gather(env, |env| Ok(TickSnapshot::capture(env, client)?));
+    let (client_state, player) = match snapshot {
+        Ok(snapshot) => (Ok(client_section(&snapshot)), player_section(&snapshot)),
+        Err(e) => (Err(anyhow!("{:#}", e)), Err(e)),
+    };
+
+    let sections: Vec<(&str, Result<Section>)> = vec![
+        ("client", client_state),
+        ("player", player),
+        ("connection", gather(env, |env| connection_section(env, client))),
+        ("session", Ok(session_section())),
+        ("friends", Ok(friends_section())),
+        ("module_timings_ms", Ok(module_timings_section())),
+        ("restorations", Ok(restorations_section())),
+    ];
+
+    let mut json = format!("{{\n  \"generated_at\": {},\n  \"version\": \"{}\"", timestamp, banner::build_id());
+    for (name, section) in sections {
+        let section = section.unwrap_or_else(|e| vec![("error", Value::Str(format!("{:#}", e)))]);
+        write!(json, ",\n  \"{}\": {{", name)?;
+        for (i, (key, value)) in section.iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            write!(json, "{}\n    \"{}\": {}", separator, key, format_value(value))?;
+        }
+        json.push_str("\n  }");
+    }
+    json.push_str("\n}\n");
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("dump-{}.json", timestamp));
+    fs::write(&path, json)?;
+    info!("Wrote diagnostic dump to {}", path.display());
+    Ok(path)
+}
+
+// Runs one part of the dump and clears the Java exception it may have left behind, so the
+// following parts can still make JNI calls.
+fn gather<T>(env: &mut JNIEnv, f: impl FnOnce(&mut JNIEnv) -> Result<T>) -> Result<T> {
+    let result = f(env);
+    if result.is_err() {
+        take_java_exception(env);
+    }
+    result
+}
+
+fn client_section(snapshot: &TickSnapshot) -> Section {
+    vec![
+        ("tick", Value::Int(snapshot.tick as i64)),
+        ("window_focused", Value::Bool(snapshot.window_focused)),
+        ("paused", Value::Bool(snapshot.paused)),
+        ("screen_open", Value::Bool(snapshot.screen_open)),
+        ("singleplayer", Value::Bool(snapshot.singleplayer)),
+        ("in_world", Value::Bool(snapshot.in_world())),
+        ("dimension", snapshot.dimension.map_or(Value::Null, |dimension| Value::Str(format!("{:?}", dimension)))),
+    ]
+}
+
+fn player_section(snapshot: &TickSnapshot) -> Result<Section> {
+    let Some(player) = snapshot.player else {
+        bail!("No world loaded");
+    };
+
+    Ok(vec![
+        ("x", Value::Num(player.position.x)),
+        ("y", Value::Num(player.position.y)),
+        ("z", Value::Num(player.position.z)),
+        ("yaw", Value::Num(player.yaw as f64)),
+        ("pitch", Value::Num(player.pitch as f64)),
+        ("velocity_x", Value::Num(player.velocity.x)),
+        ("velocity_y", Value::Num(player.velocity.y)),
+        ("velocity_z", Value::Num(player.velocity.z)),
+        ("on_ground", Value::Bool(player.on_ground)),
+        ("health", Value::Num(player.health as f64)),
+        ("food_level", Value::Int(player.food_level as i64)),
+        ("movement_speed", Value::Num(player.movement_speed)),
+    ])
+}
+
+fn connection_section(env: &mut JNIEnv, client: &MinecraftClient) -> Result<Section> {
+    let server_address = client.get_server_address(env)?;
+    let game_mode = client.get_game_mode(env)?;
+    let integrated_server_running = client.is_integrated_server_running(env)?;
+
+    Ok(vec![
+        ("integrated_server_running", Value::Bool(integrated_server_running)),
+        ("server_address", server_address.map_or(Value::Null, Value::Str)),
+        ("game_mode", game_mode.map_or(Value::Null, |mode| Value::Str(format!("{:?}", mode)))),
+    ])
+}
+
+fn session_section() -> Section {
+    let sessions = session_info::sessions();
+    let Some(session) = sessions.current() else {
+        return Vec::new();
+    };
+
+    let login_time = session.login_time.duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    vec![
+        ("username", Value::Str(session.username.clone())),
+        ("server", Value::Str(session.server.clone())),
+        ("login_time", Value::Int(login_time as i64)),
+        ("duration_secs", Value::Int(session.duration().as_secs() as i64)),
+    ]
+}
+
+fn friends_section() -> Section {
+    vec![("count", Value::Int(friends::friends().list().len() as i64))]
+}
+
+fn module_timings_section() -> Section {
+    profiling::timings()
+        .report()
+        .into_iter()
+        .map(|(module, average)| (module, Value::Num(average.as_secs_f64() * 1000.0)))
+        .collect()
+}
+
+// Pending undo actions by owner, an owner with several actions gets them joined by `; `.
+fn restorations_section() -> Section {
+    let mut section: Section = Vec::new();
+    for (owner, description) in restoration::restorations().list() {
+        match section.iter_mut().find(|(key, _)| *key == owner) {
+            Some((_, Value::Str(descriptions))) => {
+                descriptions.push_str("; ");
+                descriptions.push_str(description);
+            }
+            _ => section.push((owner, Value::Str(description.to_string()))),
+        }
+    }
+    section
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::Str(string) => format!("\"{}\"", escape(string)),
+        Value::Num(number) if number.is_finite() => number.to_string(),
+        Value::Num(_) | Value::Null => "null".to_string(),
+        Value::Int(number) => number.to_string(),
+        Value::Bool(boolean) => boolean.to_string(),
+    }
+}
+
+fn escape(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len());
+    for c in string.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,267 @@
+use std::cell::Cell;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use anyhow::Result;
+use jni::objects::{GlobalRef, JObject};
+use jni::JNIEnv;
+use tracing::error;
+
+use crate::lagback::Lagback;
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+use crate::snapshot::TickSnapshot;
+
+static BUS: Mutex<EventBus> = Mutex::new(EventBus::new());
+
+thread_local! {
+    // Set while this thread runs handlers, see `post`.
+    static DISPATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+pub fn bus() -> MutexGuard<'static, EventBus> {
+    BUS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Something happening in the game that features can react to. Each event type has its own list of
+// handlers on the bus.
+pub trait Event: Sized + 'static {
+    fn handlers(bus: &mut EventBus) -> &mut Vec<Subscription<Self>>;
+}
+
+// Runs on the thread the event is posted from. Errors are logged with the owner and do not stop the
+// remaining handlers.
+pub type Handler<E> = Box<dyn for<'local> FnMut(&mut JNIEnv<'local>, &mut E) -> Result<()> + Send>;
+
+pub struct Subscription<E> {
+    id: u64,
+    owner: &'static str,
+    // Shared with `post`, which runs it after releasing the bus.
+    handler: Arc<Mutex<Handler<E>>>,
+}
+
+// Start of a client loop iteration, posted on the tick thread after queued jobs ran. Modules do their
+// per-tick work here.
+pub struct TickEvent {
+    pub tick: u64,
+    pub snapshot: TickSnapshot,
+    client: GlobalRef,
+}
+
+impl TickEvent {
+    pub fn new(tick: u64, snapshot: TickSnapshot, client: GlobalRef) -> Self {
+        Self { tick, snapshot, client }
+    }
+
+    pub fn client(&self) -> MinecraftClient<'_> {
+        // SAFETY: the object borrows the global reference, which outlives it. `JObject` deletes nothing
+        // when dropped, so the alias never frees the reference.
+        MinecraftClient::new(unsafe { JObject::from_raw(self.client.as_raw()) })
+    }
+}
+
+// The server set the player back, posted on the tick thread before the tick event of the tick it was
+// detected in.
+pub struct LagbackEvent {
+    pub tick: u64,
+    pub lagback: Lagback,
+}
+
+// A frame is about to be shown, posted on the render thread once per frame while the game's OpenGL
+// context is current, so handlers can draw over the finished frame.
+pub struct RenderEvent {
+    // Frames rendered since the render hook was installed.
+    pub frame: u64,
+}
+
+// A key went down or up while the game window was focused.
+pub struct KeyEvent {
+    // Windows virtual key code, e.g. `0x52` for R.
+    pub key: u32,
+    pub pressed: bool,
+    // Set while a screen such as the chat or an inventory is open, where keys are typically typed
+    // rather than meant as bindings.
+    pub screen_open: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketDirection {
+    // Received from the server, before the game handles it.
+    Inbound,
+    // Sent by the game, before it is encoded.
+    Outbound,
+}
+
+// A packet passing the connection. Cancelling it drops it: inbound packets never reach the game and
+// outbound packets are never sent.
+pub struct PacketEvent {
+    pub direction: PacketDirection,
+    // The message passing the pipeline, normally a `net/minecraft/network/packet/Packet`. Handlers check
+    // its class with `is` before reading it.
+    pub packet: GlobalRef,
+    cancelled: bool,
+}
+
+impl PacketEvent {
+    pub fn new(direction: PacketDirection, packet: GlobalRef) -> Self {
+        Self { direction, packet, cancelled: false }
+    }
+
+    pub fn cancel(&mut self) {
+        self.cancelled = true;
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    // Whether the packet is an instance of `class`, e.g. `net/minecraft/network/packet/s2c/play/EntityVelocityUpdateS2CPacket`.
+    pub fn is(&self, env: &mut JNIEnv, class: &str) -> crate::sdk::Result<bool> {
+        let class = crate::sdk::jni::cache::class(env, class)?;
+        Ok(env.is_instance_of(self.packet.as_obj(), &class)?)
+    }
+}
+
+impl Event for TickEvent {
+    fn handlers(bus: &mut EventBus) -> &mut Vec<Subscription<Self>> {
+        &mut bus.tick
+    }
+}
+
+impl Event for LagbackEvent {
+    fn handlers(bus: &mut EventBus) -> &mut Vec<Subscription<Self>> {
+        &mut bus.lagback
+    }
+}
+
+impl Event for RenderEvent {
+    fn handlers(bus: &mut EventBus) -> &mut Vec<Subscription<Self>> {
+        &mut bus.render
+    }
+}
+
+impl Event for KeyEvent {
+    fn handlers(bus: &mut EventBus) -> &mut Vec<Subscription<Self>> {
+        &mut bus.key
+    }
+}
+
+impl Event for PacketEvent {
+    fn handlers(bus: &mut EventBus) -> &mut Vec<Subscription<Self>> {
+        &mut bus.packet
+    }
+}
+
+// Handlers for every event type. Features subscribe when enabled and unsubscribe by owner when
+// disabled.
+pub struct EventBus {
+    next_id: u64,
+    tick: Vec<Subscription<TickEvent>>,
+    lagback: Vec<Subscription<LagbackEvent>>,
+    render: Vec<Subscription<RenderEvent>>,
+    key: Vec<Subscription<KeyEvent>>,
+    packet: Vec<Subscription<PacketEvent>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub const fn new() -> Self {
+        Self {
+            next_id: 1,
+            tick: Vec::new(),
+            lagback: Vec::new(),
+            render: Vec::new(),
+            key: Vec::new(),
+            packet: Vec::new(),
+        }
+    }
+
+    // Adds a handler for events of type `E` and returns its id for `unsubscribe`.
+    pub fn subscribe<E, F>(&mut self, owner: &'static str, handler: F) -> u64
+    where
+        E: Event,
+        F: for<'local> FnMut(&mut JNIEnv<'local>, &mut E) -> Result<()> + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        E::handlers(self).push(Subscription { id, owner, handler: Arc::new(Mutex::new(Box::new(handler))) });
+        id
+    }
+
+    // Removes a handler. Returns whether it was subscribed.
+    pub fn unsubscribe(&mut self, id: u64) -> bool {
+        let count = self.len();
+        self.retain(|subscription_id, _| subscription_id != id);
+        self.len() != count
+    }
+
+    // Removes every handler of `owner`, e.g. when the module gets disabled. Returns how many there were.
+    pub fn unsubscribe_owner(&mut self, owner: &str) -> usize {
+        let count = self.len();
+        self.retain(|_, subscription_owner| subscription_owner != owner);
+        count - self.len()
+    }
+
+    pub fn has_subscribers<E: Event>(&mut self) -> bool {
+        !E::handlers(self).is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tick.len() + self.lagback.len() + self.render.len() + self.key.len() + self.packet.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Keeps the subscriptions of every event type for which `keep` returns true, given id and owner.
+    fn retain(&mut self, keep: impl Fn(u64, &str) -> bool) {
+        self.tick.retain(|subscription| keep(subscription.id, subscription.owner));
+        self.lagback.retain(|subscription| keep(subscription.id, subscription.owner));
+        self.render.retain(|subscription| keep(subscription.id, subscription.owner));
+        self.key.retain(|subscription| keep(subscription.id, subscription.owner));
+        self.packet.retain(|subscription| keep(subscription.id, subscription.owner));
+    }
+}
+
+// Runs the handlers of the event in the order they subscribed and returns how many ran.
+//
+// The handlers are copied out and the bus released before any of them runs, so handlers may
+// subscribe and unsubscribe, and other threads can post meanwhile. A handler unsubscribed during
+// dispatch still gets the event being dispatched. Events posted by a handler on the same thread, e.g.
+// a packet a packet handler sends, are not dispatched at all instead of deadlocking on the handler.
+pub fn post<E: Event>(env: &mut JNIEnv, event: &mut E) -> usize {
+    if DISPATCHING.with(Cell::get) {
+        return 0;
+    }
+
+    DISPATCHING.with(|dispatching| dispatching.set(true));
+    let _guard = DispatchGuard;
+    let handlers: Vec<_> = E::handlers(&mut bus())
+        .iter()
+        .map(|subscription| (subscription.owner, Arc::clone(&subscription.handler)))
+        .collect();
+    for (owner, handler) in &handlers {
+        let mut handler = handler.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Err(e) = (*handler)(env, event) {
+            match take_java_exception(env) {
+                Some(exception) => error!("Event handler of {} failed: {:?} ({})", owner, e, exception),
+                None => error!("Event handler of {} failed: {:?}", owner, e),
+            }
+        }
+    }
+    handlers.len()
+}
+
+// Clears the dispatching flag even when a handler panics.
+struct DispatchGuard;
+
+impl Drop for DispatchGuard {
+    fn drop(&mut self) {
+        DISPATCHING.with(|dispatching| dispatching.set(false));
+    }
+}
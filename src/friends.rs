@@ -0,0 +1,100 @@
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::Result;
+use jni::JNIEnv;
+
+use crate::sdk::game::entity::Entity;
+use crate::sdk::game::network::ClientPlayNetworkHandler;
+use crate::sdk::game::player_list::PlayerListEntry;
+
+// The friends of the user, shared between everything that has to leave them alone.
+static FRIENDS: Mutex<FriendList> = Mutex::new(FriendList::new());
+
+// Locks and returns the global friends list.
+pub fn friends() -> MutexGuard<'static, FriendList> {
+    FRIENDS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// A single friend. The UUID is preferred for matching so renames do not break the list,
+// the name is the fallback for friends whose UUID could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Friend {
+    pub name: String,
+    pub uuid: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FriendList {
+    friends: Vec<Friend>,
+}
+
+impl FriendList {
+    pub const fn new() -> Self {
+        Self { friends: Vec::new() }
+    }
+
+    // Adds a friend, replacing an existing entry with the same name or UUID.
+    pub fn add(&mut self, name: &str, uuid: Option<String>) {
+        self.friends.retain(|friend| {
+            !friend.name.eq_ignore_ascii_case(name) && (uuid.is_none() || friend.uuid != uuid)
+        });
+        self.friends.push(Friend { name: name.to_string(), uuid });
+    }
+
+    // Adds a friend by name and resolves the UUID from the tab list when the player is online.
+    pub fn add_by_name(&mut self, env: &mut JNIEnv, network_handler: &ClientPlayNetworkHandler, name: &str) -> Result<()> {
+        let uuid = resolve_uuid(env, network_handler, name)?;
+        self.add(name, uuid);
+        Ok(())
+    }
+
+    // Removes a friend by name or UUID, returns whether anyone was removed.
+    pub fn remove(&mut self, name_or_uuid: &str) -> bool {
+        let count = self.friends.len();
+        self.friends.retain(|friend| {
+            !friend.name.eq_ignore_ascii_case(name_or_uuid) && friend.uuid.as_deref() != Some(name_or_uuid)
+        });
+        self.friends.len() != count
+    }
+
+    pub fn list(&self) -> &[Friend] {
+        &self.friends
+    }
+
+    // Checks whether a player with the given UUID and name is a friend.
+    pub fn contains(&self, uuid: &str, name: &str) -> bool {
+        self.friends.iter().any(|friend| match &friend.uuid {
+            Some(friend_uuid) => friend_uuid == uuid,
+            None => friend.name.eq_ignore_ascii_case(name),
+        })
+    }
+
+    pub fn is_friend(&self, env: &mut JNIEnv, entity: &Entity) -> Result<bool> {
+        if self.friends.is_empty() {
+            return Ok(false);
+        }
+
+        let uuid = entity.get_uuid_string(env)?;
+        let name = entity.get_name(env)?;
+        Ok(self.contains(&uuid, &name))
+    }
+}
+
+// Looks up the UUID of an online player in the tab list.
+fn resolve_uuid(env: &mut JNIEnv, network_handler: &ClientPlayNetworkHandler, name: &str) -> Result<Option<String>> {
+    let mut uuid = None;
+
+    for entry in PlayerListEntry::collect_from(env, network_handler)? {
+        if uuid.is_none() {
+            if let Some(profile) = entry.get_profile(env)? {
+                if profile.get_name(env)?.eq_ignore_ascii_case(name) {
+                    uuid = Some(profile.get_id_string(env)?);
+                }
+                env.delete_local_ref(profile.inner)?;
+            }
+        }
+        env.delete_local_ref(entry.inner)?;
+    }
+
+    Ok(uuid)
+}
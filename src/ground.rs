@@ -0,0 +1,88 @@
+use crate::snapshot::TickSnapshot;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroundEvent {
+    // The player stepped off, jumped or was knocked off the ground.
+    LeftGround,
+    // The player touched the ground after `airborne_ticks` ticks in the air, having fallen
+    // `fall_distance` blocks from the highest point of the airtime.
+    Landed { airborne_ticks: u32, fall_distance: f64 },
+}
+
+// Follows the on-ground flag across tick snapshots to find takeoffs and landings, and measures the
+// airtime in between.
+#[derive(Debug, Default)]
+pub struct GroundTracker {
+    on_ground: bool,
+    airborne_ticks: u32,
+    // Highest Y position of the current airtime.
+    peak_y: Option<f64>,
+    initialized: bool,
+}
+
+impl GroundTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Feeds the snapshot of the current tick, returns the transition that happened since the last one.
+    pub fn update(&mut self, snapshot: &TickSnapshot) -> Option<GroundEvent> {
+        let Some(player) = snapshot.player else {
+            self.reset();
+            return None;
+        };
+
+        if !self.initialized {
+            self.initialized = true;
+            self.on_ground = player.on_ground;
+            self.peak_y = (!player.on_ground).then_some(player.position.y);
+            return None;
+        }
+
+        let was_on_ground = self.on_ground;
+        self.on_ground = player.on_ground;
+
+        match (was_on_ground, player.on_ground) {
+            (true, false) => {
+                self.airborne_ticks = 1;
+                self.peak_y = Some(player.position.y);
+                Some(GroundEvent::LeftGround)
+            }
+            (false, true) => {
+                let fall_distance = self.peak_y.map_or(0.0, |peak| (peak - player.position.y).max(0.0));
+                let event = GroundEvent::Landed { airborne_ticks: self.airborne_ticks, fall_distance };
+                self.airborne_ticks = 0;
+                self.peak_y = None;
+                Some(event)
+            }
+            (false, false) => {
+                self.airborne_ticks += 1;
+                self.peak_y = Some(self.peak_y.map_or(player.position.y, |peak| peak.max(player.position.y)));
+                None
+            }
+            (true, true) => None,
+        }
+    }
+
+    // Forgets the state, must be called when the player changes worlds or respawns.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn on_ground(&self) -> bool {
+        self.on_ground
+    }
+
+    // Ticks spent in the air so far, zero while on the ground.
+    pub fn airborne_ticks(&self) -> u32 {
+        self.airborne_ticks
+    }
+
+    // How far the player has fallen from the highest point of the current airtime.
+    pub fn current_fall_distance(&self, snapshot: &TickSnapshot) -> f64 {
+        match (self.peak_y, snapshot.player) {
+            (Some(peak), Some(player)) => (peak - player.position.y).max(0.0),
+            _ => 0.0,
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use jni::objects::GlobalRef;
+use jni::JNIEnv;
+
+use crate::sdk::game::entity::Entity;
+
+static HANDLES: Mutex<HandleTable> = Mutex::new(HandleTable::new(256, Duration::from_secs(60)));
+
+pub fn handles() -> MutexGuard<'static, HandleTable> {
+    HANDLES.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// A small integer standing in for a Java object, safe to hand out over IPC.
+pub type Handle = u32;
+
+#[derive(Debug, thiserror::Error)]
+pub enum HandleError {
+    #[error("Handle {0} was never issued")]
+    Unknown(Handle),
+    #[error("Handle {0} has expired")]
+    Expired(Handle),
+    #[error(transparent)]
+    Sdk(#[from] crate::sdk::Error),
+}
+
+struct Entry {
+    object: GlobalRef,
+    created: Instant,
+    last_used: Instant,
+}
+
+// Maps handles to global references so IPC clients can refer to objects across requests. Handles
+// expire after a fixed time and all at once when the world changes, and the least recently used
+// handle is evicted once the table is full. Handles are never reused, so a stale handle is reported
+// as expired instead of silently pointing at a different object.
+pub struct HandleTable {
+    entries: BTreeMap<Handle, Entry>,
+    max_size: usize,
+    ttl: Duration,
+    next_handle: Handle,
+}
+
+impl HandleTable {
+    pub const fn new(max_size: usize, ttl: Duration) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            max_size,
+            ttl,
+            next_handle: 1,
+        }
+    }
+
+    // Issues a handle for the entity.
+    pub fn insert_entity(&mut self, env: &mut JNIEnv, entity: &Entity) -> Result<Handle, HandleError> {
+        let object = env.new_global_ref(&entity.inner).map_err(crate::sdk::Error::from)?;
+
+        if self.entries.len() >= self.max_size.max(1) {
+            self.evict_least_recently_used();
+        }
+
+        let handle = self.next_handle;
+        self.next_handle += 1;
+
+        let now = Instant::now();
+        self.entries.insert(handle, Entry { object, created: now, last_used: now });
+        Ok(handle)
+    }
+
+    // Resolves a handle into a new local reference to the entity.
+    pub fn get_entity<'local>(&mut self, env: &mut JNIEnv<'local>, handle: Handle) -> Result<Entity<'local>, HandleError> {
+        let object = self.get(handle)?.clone();
+        let local = env.new_local_ref(&object).map_err(crate::sdk::Error::from)?;
+        Ok(Entity::new(local))
+    }
+
+    fn get(&mut self, handle: Handle) -> Result<&GlobalRef, HandleError> {
+        if handle == 0 || handle >= self.next_handle {
+            return Err(HandleError::Unknown(handle));
+        }
+
+        let ttl = self.ttl;
+        let expired = match self.entries.get(&handle) {
+            Some(entry) => entry.created.elapsed() > ttl,
+            None => return Err(HandleError::Expired(handle)),
+        };
+        if expired {
+            self.entries.remove(&handle);
+            return Err(HandleError::Expired(handle));
+        }
+
+        let entry = self.entries.get_mut(&handle).expect("entry was just checked");
+        entry.last_used = Instant::now();
+        Ok(&entry.object)
+    }
+
+    pub fn remove(&mut self, handle: Handle) {
+        self.entries.remove(&handle);
+    }
+
+    // Expires every handle, must be called when the world changes since the objects belong to it.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self.entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(&handle, _)| handle);
+        if let Some(handle) = oldest {
+            self.entries.remove(&handle);
+        }
+    }
+}
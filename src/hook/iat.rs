@@ -0,0 +1,87 @@
+use std::ffi::{c_void, CStr};
+
+use anyhow::Result;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::System::Diagnostics::Debug::{IMAGE_DIRECTORY_ENTRY_IMPORT, IMAGE_NT_HEADERS64};
+use windows::Win32::System::Memory::{VirtualProtect, PAGE_PROTECTION_FLAGS, PAGE_READWRITE};
+use windows::Win32::System::ProcessStatus::EnumProcessModules;
+use windows::Win32::System::SystemServices::{IMAGE_DOS_HEADER, IMAGE_IMPORT_BY_NAME, IMAGE_IMPORT_DESCRIPTOR, IMAGE_ORDINAL_FLAG64};
+use windows::Win32::System::Threading::GetCurrentProcess;
+
+// Hooks through the import address table: every call a module makes to a function of another DLL
+// goes through a pointer in its import table, and replacing that pointer redirects the calls of that
+// one module. Unlike patching the function itself this needs no disassembler and leaves other callers
+// alone.
+
+// Every module loaded into the process.
+pub fn loaded_modules() -> Result<Vec<HMODULE>> {
+    let mut modules = vec![HMODULE::default(); 512];
+    loop {
+        let capacity = (modules.len() * std::mem::size_of::<HMODULE>()) as u32;
+        let mut needed = 0;
+        unsafe { EnumProcessModules(GetCurrentProcess(), modules.as_mut_ptr(), capacity, &mut needed)? };
+        let count = needed as usize / std::mem::size_of::<HMODULE>();
+        if count <= modules.len() {
+            modules.truncate(count);
+            return Ok(modules);
+        }
+        // More modules were loaded than fit, the next call gets room for all of them.
+        modules.resize(count, HMODULE::default());
+    }
+}
+
+// Finds the slot the calls of `module` to `function` from `dll` go through. `None` if the module does
+// not import the function by name. `module` must be a loaded 64-bit image.
+pub(crate) unsafe fn find_import(module: HMODULE, dll: &str, function: &str) -> Option<*mut usize> {
+    let base = module.0 as *const u8;
+    let dos_header = &*(base as *const IMAGE_DOS_HEADER);
+    let nt_headers = &*(base.offset(dos_header.e_lfanew as isize) as *const IMAGE_NT_HEADERS64);
+    let directory = nt_headers.OptionalHeader.DataDirectory[IMAGE_DIRECTORY_ENTRY_IMPORT.0 as usize];
+    if directory.VirtualAddress == 0 {
+        return None;
+    }
+
+    let mut descriptor = base.add(directory.VirtualAddress as usize) as *const IMAGE_IMPORT_DESCRIPTOR;
+    while (*descriptor).Name != 0 {
+        let imported = &*descriptor;
+        descriptor = descriptor.add(1);
+        let name = CStr::from_ptr(base.add(imported.Name as usize) as *const _);
+        if !name.to_string_lossy().eq_ignore_ascii_case(dll) {
+            continue;
+        }
+
+        // The names are in the original thunks, the addresses the calls go through in the first ones.
+        // Old linkers left out the original thunks, the loader has overwritten the names since.
+        if imported.Anonymous.OriginalFirstThunk == 0 {
+            continue;
+        }
+        let mut names = base.add(imported.Anonymous.OriginalFirstThunk as usize) as *const u64;
+        let mut slot = base.add(imported.FirstThunk as usize) as *mut usize;
+        while *names != 0 {
+            if *names & IMAGE_ORDINAL_FLAG64 == 0 {
+                let import = base.add(*names as usize) as *const IMAGE_IMPORT_BY_NAME;
+                let import_name = CStr::from_ptr((*import).Name.as_ptr() as *const _);
+                if import_name.to_bytes() == function.as_bytes() {
+                    return Some(slot);
+                }
+            }
+            names = names.add(1);
+            slot = slot.add(1);
+        }
+    }
+    None
+}
+
+// Points an import slot at `replacement` and returns the address it held before. The slot is
+// read-only once the loader is done with it, so it is unprotected for the write. `slot` must come from
+// `find_import` and `replacement` be a function with the same signature and calling convention as the
+// import, as every call of the module goes there from now on.
+pub(crate) unsafe fn replace(slot: *mut usize, replacement: *const c_void) -> Result<*const c_void> {
+    let size = std::mem::size_of::<usize>();
+    let mut protection = PAGE_PROTECTION_FLAGS::default();
+    VirtualProtect(slot as *const c_void, size, PAGE_READWRITE, &mut protection)?;
+    let previous = std::ptr::replace(slot, replacement as usize);
+    // Left writable if this fails, which does no harm.
+    let _ = VirtualProtect(slot as *const c_void, size, protection, &mut protection);
+    Ok(previous as *const c_void)
+}
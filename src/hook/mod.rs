@@ -0,0 +1,4 @@
+// Native hooks into the game process, for what the JVM side cannot reach. Each hook installs once and
+// stays until the process exits.
+pub mod iat;
+pub mod swap_buffers;
@@ -0,0 +1,85 @@
+use std::ffi::c_void;
+use std::panic::catch_unwind;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use jni::{JNIEnv, JavaVM};
+use tracing::{error, info};
+use windows::core::s;
+use windows::Win32::Foundation::BOOL;
+use windows::Win32::Graphics::Gdi::HDC;
+use windows::Win32::System::LibraryLoader::GetProcAddress;
+
+use super::iat;
+use crate::event::{self, RenderEvent};
+
+type SwapBuffersFn = unsafe extern "system" fn(HDC) -> BOOL;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+// The `SwapBuffers` GLFW called before the hook, every frame is passed on to it.
+static ORIGINAL: AtomicUsize = AtomicUsize::new(0);
+
+static FRAMES: AtomicU64 = AtomicU64::new(0);
+
+// The render thread gets its environment from here, it is a Java thread and already attached.
+static VM: OnceLock<JavaVM> = OnceLock::new();
+
+// Hooks the buffer swap at the end of every frame, posting a `RenderEvent` on the render thread right
+// before the frame is shown, while the game's OpenGL context is current. The hook replaces GLFW's
+// import of `SwapBuffers` from gdi32.dll, which `glfwSwapBuffers` calls and which calls
+// `wglSwapBuffers` in turn. Does nothing if already installed.
+pub fn install(env: &mut JNIEnv) -> Result<()> {
+    if INSTALLED.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+    let _ = VM.set(env.get_java_vm()?);
+
+    // LWJGL extracts GLFW under names that vary between versions, it is whichever module exports
+    // `glfwSwapBuffers`.
+    let glfw = iat::loaded_modules()?
+        .into_iter()
+        .find(|&module| unsafe { GetProcAddress(module, s!("glfwSwapBuffers")) }.is_some())
+        .ok_or_else(|| anyhow!("GLFW is not loaded"))?;
+    let slot = unsafe { iat::find_import(glfw, "gdi32.dll", "SwapBuffers") }.ok_or_else(|| anyhow!("GLFW does not import SwapBuffers"))?;
+
+    // Stored before the slot changes, the render thread may call the hook right after.
+    ORIGINAL.store(unsafe { *slot }, Ordering::SeqCst);
+    unsafe { iat::replace(slot, swap_buffers as SwapBuffersFn as *const c_void)? };
+    INSTALLED.store(true, Ordering::SeqCst);
+    info!("Render hook installed");
+    Ok(())
+}
+
+// Frames shown since the hook was installed.
+pub fn frames() -> u64 {
+    FRAMES.load(Ordering::Relaxed)
+}
+
+// Called by GLFW in place of `SwapBuffers`. Nothing may unwind into GLFW, so panics are logged and
+// the frame is shown regardless.
+unsafe extern "system" fn swap_buffers(hdc: HDC) -> BOOL {
+    let frame = FRAMES.fetch_add(1, Ordering::Relaxed) + 1;
+    if catch_unwind(|| post_render(frame)).is_err() {
+        error!("Render event of frame {} panicked", frame);
+    }
+
+    let original: SwapBuffersFn = std::mem::transmute(ORIGINAL.load(Ordering::SeqCst));
+    original(hdc)
+}
+
+fn post_render(frame: u64) {
+    let Some(vm) = VM.get() else {
+        return;
+    };
+    if !event::bus().has_subscribers::<RenderEvent>() {
+        return;
+    }
+    match vm.get_env() {
+        Ok(mut env) => {
+            event::post(&mut env, &mut RenderEvent { frame });
+        }
+        Err(e) => error!("Render thread has no JNI environment: {}", e),
+    }
+}
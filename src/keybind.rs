@@ -0,0 +1,181 @@
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::{config, dispatch};
+use crate::event::{self, KeyEvent};
+use crate::modules;
+
+// Presses of the same key closer together than this count once. Keyboards with bouncing switches
+// and quick double taps would otherwise toggle a module on and straight off again.
+pub const DEBOUNCE: Duration = Duration::from_millis(150);
+
+static KEYBINDS: Mutex<Keybinds> = Mutex::new(Keybinds::new());
+
+pub fn keybinds() -> MutexGuard<'static, Keybinds> {
+    KEYBINDS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Maps keys to the modules they toggle. A module has at most one key, a key may toggle several
+// modules at once.
+pub struct Keybinds {
+    binds: Vec<(String, u32)>,
+    last_press: Vec<(u32, Instant)>,
+    subscribed: bool,
+}
+
+impl Default for Keybinds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Keybinds {
+    pub const fn new() -> Self {
+        Self { binds: Vec::new(), last_press: Vec::new(), subscribed: false }
+    }
+
+    // Binds the module to the Windows virtual key code, replacing its previous key.
+    pub fn bind(&mut self, module: &str, key: u32) {
+        self.unbind(module);
+        self.binds.push((module.to_string(), key));
+        config::mark_dirty();
+    }
+
+    // Returns whether the module had a key.
+    pub fn unbind(&mut self, module: &str) -> bool {
+        let count = self.binds.len();
+        self.binds.retain(|(bound, _)| !bound.eq_ignore_ascii_case(module));
+        let removed = self.binds.len() != count;
+        if removed {
+            config::mark_dirty();
+        }
+        removed
+    }
+
+    pub fn key_of(&self, module: &str) -> Option<u32> {
+        self.binds.iter().find(|(bound, _)| bound.eq_ignore_ascii_case(module)).map(|&(_, key)| key)
+    }
+
+    // Module and key of every bind, in the order they were bound.
+    pub fn list(&self) -> Vec<(&str, u32)> {
+        self.binds.iter().map(|(module, key)| (module.as_str(), *key)).collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.binds.clear();
+    }
+
+    // Modules bound to the key, or nothing while the key is bouncing.
+    fn pressed(&mut self, key: u32, now: Instant) -> Vec<String> {
+        match self.last_press.iter_mut().find(|(pressed, _)| *pressed == key) {
+            Some((_, last)) if now.duration_since(*last) < DEBOUNCE => return Vec::new(),
+            Some((_, last)) => *last = now,
+            None => self.last_press.push((key, now)),
+        }
+        self.binds.iter().filter(|(_, bound)| *bound == key).map(|(module, _)| module.clone()).collect()
+    }
+}
+
+// Toggles bound modules on key presses. Keys typed while a screen is open, e.g. into the chat, are
+// ignored. Does nothing when called again.
+pub fn install() {
+    let mut keybinds = keybinds();
+    if keybinds.subscribed {
+        return;
+    }
+    keybinds.subscribed = true;
+    drop(keybinds);
+
+    event::bus().subscribe::<KeyEvent, _>("Keybinds", |_env, event| {
+        if !event.pressed || event.screen_open {
+            return Ok(());
+        }
+
+        // Toggled by a dispatched job, which gets the client the module hooks need.
+        for module in self::keybinds().pressed(event.key, Instant::now()) {
+            debug!("{} pressed, toggling {}", key_name(event.key), module);
+            let queued = dispatch::dispatch("Keybinds", move |context| {
+                modules::manager().toggle(context.env, context.client, &module)?;
+                Ok(())
+            });
+            if let Err(e) = queued {
+                warn!("Could not toggle a module by key: {}", e);
+            }
+        }
+        Ok(())
+    });
+}
+
+// Named keys besides letters, digits and F1-F24, with their virtual key codes.
+const NAMED_KEYS: &[(&str, u32)] = &[
+    ("backspace", 0x08),
+    ("tab", 0x09),
+    ("enter", 0x0D),
+    ("pause", 0x13),
+    ("capslock", 0x14),
+    ("escape", 0x1B),
+    ("space", 0x20),
+    ("pageup", 0x21),
+    ("pagedown", 0x22),
+    ("end", 0x23),
+    ("home", 0x24),
+    ("left", 0x25),
+    ("up", 0x26),
+    ("right", 0x27),
+    ("down", 0x28),
+    ("insert", 0x2D),
+    ("delete", 0x2E),
+    ("numpad0", 0x60),
+    ("numpad1", 0x61),
+    ("numpad2", 0x62),
+    ("numpad3", 0x63),
+    ("numpad4", 0x64),
+    ("numpad5", 0x65),
+    ("numpad6", 0x66),
+    ("numpad7", 0x67),
+    ("numpad8", 0x68),
+    ("numpad9", 0x69),
+    ("lshift", 0xA0),
+    ("rshift", 0xA1),
+    ("lcontrol", 0xA2),
+    ("rcontrol", 0xA3),
+    ("lalt", 0xA4),
+    ("ralt", 0xA5),
+];
+
+// Parses a key name like `r`, `7`, `f6`, `rshift` or `insert` into its virtual key code, ignoring case.
+pub fn parse_key(name: &str) -> Option<u32> {
+    let name = name.to_ascii_lowercase();
+    if let [character] = name.as_bytes() {
+        if character.is_ascii_alphanumeric() {
+            // Letters and digits share their code with the upper case ASCII character.
+            return Some(character.to_ascii_uppercase() as u32);
+        }
+    }
+    if let Some(number) = name.strip_prefix('f').and_then(|number| number.parse::<u32>().ok()) {
+        return (1..=24).contains(&number).then_some(0x70 + number - 1);
+    }
+    NAMED_KEYS.iter().find(|(named, _)| *named == name).map(|&(_, key)| key)
+}
+
+// Every name `parse_key` accepts, for completions.
+pub fn key_names() -> Vec<String> {
+    let mut names: Vec<String> = ('a'..='z').chain('0'..='9').map(String::from).collect();
+    names.extend((1..=24).map(|number| format!("f{}", number)));
+    names.extend(NAMED_KEYS.iter().map(|(name, _)| name.to_string()));
+    names
+}
+
+// Name of the key as `parse_key` accepts it, or its hexadecimal code for keys without one.
+pub fn key_name(key: u32) -> String {
+    match key {
+        0x30..=0x39 | 0x41..=0x5A => char::from(key as u8).to_ascii_lowercase().to_string(),
+        0x70..=0x87 => format!("f{}", key - 0x70 + 1),
+        _ => NAMED_KEYS
+            .iter()
+            .find(|&&(_, named)| named == key)
+            .map_or_else(|| format!("0x{:02x}", key), |(name, _)| name.to_string()),
+    }
+}
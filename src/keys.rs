@@ -0,0 +1,45 @@
+use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+
+// Highest Windows virtual key code.
+const KEY_COUNT: usize = 256;
+
+// Finds key transitions by polling the keyboard once per tick. GLFW's key state may only be read on
+// the render thread, so the tick thread asks Windows directly. A key pressed and released between
+// two polls is missed.
+pub struct KeyPoller {
+    down: [bool; KEY_COUNT],
+}
+
+impl Default for KeyPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KeyPoller {
+    pub fn new() -> Self {
+        Self { down: [false; KEY_COUNT] }
+    }
+
+    // Returns the keys that changed since the last poll as virtual key code and whether it is now
+    // down, in key code order.
+    pub fn poll(&mut self) -> Vec<(u32, bool)> {
+        let mut changes = Vec::new();
+        // Key code 0 is not a key.
+        for key in 1..KEY_COUNT {
+            // The most significant bit is set while the key is down.
+            let down = unsafe { GetAsyncKeyState(key as i32) } < 0;
+            if down != self.down[key] {
+                self.down[key] = down;
+                changes.push((key as u32, down));
+            }
+        }
+        changes
+    }
+
+    // Forgets the pressed keys, e.g. after the window lost focus, so keys held meanwhile are reported
+    // again as pressed instead of being missed.
+    pub fn reset(&mut self) {
+        self.down = [false; KEY_COUNT];
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tracing::debug;
+
+use crate::event::{self, LagbackEvent};
+use crate::sdk::math::DVec3;
+use crate::snapshot::TickSnapshot;
+use crate::timing;
+
+// Number of past ticks of motion the correction is compared against.
+const MOTION_HISTORY: usize = 5;
+
+// The server set the player back, which it does after rejecting movement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lagback {
+    pub expected: DVec3,
+    pub actual: DVec3,
+}
+
+impl Lagback {
+    pub fn distance(&self) -> f64 {
+        self.expected.distance(self.actual)
+    }
+}
+
+// Detects server setbacks by comparing where the player should be (last position plus last
+// velocity) with where it is. Only corrections against the recent direction of movement count, so
+// ender pearls and /tp, which can move the player anywhere, are not mistaken for a setback. The client
+// loop runs one detector and posts a `LagbackEvent` for each setback.
+#[derive(Debug)]
+pub struct LagbackDetector {
+    // Minimum distance between the expected and actual position, in blocks.
+    pub threshold: f64,
+    // Tick, position and velocity of the last sample.
+    last: Option<(u64, DVec3, DVec3)>,
+    recent_motion: VecDeque<DVec3>,
+}
+
+impl Default for LagbackDetector {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+impl LagbackDetector {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold, last: None, recent_motion: VecDeque::with_capacity(MOTION_HISTORY) }
+    }
+
+    // Feeds the snapshot of the current tick, returns the setback if one happened since the last tick.
+    pub fn update(&mut self, snapshot: &TickSnapshot) -> Option<Lagback> {
+        let Some(player) = snapshot.player else {
+            self.reset();
+            return None;
+        };
+
+        // A sample taken again before the game moved the player has nothing new, and would read the
+        // whole velocity as a correction.
+        if let Some((tick, position, _)) = self.last {
+            if tick == snapshot.tick && position == player.position {
+                return None;
+            }
+        }
+
+        let (_, last_position, last_velocity) = self.last.replace((snapshot.tick, player.position, player.velocity))?;
+
+        let expected = last_position + last_velocity;
+        let correction = player.position - expected;
+
+        let motion = self.recent_motion.iter().fold(DVec3::ZERO, |sum, &motion| sum + motion);
+        if self.recent_motion.len() == MOTION_HISTORY {
+            self.recent_motion.pop_front();
+        }
+        self.recent_motion.push_back(player.position - last_position);
+
+        if correction.length() < self.threshold || motion.horizontal_length() < 1e-3 {
+            return None;
+        }
+
+        // The correction has to point mostly back where the player came from.
+        if correction.normalize().dot(motion.normalize()) > -0.5 {
+            return None;
+        }
+
+        let lagback = Lagback { expected, actual: player.position };
+        debug!("Detected a lagback of {:.2} blocks", lagback.distance());
+        self.recent_motion.clear();
+        Some(lagback)
+    }
+
+    // Forgets the state, must be called when the player changes worlds or respawns.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.recent_motion.clear();
+    }
+}
+
+// Holds a movement module back for a while after each setback. `subscribe` it when the module gets
+// enabled, disabling the module removes the subscription with its other handlers.
+#[derive(Debug)]
+pub struct LagbackPause {
+    pub ticks: u32,
+    // Tick of the last setback, shared with the handler. 0 before the first one.
+    last_lagback: Arc<AtomicU64>,
+}
+
+impl LagbackPause {
+    pub fn new(ticks: u32) -> Self {
+        Self { ticks, last_lagback: Arc::new(AtomicU64::new(0)) }
+    }
+
+    pub fn subscribe(&self, owner: &'static str) {
+        let last_lagback = Arc::clone(&self.last_lagback);
+        event::bus().subscribe::<LagbackEvent, _>(owner, move |_env, event| {
+            last_lagback.store(event.tick, Ordering::Relaxed);
+            Ok(())
+        });
+    }
+
+    // Whether a setback happened within the last `ticks` ticks.
+    pub fn is_paused(&self) -> bool {
+        let last_lagback = self.last_lagback.load(Ordering::Relaxed);
+        last_lagback != 0 && timing::current_tick() < last_lagback + self.ticks as u64
+    }
+
+    pub fn reset(&self) {
+        self.last_lagback.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::PlayerSnapshot;
+
+    fn snapshot(tick: u64, position: DVec3, velocity: DVec3) -> TickSnapshot {
+        let player = PlayerSnapshot {
+            position,
+            eye_position: position,
+            velocity,
+            on_ground: false,
+            yaw: 0.0,
+            pitch: 0.0,
+            health: 20.0,
+            food_level: 20,
+            movement_speed: 0.1,
+        };
+        TickSnapshot { tick, player: Some(player), ..TickSnapshot::default() }
+    }
+
+    #[test]
+    fn repeated_sample_is_not_a_lagback() {
+        let mut detector = LagbackDetector::default();
+        let velocity = DVec3::new(0.5, 0.0, 0.0);
+        for tick in 1..=6 {
+            let position = DVec3::new(tick as f64 * 0.5, 64.0, 0.0);
+            assert_eq!(detector.update(&snapshot(tick, position, velocity)), None);
+        }
+        assert_eq!(detector.update(&snapshot(6, DVec3::new(3.0, 64.0, 0.0), velocity)), None);
+        assert_eq!(detector.update(&snapshot(7, DVec3::new(3.5, 64.0, 0.0), velocity)), None);
+    }
+
+    #[test]
+    fn setback_against_motion_is_a_lagback() {
+        let mut detector = LagbackDetector::default();
+        let velocity = DVec3::new(0.5, 0.0, 0.0);
+        for tick in 1..=6 {
+            let position = DVec3::new(tick as f64 * 0.5, 64.0, 0.0);
+            detector.update(&snapshot(tick, position, velocity));
+        }
+        let lagback = detector.update(&snapshot(7, DVec3::new(1.0, 64.0, 0.0), velocity));
+        assert_eq!(lagback.map(|lagback| lagback.actual), Some(DVec3::new(1.0, 64.0, 0.0)));
+    }
+}
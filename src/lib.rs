@@ -1,23 +1,59 @@
+pub mod banner;
+pub mod chat_log;
+pub mod client_loop;
+pub mod command;
+pub mod config;
+pub mod death;
+pub mod dispatch;
+pub mod dump;
+pub mod event;
+pub mod friends;
+pub mod ground;
+pub mod handles;
+pub mod hook;
+pub mod keybind;
+pub mod keys;
+pub mod lagback;
+pub mod modules;
+pub mod movement_log;
+pub mod notifications;
+pub mod packet_interceptor;
+pub mod preflight;
+pub mod profiles;
+pub mod profiling;
+pub mod render;
+pub mod restoration;
+pub mod rotation;
+pub mod selftest;
+pub mod sdk;
+pub mod session_info;
+pub mod slot_switch;
+pub mod snapshot;
+pub mod targets;
+pub mod throwing;
+pub mod timing;
+pub mod tps;
+pub mod tracking;
+pub mod velocity;
+pub mod world_change;
+
 use anyhow::Result;
 use jni::JavaVM;
-use jni::sys::{jsize, jint};
 
 use windows::{ 
     Win32::Foundation::*, 
     Win32::System::SystemServices::*, 
     Win32::System::Console::{AllocConsole, FreeConsole},
-    Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress},
 };
-use windows::core::s;
 
-use tracing::{info, Level, error, trace, debug};
+use tracing::{info, Level, error, trace, warn};
 use tracing_subscriber::FmtSubscriber;
 
+use std::sync::OnceLock;
 use std::thread;
 
-// Define a type alias for the `JNI_GetCreatedJavaVMs` function pointer.
-#[allow(non_camel_case_types)]
-type JNI_GetCreatedJavaVMs_Fn = fn(vm_buf: *mut *mut JavaVM, buf_len: jsize, num_vms: *mut jsize) -> jint;
+// The JVM the client attached to, kept to restore the game state when the DLL is unloaded.
+static JAVA_VM: OnceLock<JavaVM> = OnceLock::new();
 
 // The entry function responsible for the primary execution thread of the application.
 pub fn main_thread() {
@@ -29,6 +65,14 @@ pub fn main_thread() {
     tracing::subscriber::set_global_default(subscriber)
         .expect("Setting the default subscriber failed");
 
+    // Bail out before allocating anything when injected into the wrong process, the message box is
+    // the only thing the user will see.
+    if let Err(e) = preflight::check() {
+        error!("Pre-flight check failed: {}", e);
+        preflight::report(&e);
+        return;
+    }
+
     // Initialize a Windows console using the Win32 API
     // Attempt to allocate a console and log any error that occurs.
     if let Err(e) = unsafe { AllocConsole() } {
@@ -46,43 +90,84 @@ pub fn main_thread() {
 
 // Responsible for starting the client, including attaching to the JVM and initializing logging.
 pub fn start_client() -> Result<()> {
-    // Attempt to obtain a module handle for the JVM DLL and log if successful.
-    let jvm_dll_handle = unsafe { GetModuleHandleA(s!("jvm.dll")) }?;
-    trace!("Obtained jvm.dll handle: {:?}", jvm_dll_handle);
-
-    // Retrieve the `JNI_GetCreatedJavaVMs` function from the JVM DLL.
-    let jni_get_created_java_vms_fn_ptr = unsafe { GetProcAddress(jvm_dll_handle, s!("JNI_GetCreatedJavaVMs")) };
-    let get_created_java_vms: JNI_GetCreatedJavaVMs_Fn = unsafe { std::mem::transmute(jni_get_created_java_vms_fn_ptr) };
-    debug!("Address of JNI_GetCreatedJavaVMs function: {:?}", jni_get_created_java_vms_fn_ptr);
-
-    // Initialize variables to hold the JVM instance and JVM count.
-    debug!("Retrieving the list of JVMs.");
-    let mut jvm_instance: *mut JavaVM = std::ptr::null_mut();
-    let mut number_of_jvms: jsize = 0;
-    let get_jvms_result = get_created_java_vms(&mut jvm_instance as _, 1, &mut number_of_jvms as _);
-    debug!("Number of JVMs found: {}", number_of_jvms);
-    trace!("JNI_GetCreatedJavaVMs response code: {:?}", get_jvms_result);
-
-    // Convert the raw JVM pointer to a `JavaVM` instance and attach the current thread as a daemon.
-    let jvm = unsafe { JavaVM::from_raw(jvm_instance as _)? };
-    jvm.attach_current_thread_as_daemon()?;
+    // Find the JVM running Minecraft among those in the process and attach to it as a daemon.
+    let jvm = preflight::retrieve_java_vm()?;
+    let jvm = JAVA_VM.get_or_init(|| jvm);
 
     // Retrieve the Java environment for further operations.
     let mut jvm_environment = jvm.get_env()?;
     info!("Obtained the JVM environment.");
 
-    // TODO: Implement a name remapper for working with obfuscated code.
-    // TODO: Generate an SDK based on source code or mappings.
+    // The version decides which mappings are loaded and how wrappers call methods whose signature
+    // changed between versions.
+    let (game_version, version_range) = sdk::version::detect(&mut jvm_environment)?;
+    match version_range {
+        Some(range) => info!("Detected Minecraft {} ({})", game_version, range),
+        None => warn!("Minecraft {} is not supported, assuming {}", game_version, sdk::version::GameVersion::LATEST),
+    }
+
+    // Without mappings names are looked up as they are, which works in a development environment.
+    // Obfuscated clients without shipped mappings get the ones matching their version downloaded.
+    if !sdk::mappings::uses_yarn_names(&mut jvm_environment) {
+        let mappings = match sdk::mappings::Mappings::load(game_version) {
+            Ok(mappings) => mappings,
+            Err(e) => {
+                warn!("No local mappings for {}: {}", game_version, e);
+                sdk::mappings::download::load_for(&mut jvm_environment, game_version)?
+            }
+        };
+        info!(
+            "Loaded {} mappings for Minecraft {} from {}: {} classes, {} methods, {} fields",
+            mappings.namespace,
+            game_version,
+            mappings.source,
+            mappings.class_count(),
+            mappings.method_count(),
+            mappings.field_count()
+        );
+        sdk::mappings::install(mappings);
+
+        // A mappings file for another version fails on the first lookup of each missing name, list
+        // them all upfront instead.
+        if let Some(coverage) = sdk::mappings::check_coverage(sdk::mappings::REQUIRED) {
+            if coverage.is_complete() {
+                info!("Mappings cover all {} required classes", coverage.classes);
+            } else {
+                warn!("{} from the mappings, they may be for another version", coverage);
+                for requirement in &coverage.missing {
+                    warn!("Not in the mappings: {}", requirement);
+                }
+            }
+        }
+    }
+
+    // Log the build identity first, so every log and bug report names the build that ran.
+    banner::log_startup(&mut jvm_environment);
 
     // Look up the Minecraft client class using the JNI environment.
-    let minecraft_client_class = jvm_environment.find_class("net/minecraft/client/MinecraftClient")?;
+    let minecraft_client_class = sdk::jni::cache::class(&mut jvm_environment, "net/minecraft/client/MinecraftClient")?;
     trace!("Located MinecraftClient class: {:?}", minecraft_client_class);
 
     // Retrieve the field ID for the static `instance` field of the Minecraft client class.
-    let minecraft_client_instance_field_id = jvm_environment.get_static_field_id(minecraft_client_class, "instance", "Lnet/minecraft/client/MinecraftClient;")?;
+    let minecraft_client_instance_field_id = sdk::jni::cache::static_field_id(&mut jvm_environment, "net/minecraft/client/MinecraftClient", "instance", "Lnet/minecraft/client/MinecraftClient;")?;
     trace!("MinecraftClient `instance` field ID: {:?}", minecraft_client_instance_field_id);
 
-    Ok(())
+    // Everything from here on is driven by the events the client loop posts.
+    client_loop::run(&mut jvm_environment)
+}
+
+// Runs the registered restorations on the unloading thread, which has to attach to the JVM first.
+fn restore_on_detach() {
+    let Some(jvm) = JAVA_VM.get() else {
+        return;
+    };
+    match jvm.attach_current_thread_as_daemon() {
+        Ok(mut env) => {
+            let restored = restoration::restore_all(&mut env);
+            info!("Unloading, restored {} changes", restored);
+        }
+        Err(e) => error!("Failed to attach to the JVM to restore the game state: {:?}", e),
+    }
 }
 
 // The DLL entry point, which is executed when the DLL is loaded or unloaded.
@@ -99,8 +184,9 @@ extern "system" fn DllMain(
         DLL_PROCESS_ATTACH => {
             thread::spawn(main_thread);
         },
-        // No action is taken when the DLL is detached.
-        DLL_PROCESS_DETACH => {},
+        // `reserved` is null when the DLL is unloaded while the game keeps running, e.g. by an
+        // ejector, and set when the whole process exits, where there is nothing left to restore.
+        DLL_PROCESS_DETACH if reserved.is_null() => restore_on_detach(),
         _ => {}
     }
 
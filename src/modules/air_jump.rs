@@ -0,0 +1,125 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::ground::{GroundEvent, GroundTracker};
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::math::DVec3;
+use crate::snapshot::TickSnapshot;
+use crate::velocity::VelocityIntent;
+
+// Upward velocity of a vanilla jump.
+const JUMP_VELOCITY: f64 = 0.42;
+
+// Allows jumping again while in the air, a limited number of times per airtime.
+#[derive(Debug)]
+pub struct AirJump {
+    // Scales the jump velocity, 1.0 is a normal jump.
+    pub jump_multiplier: f64,
+    // Extra jumps allowed before the player has to land again.
+    pub max_extra_jumps: u32,
+    used_jumps: u32,
+    ground: GroundTracker,
+    // Whether the jump key was held on the previous tick, only a new press jumps.
+    jump_held: bool,
+}
+
+impl Default for AirJump {
+    fn default() -> Self {
+        Self {
+            jump_multiplier: 1.0,
+            max_extra_jumps: 1,
+            used_jumps: 0,
+            ground: GroundTracker::new(),
+            jump_held: false,
+        }
+    }
+}
+
+impl AirJump {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "AirJump", category: Category::Movement };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Feeds the ground transition of the current tick, landing gives back the extra jumps.
+    pub fn on_ground_event(&mut self, event: Option<GroundEvent>) {
+        if let Some(GroundEvent::Landed { .. }) = event {
+            self.used_jumps = 0;
+        }
+    }
+
+    // Called when the jump key is pressed. Jumps if the player is airborne and has extra jumps left,
+    // keeping the horizontal momentum. Returns whether it jumped.
+    pub fn on_jump_pressed(&mut self, snapshot: &TickSnapshot, intent: &mut VelocityIntent) -> bool {
+        if self.ground.on_ground() || self.used_jumps >= self.max_extra_jumps {
+            return false;
+        }
+        let Some(player) = snapshot.player else {
+            return false;
+        };
+
+        let velocity = player.velocity;
+        intent.set(Self::INFO.name, 0, DVec3::new(velocity.x, JUMP_VELOCITY * self.jump_multiplier, velocity.z));
+
+        self.used_jumps += 1;
+        true
+    }
+}
+
+impl Module for AirJump {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("jump_multiplier", SettingValue::Number(self.jump_multiplier)),
+            ("max_extra_jumps", SettingValue::Number(self.max_extra_jumps as f64)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("jump_multiplier", SettingValue::Number(multiplier)) if multiplier > 0.0 && multiplier <= 5.0 => {
+                self.jump_multiplier = multiplier
+            }
+            ("max_extra_jumps", SettingValue::Number(jumps)) if (0.0..=10.0).contains(&jumps) && jumps.fract() == 0.0 => {
+                self.max_extra_jumps = jumps as u32
+            }
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.ground.reset();
+        self.used_jumps = 0;
+        self.jump_held = false;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, intent: &mut VelocityIntent) -> Result<()> {
+        let ground_event = self.ground.update(&event.snapshot);
+        self.on_ground_event(ground_event);
+        if !event.snapshot.in_world() || event.snapshot.screen_open {
+            self.jump_held = false;
+            return Ok(());
+        }
+
+        let jumping = env.with_local_frame(4, |env| -> Result<bool> {
+            let Some(player) = event.client().get_player(env)? else {
+                return Ok(false);
+            };
+            Ok(player.get_input(env)?.is_jumping(env)?)
+        })?;
+        let pressed = jumping && !self.jump_held;
+        self.jump_held = jumping;
+        if pressed {
+            self.on_jump_pressed(&event.snapshot, intent);
+        }
+        Ok(())
+    }
+}
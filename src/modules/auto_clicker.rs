@@ -0,0 +1,181 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Choice, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::hand::Hand;
+use crate::sdk::game::options::Key;
+use crate::tps;
+use crate::velocity::VelocityIntent;
+
+// Clicks beyond this in one tick are dropped, so a stalled tick does not turn into a burst of clicks.
+const MAX_CLICKS_PER_TICK: u32 = 2;
+
+// Lowest `tps::tick_scale` the delays follow. A frozen server would otherwise stop the clicks for good.
+const MIN_TICK_SCALE: f64 = 0.25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickInput {
+    // Queues clicks of the attack key, which the game handles like the player's own: it attacks the
+    // entity under the crosshair, mines or swings at the air.
+    Key,
+    // Attacks the entity under the crosshair through the interaction manager and swings, doing nothing
+    // else when aiming at a block or nothing.
+    Interact,
+}
+
+impl Choice for ClickInput {
+    const ALL: &'static [ClickInput] = &[ClickInput::Key, ClickInput::Interact];
+
+    fn name(self) -> &'static str {
+        match self {
+            ClickInput::Key => "key",
+            ClickInput::Interact => "interact",
+        }
+    }
+}
+
+// Clicks the attack button at a varying rate. Every delay is drawn from a triangular distribution
+// between the minimum and maximum CPS, peaking in the middle, so the rate wanders like a player's
+// instead of holding one value. The delays stretch with the server's tick rate, so a lagging server
+// sees the same number of clicks per tick it processes.
+#[derive(Debug)]
+pub struct AutoClicker {
+    pub min_cps: f64,
+    pub max_cps: f64,
+    // Only clicks while the player holds the attack key.
+    pub hold_only: bool,
+    pub input: ClickInput,
+    next_click: Option<Instant>,
+    random: XorShift,
+}
+
+impl Default for AutoClicker {
+    fn default() -> Self {
+        Self { min_cps: 8.0, max_cps: 12.0, hold_only: true, input: ClickInput::Key, next_click: None, random: XorShift::seeded() }
+    }
+}
+
+impl AutoClicker {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "AutoClicker", category: Category::Combat };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_delay(&mut self, tick_scale: f64) -> Duration {
+        let (low, high) = if self.min_cps <= self.max_cps { (self.min_cps, self.max_cps) } else { (self.max_cps, self.min_cps) };
+        let spread = (self.random.next_f64() + self.random.next_f64()) / 2.0;
+        Duration::from_secs_f64(1.0 / (low + (high - low) * spread) / tick_scale.max(MIN_TICK_SCALE))
+    }
+
+    fn click(&self, env: &mut JNIEnv, client: &MinecraftClient) -> Result<()> {
+        match self.input {
+            ClickInput::Key => {
+                let options = client.get_options(env)?;
+                options.click_key(env, Key::Attack)?;
+                env.delete_local_ref(options.inner)?;
+            }
+            ClickInput::Interact => {
+                let (Some(player), Some(interaction_manager)) = (client.get_player(env)?, client.get_interaction_manager(env)?) else {
+                    return Ok(());
+                };
+                if let Some(target) = client.get_crosshair_entity(env)? {
+                    interaction_manager.attack_entity(env, &player, &target)?;
+                    player.swing_hand(env, Hand::MainHand)?;
+                    env.delete_local_ref(target.inner)?;
+                }
+                env.delete_local_ref(interaction_manager.inner)?;
+                env.delete_local_ref(player.as_entity().inner)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Module for AutoClicker {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("min_cps", SettingValue::Number(self.min_cps)),
+            ("max_cps", SettingValue::Number(self.max_cps)),
+            ("hold_only", SettingValue::Bool(self.hold_only)),
+            ("input", self.input.to_setting()),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("min_cps", SettingValue::Number(cps)) if (1.0..=20.0).contains(&cps) => self.min_cps = cps,
+            ("max_cps", SettingValue::Number(cps)) if (1.0..=20.0).contains(&cps) => self.max_cps = cps,
+            ("hold_only", SettingValue::Bool(hold_only)) => self.hold_only = hold_only,
+            ("input", value) => self.input = ClickInput::from_setting(Self::INFO.name, name, &value)?,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.next_click = None;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        if event.snapshot.player.is_none() || event.snapshot.screen_open || event.snapshot.automation_suspended(None) {
+            self.next_click = None;
+            return Ok(());
+        }
+
+        let tick_scale = tps::tick_scale();
+        env.with_local_frame(16, |env| -> Result<()> {
+            let client = event.client();
+            if self.hold_only {
+                let options = client.get_options(env)?;
+                if !options.is_key_pressed(env, Key::Attack)? {
+                    self.next_click = None;
+                    return Ok(());
+                }
+            }
+
+            let now = Instant::now();
+            let mut next_click = self.next_click.unwrap_or(now);
+            let mut clicks = 0;
+            while next_click <= now && clicks < MAX_CLICKS_PER_TICK {
+                self.click(env, &client)?;
+                next_click += self.next_delay(tick_scale);
+                clicks += 1;
+            }
+            if next_click <= now {
+                next_click = now + self.next_delay(tick_scale);
+            }
+            self.next_click = Some(next_click);
+            Ok(())
+        })
+    }
+}
+
+// Xorshift generator for the click timing, which needs no better randomness than this.
+#[derive(Debug)]
+struct XorShift(u64);
+
+impl XorShift {
+    fn seeded() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        // The state must never be zero, it would stay zero.
+        Self(nanos | 1)
+    }
+
+    // Uniform in [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::render::{scene, Color, Shape};
+use crate::sdk::game::block::{BlockPos, KnownBlock};
+use crate::sdk::game::camera::capture_projection;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::world::MAX_SEARCH_RADIUS;
+use crate::sdk::math::{DVec3, RustBox};
+use crate::velocity::VelocityIntent;
+
+// Outlines nearby ores and containers through walls on the overlay: containers in aqua, spawners in
+// red and ores in white. A search is thousands of block state queries, so only one kind of block is
+// searched per tick and the outlines of the others are kept from their last search.
+#[derive(Debug)]
+pub struct BlockEsp {
+    // Kinds of blocks to outline.
+    pub blocks: Vec<KnownBlock>,
+    // Blocks around the player that are searched, capped at `MAX_SEARCH_RADIUS`.
+    pub radius: i32,
+    found: HashMap<KnownBlock, Vec<BlockPos>>,
+    // Index into `blocks` of the kind searched on the next tick.
+    next_search: usize,
+}
+
+impl Default for BlockEsp {
+    fn default() -> Self {
+        Self {
+            blocks: vec![KnownBlock::DiamondOre, KnownBlock::DeepslateDiamondOre, KnownBlock::AncientDebris, KnownBlock::Spawner],
+            radius: MAX_SEARCH_RADIUS,
+            found: HashMap::new(),
+            next_search: 0,
+        }
+    }
+}
+
+impl BlockEsp {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "BlockESP", category: Category::Render };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn color(block: KnownBlock) -> Color {
+        if block.is_container() {
+            Color::AQUA
+        } else if block == KnownBlock::Spawner {
+            Color::RED
+        } else {
+            Color::WHITE
+        }
+    }
+}
+
+impl Module for BlockEsp {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        let blocks: Vec<_> = self.blocks.iter().map(|block| block.id()).collect();
+        vec![
+            ("blocks", SettingValue::Text(blocks.join(","))),
+            ("radius", SettingValue::Number(self.radius as f64)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("blocks", SettingValue::Text(blocks)) => match parse_block_list(&blocks) {
+                Some(blocks) => {
+                    self.blocks = blocks;
+                    self.found.clear();
+                    self.next_search = 0;
+                }
+                None => bail!("Invalid value {} for {}.{}", blocks, Self::INFO.name, name),
+            },
+            ("radius", SettingValue::Number(radius)) if (1.0..=MAX_SEARCH_RADIUS as f64).contains(&radius) && radius.fract() == 0.0 => {
+                self.radius = radius as i32
+            }
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        scene().clear(Self::INFO.name);
+        self.found.clear();
+        self.next_search = 0;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player) = event.snapshot.player else {
+            self.found.clear();
+            scene().clear(Self::INFO.name);
+            return Ok(());
+        };
+
+        let shapes = env.with_local_frame(16, |env| -> Result<Vec<Shape>> {
+            let client = event.client();
+            let Some(world) = client.get_world(env)? else {
+                return Ok(Vec::new());
+            };
+
+            if !self.blocks.is_empty() {
+                let block = self.blocks[self.next_search % self.blocks.len()];
+                self.next_search = (self.next_search + 1) % self.blocks.len();
+                let positions = world.find_blocks(env, BlockPos::of_floored(player.position), self.radius, block)?;
+                self.found.insert(block, positions);
+            }
+
+            let projection = capture_projection(env, &client)?;
+            let mut shapes = Vec::new();
+            for (&block, positions) in &self.found {
+                let color = Self::color(block);
+                for pos in positions {
+                    let corner = DVec3::new(pos.x as f64, pos.y as f64, pos.z as f64);
+                    let bounding_box = RustBox::new(corner, corner + DVec3::new(1.0, 1.0, 1.0));
+                    if let Some(rect) = projection.project_box(bounding_box) {
+                        shapes.push(Shape::Rect { rect, color });
+                    }
+                }
+            }
+            Ok(shapes)
+        })?;
+        scene().submit(Self::INFO.name, shapes);
+        Ok(())
+    }
+}
+
+// Parses a comma-separated list of block ids, `minecraft:` may be left out. `None` if one of them is
+// not a `KnownBlock`.
+fn parse_block_list(blocks: &str) -> Option<Vec<KnownBlock>> {
+    blocks
+        .split(',')
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .map(|block| if block.contains(':') { block.to_string() } else { format!("minecraft:{}", block) })
+        .map(|id| KnownBlock::from_id(&id))
+        .collect()
+}
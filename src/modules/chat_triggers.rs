@@ -0,0 +1,226 @@
+use anyhow::{anyhow, bail, Result};
+use jni::JNIEnv;
+use tracing::{info, warn};
+
+use crate::chat_log::ChatLog;
+use crate::dispatch;
+use crate::event::TickEvent;
+use crate::modules::{manager, Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::network::ClientPlayNetworkHandler;
+use crate::velocity::VelocityIntent;
+
+// A chat line pattern. `*` matches any run of characters and `?` a single character, everything
+// else matches itself, case-sensitively. The pattern has to match the whole line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatPattern {
+    pattern: Vec<char>,
+}
+
+impl ChatPattern {
+    pub fn parse(pattern: &str) -> Result<Self> {
+        if pattern.trim().is_empty() {
+            bail!("Empty chat pattern");
+        }
+        Ok(Self { pattern: pattern.chars().collect() })
+    }
+
+    pub fn matches(&self, line: &str) -> bool {
+        let line: Vec<char> = line.chars().collect();
+        let (mut p, mut l) = (0, 0);
+        // Position after the last `*` and the line position it was tried at, to backtrack to.
+        let mut backtrack = None;
+
+        while l < line.len() {
+            match self.pattern.get(p) {
+                Some('*') => {
+                    backtrack = Some((p + 1, l));
+                    p += 1;
+                }
+                Some(&c) if c == '?' || c == line[l] => {
+                    p += 1;
+                    l += 1;
+                }
+                _ => match backtrack {
+                    Some((star_p, star_l)) => {
+                        p = star_p;
+                        l = star_l + 1;
+                        backtrack = Some((star_p, star_l + 1));
+                    }
+                    None => return false,
+                },
+            }
+        }
+
+        self.pattern[p..].iter().all(|&c| c == '*')
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TriggerAction {
+    // Runs a server command, given without the leading slash.
+    Command(String),
+    // Sends a chat message.
+    Say(String),
+    // Toggles a module by name.
+    Toggle(String),
+}
+
+impl TriggerAction {
+    pub fn parse(action: &str) -> Result<Self> {
+        let Some((kind, argument)) = action.split_once(':') else {
+            bail!("Action '{}' is missing its kind, expected command:, say: or toggle:", action);
+        };
+
+        let argument = argument.trim().to_string();
+        if argument.is_empty() {
+            bail!("Action '{}' has nothing to do", action);
+        }
+
+        match kind.trim() {
+            "command" => Ok(TriggerAction::Command(argument.trim_start_matches('/').to_string())),
+            "say" => Ok(TriggerAction::Say(argument)),
+            "toggle" => Ok(TriggerAction::Toggle(argument)),
+            kind => bail!("Unknown action kind '{}'", kind),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatTrigger {
+    pub pattern: ChatPattern,
+    pub action: TriggerAction,
+}
+
+// Runs actions when received chat lines match configured patterns.
+#[derive(Default)]
+pub struct ChatTriggers {
+    triggers: Vec<ChatTrigger>,
+    // The `triggers` setting the triggers were loaded from, lines separated by `;`.
+    source: String,
+    chat_log: ChatLog,
+}
+
+impl ChatTriggers {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "ChatTriggers", category: Category::Misc };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Loads triggers from config lines of the form `pattern -> kind:argument`, e.g.
+    // `*joined the game -> say:Welcome!`. Blank lines and lines starting with `#` are ignored.
+    // Invalid lines are logged and skipped, so one typo does not disable the other triggers.
+    pub fn load(config: &str) -> Self {
+        let mut triggers = Vec::new();
+
+        for (number, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match parse_trigger(line) {
+                Ok(trigger) => triggers.push(trigger),
+                Err(e) => warn!("Skipping chat trigger on line {}: {}", number + 1, e),
+            }
+        }
+
+        Self { triggers, source: config.lines().collect::<Vec<_>>().join(";"), chat_log: ChatLog::default() }
+    }
+
+    pub fn triggers(&self) -> &[ChatTrigger] {
+        &self.triggers
+    }
+
+    // Runs the actions of every trigger matching one of the lines. Commands and messages are sent
+    // right away, the names of modules to toggle are returned for the caller to toggle.
+    pub fn handle_lines(&self, env: &mut JNIEnv, network_handler: &ClientPlayNetworkHandler, lines: &[String]) -> Result<Vec<String>> {
+        let mut toggles = Vec::new();
+
+        for line in lines {
+            for trigger in self.triggers.iter().filter(|trigger| trigger.pattern.matches(line)) {
+                info!("Chat trigger matched '{}'", line);
+                match &trigger.action {
+                    TriggerAction::Command(command) => network_handler.send_chat_command(env, command)?,
+                    TriggerAction::Say(message) => network_handler.send_chat_message(env, message)?,
+                    TriggerAction::Toggle(module) => toggles.push(module.clone()),
+                }
+            }
+        }
+
+        Ok(toggles)
+    }
+}
+
+impl Module for ChatTriggers {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![("triggers", SettingValue::Text(self.source.clone()))]
+    }
+
+    // The setting holds the config lines of `load` separated by `;`, as a setting is a single line.
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("triggers", SettingValue::Text(source)) => {
+                let lines: Vec<&str> = source.split(';').map(str::trim).filter(|line| !line.is_empty()).collect();
+                for line in &lines {
+                    parse_trigger(line).map_err(|e| anyhow!("Invalid chat trigger '{}': {}", line, e))?;
+                }
+                self.triggers = Self::load(&lines.join("\n")).triggers;
+                self.source = lines.join(";");
+            }
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.chat_log.clear();
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        if !event.snapshot.in_world() {
+            return Ok(());
+        }
+
+        let client = event.client();
+        let lines = self.chat_log.poll(env, &client)?;
+        if lines.is_empty() || self.triggers.is_empty() {
+            return Ok(());
+        }
+
+        let toggles = env.with_local_frame(4, |env| -> Result<Vec<String>> {
+            let Some(network_handler) = client.get_network_handler(env)? else {
+                return Ok(Vec::new());
+            };
+            self.handle_lines(env, &network_handler, &lines)
+        })?;
+        // The manager is busy ticking this module, the toggles run once the tick is over.
+        for module in toggles {
+            let queued = dispatch::dispatch("ChatTriggers", move |context| {
+                manager().toggle(context.env, context.client, &module)?;
+                Ok(())
+            });
+            if let Err(e) = queued {
+                warn!("Could not toggle a module from a chat trigger: {}", e);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_trigger(line: &str) -> Result<ChatTrigger> {
+    let Some((pattern, action)) = line.rsplit_once("->") else {
+        bail!("Expected 'pattern -> action'");
+    };
+
+    Ok(ChatTrigger {
+        pattern: ChatPattern::parse(pattern.trim())?,
+        action: TriggerAction::parse(action)?,
+    })
+}
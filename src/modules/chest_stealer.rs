@@ -0,0 +1,145 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+use tracing::debug;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::player::ClientPlayerEntity;
+use crate::sdk::game::screen_handler::{ScreenHandler, SlotActionType, GENERIC_CONTAINER_CLASS, PLAYER_SLOT_COUNT};
+use crate::snapshot::TickSnapshot;
+use crate::timing::TickTimer;
+use crate::velocity::VelocityIntent;
+
+// Empties chests, barrels and ender chests into the player's inventory. While one is open, its
+// stacks are shift-clicked out one at a time in slot order. It stops when the container is empty or a
+// stack no longer fits, and can close the screen then.
+#[derive(Debug)]
+pub struct ChestStealer {
+    // Ticks to wait before the first and between two moved stacks.
+    pub delay_ticks: u64,
+    // Closes the container once it is empty or the inventory is full.
+    pub close_screen: bool,
+    // Sync id of the container being emptied, and whether it is done.
+    container: Option<(i32, bool)>,
+    timer: TickTimer,
+}
+
+impl Default for ChestStealer {
+    fn default() -> Self {
+        Self { delay_ticks: 2, close_screen: true, container: None, timer: TickTimer::new() }
+    }
+}
+
+impl ChestStealer {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "ChestStealer", category: Category::Player };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tick(&mut self, env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot) -> Result<()> {
+        if !snapshot.in_world() || !snapshot.screen_open || snapshot.automation_suspended(None) {
+            self.container = None;
+            return Ok(());
+        }
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let (Some(player), Some(interaction_manager)) = (client.get_player(env)?, client.get_interaction_manager(env)?) else {
+                return Ok(());
+            };
+            let screen_handler = player.get_current_screen_handler(env)?;
+            if screen_handler.get_class_name(env)? != GENERIC_CONTAINER_CLASS {
+                self.container = None;
+                return Ok(());
+            }
+
+            let sync_id = screen_handler.get_sync_id(env)?;
+            if self.container.map(|(id, _)| id) != Some(sync_id) {
+                self.container = Some((sync_id, false));
+                self.timer.reset();
+            }
+            if self.container == Some((sync_id, true)) || !self.timer.has_passed(self.delay_ticks) {
+                return Ok(());
+            }
+            self.timer.reset();
+
+            let Some((slot_id, count)) = first_stack(env, &screen_handler)? else {
+                debug!("Container {} is empty", sync_id);
+                return self.finish(env, &player, sync_id);
+            };
+            interaction_manager.click_slot(env, sync_id, slot_id as i32, 0, SlotActionType::QuickMove, &player)?;
+
+            // The click is applied locally right away. A stack that did not move has no room left.
+            if stack_count(env, &screen_handler, slot_id)? == count {
+                debug!("Inventory is full, leaving container {}", sync_id);
+                return self.finish(env, &player, sync_id);
+            }
+            Ok(())
+        })
+    }
+
+    fn finish(&mut self, env: &mut JNIEnv, player: &ClientPlayerEntity, sync_id: i32) -> Result<()> {
+        self.container = Some((sync_id, true));
+        if self.close_screen {
+            player.close_handled_screen(env)?;
+        }
+        Ok(())
+    }
+}
+
+impl Module for ChestStealer {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("delay_ticks", SettingValue::Number(self.delay_ticks as f64)),
+            ("close_screen", SettingValue::Bool(self.close_screen)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("delay_ticks", SettingValue::Number(ticks)) if (0.0..=200.0).contains(&ticks) && ticks.fract() == 0.0 => {
+                self.delay_ticks = ticks as u64
+            }
+            ("close_screen", SettingValue::Bool(close_screen)) => self.close_screen = close_screen,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.container = None;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        self.tick(env, &event.client(), &event.snapshot)
+    }
+}
+
+// Id and count of the first non-empty slot of the container itself, the player's slots after it are
+// left out.
+fn first_stack(env: &mut JNIEnv, screen_handler: &ScreenHandler) -> Result<Option<(usize, i32)>> {
+    let container_slots = screen_handler.get_slot_count(env)?.saturating_sub(PLAYER_SLOT_COUNT);
+    for slot_id in 0..container_slots {
+        let count = stack_count(env, screen_handler, slot_id)?;
+        if count > 0 {
+            return Ok(Some((slot_id, count)));
+        }
+    }
+    Ok(None)
+}
+
+// Items in the slot, 0 if it is empty.
+fn stack_count(env: &mut JNIEnv, screen_handler: &ScreenHandler, slot_id: usize) -> Result<i32> {
+    let slot = screen_handler.get_slot(env, slot_id)?;
+    let stack = slot.get_stack(env)?;
+    let count = if stack.is_empty(env)? { 0 } else { stack.get_count(env)? };
+    env.delete_local_ref(stack.inner)?;
+    env.delete_local_ref(slot.inner)?;
+    Ok(count)
+}
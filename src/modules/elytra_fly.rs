@@ -0,0 +1,105 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::movement_log;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::math::rotations;
+use crate::snapshot::TickSnapshot;
+use crate::velocity::VelocityIntent;
+
+// Steers elytra flight without fireworks by pushing the player along the look direction while gliding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElytraFly {
+    // Velocity added along the look direction each tick.
+    pub speed: f64,
+    // Velocity added upwards while jumping and downwards while sneaking each tick.
+    pub vertical_speed: f64,
+    // The resulting velocity is never longer than this, nor than the global speed ceiling.
+    pub max_speed: f64,
+}
+
+impl Default for ElytraFly {
+    fn default() -> Self {
+        Self {
+            speed: 0.05,
+            vertical_speed: 0.04,
+            max_speed: 1.5,
+        }
+    }
+}
+
+impl ElytraFly {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "ElytraFly", category: Category::Movement };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tick(&self, env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot, intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player_snapshot) = snapshot.player else {
+            return Ok(());
+        };
+        if player_snapshot.on_ground || snapshot.screen_open {
+            return Ok(());
+        }
+
+        env.with_local_frame(8, |env| -> Result<()> {
+            let Some(player) = client.get_player(env)? else {
+                return Ok(());
+            };
+            if !player.is_fall_flying(env)? {
+                return Ok(());
+            }
+
+            let input = player.get_input(env)?;
+            let vertical = match (input.is_jumping(env)?, input.is_sneaking(env)?) {
+                (true, false) => self.vertical_speed,
+                (false, true) => -self.vertical_speed,
+                _ => 0.0,
+            };
+
+            let direction = rotations::direction_from_rotation(player_snapshot.yaw, player_snapshot.pitch);
+            let mut velocity = player_snapshot.velocity + direction * self.speed;
+            velocity.y += vertical;
+
+            let max_speed = movement_log::speed_ceiling_per_tick().map_or(self.max_speed, |ceiling| ceiling.min(self.max_speed));
+            let length = velocity.length();
+            if length > max_speed {
+                velocity = velocity * (max_speed / length);
+            }
+
+            intent.set(Self::INFO.name, 0, velocity);
+            Ok(())
+        })
+    }
+}
+
+impl Module for ElytraFly {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("speed", SettingValue::Number(self.speed)),
+            ("vertical_speed", SettingValue::Number(self.vertical_speed)),
+            ("max_speed", SettingValue::Number(self.max_speed)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("speed", SettingValue::Number(speed)) if speed >= 0.0 => self.speed = speed,
+            ("vertical_speed", SettingValue::Number(speed)) if speed >= 0.0 => self.vertical_speed = speed,
+            ("max_speed", SettingValue::Number(speed)) if speed > 0.0 => self.max_speed = speed,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, intent: &mut VelocityIntent) -> Result<()> {
+        self.tick(env, &event.client(), &event.snapshot, intent)
+    }
+}
@@ -0,0 +1,127 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::friends::friends;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::render::{scene, Color, Shape};
+use crate::sdk::game::camera::capture_projection;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::living_entity::LivingEntity;
+use crate::velocity::VelocityIntent;
+
+// Height of a line of overlay text in pixels, for placing names above the box.
+const TEXT_HEIGHT: f64 = 16.0;
+
+// Outlines living entities through walls on the overlay: players in red, friends in green and
+// everything else in white. The shapes are rebuilt every tick from the camera of the last frame, so
+// they trail fast movement by up to a tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Esp {
+    // Distance in blocks from the camera up to which entities are outlined.
+    pub range: f64,
+    pub players_only: bool,
+    // Shows the name above players.
+    pub names: bool,
+    // Draws a line from the middle of the screen to the feet of each entity.
+    pub tracers: bool,
+}
+
+impl Default for Esp {
+    fn default() -> Self {
+        Self { range: 64.0, players_only: false, names: true, tracers: false }
+    }
+}
+
+impl Esp {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "ESP", category: Category::Render };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Module for Esp {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("range", SettingValue::Number(self.range)),
+            ("players_only", SettingValue::Bool(self.players_only)),
+            ("names", SettingValue::Bool(self.names)),
+            ("tracers", SettingValue::Bool(self.tracers)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("range", SettingValue::Number(range)) if range > 0.0 && range <= 512.0 => self.range = range,
+            ("players_only", SettingValue::Bool(players_only)) => self.players_only = players_only,
+            ("names", SettingValue::Bool(names)) => self.names = names,
+            ("tracers", SettingValue::Bool(tracers)) => self.tracers = tracers,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        scene().clear(Self::INFO.name);
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        if event.snapshot.player.is_none() {
+            scene().clear(Self::INFO.name);
+            return Ok(());
+        }
+
+        let shapes = env.with_local_frame(16, |env| -> Result<Vec<Shape>> {
+            let client = event.client();
+            let (Some(player), Some(world)) = (client.get_player(env)?, client.get_world(env)?) else {
+                return Ok(Vec::new());
+            };
+            let player_id = player.get_id(env)?;
+            let projection = capture_projection(env, &client)?;
+            let screen_center = (projection.width / 2.0, projection.height / 2.0);
+
+            let mut shapes = Vec::new();
+            world.for_each_entity(env, |env, entity| {
+                if !LivingEntity::is_living(env, entity)? || entity.get_id(env)? == player_id {
+                    return Ok(());
+                }
+                let is_player = entity.is_player(env)?;
+                if self.players_only && !is_player {
+                    return Ok(());
+                }
+                let bounding_box = entity.get_bounding_box(env)?;
+                if bounding_box.center().distance(projection.camera_pos) > self.range {
+                    return Ok(());
+                }
+                let Some(rect) = projection.project_box(bounding_box) else {
+                    return Ok(());
+                };
+
+                let name = if is_player { Some(entity.get_name(env)?) } else { None };
+                let color = match &name {
+                    Some(name) if friends().contains(&entity.get_uuid_string(env)?, name) => Color::GREEN,
+                    Some(_) => Color::RED,
+                    None => Color::WHITE,
+                };
+                shapes.push(Shape::Rect { rect, color });
+                if let Some(text) = name.filter(|_| self.names) {
+                    shapes.push(Shape::Text { position: (rect.min_x, rect.min_y - TEXT_HEIGHT), text, color });
+                }
+                if self.tracers {
+                    let feet = ((rect.min_x + rect.max_x) / 2.0, rect.max_y);
+                    shapes.push(Shape::Line { from: screen_center, to: feet, color });
+                }
+                Ok(())
+            })?;
+            Ok(shapes)
+        })?;
+        scene().submit(Self::INFO.name, shapes);
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::lagback::LagbackPause;
+use crate::modules::{Category, Choice, Module, ModuleInfo, SettingValue};
+use crate::movement_log;
+use crate::restoration::restorations;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::packet::PositionAndOnGroundC2SPacket;
+use crate::sdk::math::{movement, DVec3};
+use crate::velocity::VelocityIntent;
+
+// Servers kick players that float this many ticks, see `FlightMode::Packet`.
+const ANTI_KICK_INTERVAL: u32 = 40;
+
+// How far below the player the anti-kick packet reports them, more than the 1/32 block the server
+// still counts as floating.
+const ANTI_KICK_DROP: f64 = 0.04;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlightMode {
+    // Only replaces the velocity.
+    Vanilla,
+    // Also sends a position slightly lower every `ANTI_KICK_INTERVAL` ticks while hovering or rising,
+    // which resets the server's count of floating ticks before it kicks for flying. The next regular
+    // movement packet moves the player back up.
+    Packet,
+}
+
+impl Choice for FlightMode {
+    const ALL: &'static [FlightMode] = &[FlightMode::Vanilla, FlightMode::Packet];
+
+    fn name(self) -> &'static str {
+        match self {
+            FlightMode::Vanilla => "vanilla",
+            FlightMode::Packet => "packet",
+        }
+    }
+}
+
+// Flies by replacing the player's velocity every tick: the movement keys move horizontally at a fixed
+// speed, jump and sneak move up and down, and without input the player hovers in place. After the
+// server sets the player back the velocity is left alone for a while.
+#[derive(Debug)]
+pub struct Flight {
+    pub mode: FlightMode,
+    // Horizontal speed in blocks per tick.
+    pub speed: f64,
+    // Vertical speed in blocks per tick while jumping or sneaking.
+    pub vertical_speed: f64,
+    // Ticks since the player last went down, for `FlightMode::Packet`.
+    floating_ticks: u32,
+    lagback: LagbackPause,
+}
+
+impl Default for Flight {
+    fn default() -> Self {
+        Self {
+            mode: FlightMode::Vanilla,
+            speed: 0.5,
+            vertical_speed: 0.4,
+            floating_ticks: 0,
+            lagback: LagbackPause::new(20),
+        }
+    }
+}
+
+impl Flight {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "Flight", category: Category::Movement };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Module for Flight {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("mode", self.mode.to_setting()),
+            ("speed", SettingValue::Number(self.speed)),
+            ("vertical_speed", SettingValue::Number(self.vertical_speed)),
+            ("lagback_pause_ticks", SettingValue::Number(self.lagback.ticks as f64)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("mode", value) => self.mode = FlightMode::from_setting(Self::INFO.name, name, &value)?,
+            ("speed", SettingValue::Number(speed)) if speed >= 0.0 => self.speed = speed,
+            ("vertical_speed", SettingValue::Number(speed)) if speed >= 0.0 => self.vertical_speed = speed,
+            ("lagback_pause_ticks", SettingValue::Number(ticks)) if (0.0..=200.0).contains(&ticks) && ticks.fract() == 0.0 => {
+                self.lagback.ticks = ticks as u32
+            }
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    // Registers stopping the player where they are for when Flight is disabled, otherwise the last
+    // flight velocity flings them on. As a restoration it also happens when the client stops.
+    fn on_enable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.lagback.subscribe(Self::INFO.name);
+        restorations().register(Self::INFO.name, "flight velocity", |env| {
+            let client = MinecraftClient::get_instance(env)?;
+            if let Some(player) = client.get_player(env)? {
+                player.set_velocity(env, DVec3::ZERO)?;
+                env.delete_local_ref(player.as_entity().inner)?;
+            }
+            env.delete_local_ref(client.inner)?;
+            Ok(())
+        });
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.floating_ticks = 0;
+        self.lagback.reset();
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, intent: &mut VelocityIntent) -> Result<()> {
+        if self.lagback.is_paused() {
+            return Ok(());
+        }
+        let Some(player_snapshot) = event.snapshot.player else {
+            return Ok(());
+        };
+
+        env.with_local_frame(8, |env| -> Result<()> {
+            let Some(player) = event.client().get_player(env)? else {
+                return Ok(());
+            };
+
+            let input = player.get_input(env)?;
+            let speed = movement_log::speed_ceiling_per_tick().map_or(self.speed, |ceiling| ceiling.min(self.speed));
+            let mut velocity = movement::input_to_motion(
+                player_snapshot.yaw,
+                input.get_movement_forward(env)?,
+                input.get_movement_sideways(env)?,
+                speed,
+            );
+            velocity.y = match (input.is_jumping(env)?, input.is_sneaking(env)?) {
+                (true, false) => self.vertical_speed,
+                (false, true) => -self.vertical_speed,
+                _ => 0.0,
+            };
+
+            intent.set(Self::INFO.name, 0, velocity);
+
+            self.floating_ticks = if velocity.y < 0.0 { 0 } else { self.floating_ticks + 1 };
+            if self.mode == FlightMode::Packet && self.floating_ticks >= ANTI_KICK_INTERVAL {
+                self.floating_ticks = 0;
+                let position = player_snapshot.position - DVec3::new(0.0, ANTI_KICK_DROP, 0.0);
+                let network_handler = player.get_network_handler(env)?;
+                let packet = PositionAndOnGroundC2SPacket::new(env, position, false)?;
+                network_handler.send_packet(env, &packet.inner)?;
+            }
+            Ok(())
+        })
+    }
+}
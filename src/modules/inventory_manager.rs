@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+use tracing::debug;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::screen_handler::{SlotActionType, PLAYER_INVENTORY_SLOT_IDS};
+use crate::sdk::game::text::Text;
+use crate::snapshot::TickSnapshot;
+use crate::timing::TickTimer;
+use crate::velocity::VelocityIntent;
+
+// Ticks between two checks whether the inventory is full.
+const CHECK_INTERVAL_TICKS: u64 = 20;
+
+// Drops junk items once the inventory is full. Every second the main inventory and hotbar are checked,
+// and if no slot is empty the stacks whose item is on the junk list are queued and thrown out one at
+// a time. Items on the allowlist are never dropped, even when they are also on the junk list.
+#[derive(Debug)]
+pub struct InventoryManager {
+    // Registry ids of items to drop, e.g. `minecraft:rotten_flesh`.
+    pub junk: Vec<String>,
+    // Registry ids of items that are never dropped.
+    pub allowlist: Vec<String>,
+    // Ticks to wait between two dropped stacks.
+    pub drop_delay_ticks: u64,
+    // Slot ids waiting to be dropped, with the item id they held when queued.
+    queue: VecDeque<(i32, String)>,
+    check_timer: TickTimer,
+    drop_timer: TickTimer,
+}
+
+impl Default for InventoryManager {
+    fn default() -> Self {
+        Self {
+            junk: Vec::new(),
+            allowlist: Vec::new(),
+            drop_delay_ticks: 4,
+            queue: VecDeque::new(),
+            check_timer: TickTimer::new(),
+            drop_timer: TickTimer::new(),
+        }
+    }
+}
+
+impl InventoryManager {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "InventoryManager", category: Category::Player };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_junk(&self, item_id: &str) -> bool {
+        self.junk.iter().any(|junk| junk == item_id) && !self.allowlist.iter().any(|allowed| allowed == item_id)
+    }
+
+    pub fn tick(&mut self, env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot) -> Result<()> {
+        // An open screen may be a container, whose slot ids do not match the player inventory.
+        if !snapshot.in_world() || snapshot.screen_open || snapshot.automation_suspended(None) {
+            self.queue.clear();
+            return Ok(());
+        }
+
+        if self.queue.is_empty() && self.check_timer.has_passed(CHECK_INTERVAL_TICKS) {
+            self.check_timer.reset();
+            self.queue = self.find_junk_if_full(env, client)?;
+        }
+
+        if !self.queue.is_empty() && self.drop_timer.has_passed(self.drop_delay_ticks) {
+            self.drop_timer.reset();
+            if let Some((slot_id, item_id)) = self.queue.pop_front() {
+                self.drop_slot(env, client, slot_id, &item_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Returns the junk slots if the main inventory and hotbar have no empty slot left.
+    fn find_junk_if_full(&self, env: &mut JNIEnv, client: &MinecraftClient) -> Result<VecDeque<(i32, String)>> {
+        env.with_local_frame(8, |env| -> Result<VecDeque<(i32, String)>> {
+            let Some(player) = client.get_player(env)? else {
+                return Ok(VecDeque::new());
+            };
+            let screen_handler = player.get_current_screen_handler(env)?;
+
+            let mut full = true;
+            let mut junk = VecDeque::new();
+            screen_handler.for_each_slot(env, |env, slot| {
+                let id = slot.get_id(env)?;
+                if !full || !PLAYER_INVENTORY_SLOT_IDS.contains(&id) {
+                    return Ok(());
+                }
+                if !slot.has_stack(env)? {
+                    full = false;
+                    return Ok(());
+                }
+
+                let stack = slot.get_stack(env)?;
+                let item_id = stack.get_item_id(env)?;
+                env.delete_local_ref(stack.inner)?;
+                if self.is_junk(&item_id) {
+                    junk.push_back((id, item_id));
+                }
+                Ok(())
+            })?;
+
+            Ok(if full { junk } else { VecDeque::new() })
+        })
+    }
+
+    // Throws the whole stack in `slot_id` if it still holds `item_id` and reports it in chat.
+    fn drop_slot(&self, env: &mut JNIEnv, client: &MinecraftClient, slot_id: i32, item_id: &str) -> Result<()> {
+        env.with_local_frame(16, |env| -> Result<()> {
+            let (Some(player), Some(interaction_manager)) = (client.get_player(env)?, client.get_interaction_manager(env)?) else {
+                return Ok(());
+            };
+            let screen_handler = player.get_current_screen_handler(env)?;
+
+            // The stack may have moved or been used up since the slot was queued.
+            let stack = screen_handler.get_slot(env, slot_id as usize)?.get_stack(env)?;
+            if stack.is_empty(env)? || stack.get_item_id(env)? != item_id {
+                debug!("Slot {} no longer holds {}, not dropping it", slot_id, item_id);
+                return Ok(());
+            }
+            let count = stack.get_count(env)?;
+
+            let sync_id = screen_handler.get_sync_id(env)?;
+            interaction_manager.click_slot(env, sync_id, slot_id, 1, SlotActionType::Throw, &player)?;
+
+            let message = Text::literal(env, &format!("§7[InventoryManager] Dropped §f{}x {}", count, item_id))?;
+            client.get_chat_hud(env)?.add_message(env, &message)?;
+            Ok(())
+        })
+    }
+}
+
+impl Module for InventoryManager {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("junk", SettingValue::Text(self.junk.join(","))),
+            ("allowlist", SettingValue::Text(self.allowlist.join(","))),
+            ("drop_delay_ticks", SettingValue::Number(self.drop_delay_ticks as f64)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("junk", SettingValue::Text(items)) => self.junk = parse_item_list(&items),
+            ("allowlist", SettingValue::Text(items)) => self.allowlist = parse_item_list(&items),
+            ("drop_delay_ticks", SettingValue::Number(ticks)) if (0.0..=200.0).contains(&ticks) && ticks.fract() == 0.0 => {
+                self.drop_delay_ticks = ticks as u64
+            }
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        // Queued slots were picked with the old lists.
+        self.queue.clear();
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.queue.clear();
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        self.tick(env, &event.client(), &event.snapshot)
+    }
+}
+
+// Parses a comma-separated list of item ids. Ids without a namespace get `minecraft:`, so `dirt` and
+// `minecraft:dirt` are the same item.
+fn parse_item_list(items: &str) -> Vec<String> {
+    items
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| if item.contains(':') { item.to_string() } else { format!("minecraft:{}", item) })
+        .collect()
+}
@@ -0,0 +1,220 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::friends::friends;
+use crate::modules::{Category, Choice, Module, ModuleInfo, SettingValue};
+use crate::rotation::{rotations, DEFAULT_TURN_SPEED};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::entity::Entity;
+use crate::sdk::game::hand::Hand;
+use crate::sdk::game::living_entity::LivingEntity;
+use crate::sdk::game::packet::{HandSwingC2SPacket, PlayerInteractEntityC2SPacket};
+use crate::sdk::math::{closest_point_on_aabb, DVec3};
+use crate::sdk::math::rotations::rotation_to;
+use crate::targets::{TargetFilter, TargetInfo, TargetPriority};
+use crate::tps;
+use crate::tracking::tracker;
+use crate::velocity::VelocityIntent;
+
+// Attacks only once the server rotation is this close to the target on both axes, so no hit is sent
+// while still turning towards it.
+const AIM_TOLERANCE: f32 = 10.0;
+
+// Ticks the aim leads a moving target by. The rotation reaches the server with the next movement
+// packet, by which time the target has moved on for about a tick.
+const LEAD_TICKS: f64 = 1.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackMode {
+    // Attacks through the interaction manager like a click would, which also resets the attack cooldown.
+    Interact,
+    // Sends the attack and swing packets directly, leaving the client's own combat state untouched.
+    Packet,
+}
+
+impl Choice for AttackMode {
+    const ALL: &'static [AttackMode] = &[AttackMode::Interact, AttackMode::Packet];
+
+    fn name(self) -> &'static str {
+        match self {
+            AttackMode::Interact => "interact",
+            AttackMode::Packet => "packet",
+        }
+    }
+}
+
+// Turns to the best living entity within reach and attacks it at a fixed rate. The aim point is the
+// spot of the target's hitbox closest to the eyes, the same distance the server checks the reach
+// against, moved ahead by the target's tracked velocity. Friends are never targeted. The attack rate
+// follows the server's tick rate, and nothing is attacked while automation is suspended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KillAura {
+    // Reach to the target's hitbox in blocks.
+    pub range: f64,
+    // Attacks per second, at most one per tick.
+    pub cps: f64,
+    pub priority: TargetPriority,
+    // Opening angle around the player's view in degrees, 360 targets in every direction.
+    pub fov: f64,
+    pub players_only: bool,
+    // Degrees per tick the server rotation turns towards the target.
+    pub turn_speed: f64,
+    pub mode: AttackMode,
+    // Attacks owed, growing by `cps / 20` every tick so rates that do not divide 20 average out.
+    attacks_due: f64,
+}
+
+impl Default for KillAura {
+    fn default() -> Self {
+        Self {
+            range: 3.0,
+            cps: 10.0,
+            priority: TargetPriority::Closest,
+            fov: 360.0,
+            players_only: false,
+            turn_speed: DEFAULT_TURN_SPEED as f64,
+            mode: AttackMode::Interact,
+            attacks_due: 0.0,
+        }
+    }
+}
+
+impl KillAura {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "KillAura", category: Category::Combat };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Module for KillAura {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("range", SettingValue::Number(self.range)),
+            ("cps", SettingValue::Number(self.cps)),
+            ("priority", self.priority.to_setting()),
+            ("fov", SettingValue::Number(self.fov)),
+            ("players_only", SettingValue::Bool(self.players_only)),
+            ("turn_speed", SettingValue::Number(self.turn_speed)),
+            ("mode", self.mode.to_setting()),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("range", SettingValue::Number(range)) if (0.0..=6.0).contains(&range) => self.range = range,
+            ("cps", SettingValue::Number(cps)) if (1.0..=20.0).contains(&cps) => self.cps = cps,
+            ("priority", value) => self.priority = TargetPriority::from_setting(Self::INFO.name, name, &value)?,
+            ("fov", SettingValue::Number(fov)) if (0.0..=360.0).contains(&fov) => self.fov = fov,
+            ("players_only", SettingValue::Bool(players_only)) => self.players_only = players_only,
+            ("turn_speed", SettingValue::Number(speed)) if speed > 0.0 && speed <= 180.0 => self.turn_speed = speed,
+            ("mode", value) => self.mode = AttackMode::from_setting(Self::INFO.name, name, &value)?,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.attacks_due = 0.0;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player_snapshot) = event.snapshot.player.filter(|_| !event.snapshot.screen_open) else {
+            self.attacks_due = 0.0;
+            return Ok(());
+        };
+        if event.snapshot.automation_suspended(None) {
+            self.attacks_due = 0.0;
+            return Ok(());
+        }
+        let tick_scale = tps::tick_scale();
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let client = event.client();
+            let (Some(player), Some(world), Some(interaction_manager)) =
+                (client.get_player(env)?, client.get_world(env)?, client.get_interaction_manager(env)?)
+            else {
+                return Ok(());
+            };
+
+            let player_id = player.get_id(env)?;
+            let eye = player_snapshot.eye_position;
+            let range = self.range;
+            let mut candidates = Vec::new();
+            world.for_each_entity(env, |env, entity| {
+                if !LivingEntity::is_living(env, entity)? || entity.get_id(env)? == player_id {
+                    return Ok(());
+                }
+                let aim = closest_point_on_aabb(entity.get_bounding_box(env)?, eye);
+                if aim.distance(eye) > range {
+                    return Ok(());
+                }
+
+                let living = LivingEntity::new(env.new_local_ref(&entity.inner)?);
+                let health = living.get_health(env)?;
+                if health <= 0.0 || living.is_dead(env)? {
+                    return Ok(());
+                }
+                candidates.push(TargetInfo {
+                    handle: (env.new_global_ref(&entity.inner)?, aim, entity.get_id(env)?),
+                    uuid: entity.get_uuid_string(env)?,
+                    name: entity.get_name(env)?,
+                    position: aim,
+                    health,
+                    is_player: entity.is_player(env)?,
+                    // Visibility is not required, so it is not checked.
+                    is_visible: true,
+                    // A target still flashing red from the last hit takes no damage from the next one.
+                    damage_potential: if living.get_hurt_time(env)? == 0 { 1.0 } else { 0.0 },
+                });
+                Ok(())
+            })?;
+
+            let filter = TargetFilter::new()
+                .range(self.range)
+                .fov(self.fov as f32)
+                .players_only(self.players_only)
+                .exclude_friends(&friends());
+            let Some((target, aim, target_id)) = filter.select(eye, (player_snapshot.yaw, player_snapshot.pitch), self.priority, candidates) else {
+                self.attacks_due = 0.0;
+                return Ok(());
+            };
+
+            let lead = tracker().average_velocity(target_id).unwrap_or(DVec3::ZERO) * LEAD_TICKS;
+            let rotation = rotation_to(eye, aim + lead);
+            rotations().request(Self::INFO.name, 0, rotation, self.turn_speed as f32);
+
+            // Owed attacks do not pile up while turning, which would otherwise burst once aimed.
+            self.attacks_due = (self.attacks_due + self.cps / 20.0 * tick_scale).min(1.0);
+            if self.attacks_due < 1.0 || !rotations().is_facing(rotation, AIM_TOLERANCE) {
+                return Ok(());
+            }
+
+            self.attacks_due -= 1.0;
+            let target = Entity::new(env.new_local_ref(target.as_obj())?);
+            match self.mode {
+                AttackMode::Interact => {
+                    interaction_manager.attack_entity(env, &player, &target)?;
+                    player.swing_hand(env, Hand::MainHand)?;
+                }
+                AttackMode::Packet => {
+                    let Some(network_handler) = client.get_network_handler(env)? else {
+                        return Ok(());
+                    };
+                    let sneaking = player.as_entity().is_sneaking(env)?;
+                    let attack = PlayerInteractEntityC2SPacket::attack(env, &target, sneaking)?;
+                    network_handler.send_packet(env, &attack.inner)?;
+                    let swing = HandSwingC2SPacket::new(env, Hand::MainHand)?;
+                    network_handler.send_packet(env, &swing.inner)?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,238 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use jni::JNIEnv;
+use tracing::{error, info};
+
+use crate::event::{self, PacketEvent, TickEvent};
+use crate::modules::air_jump::AirJump;
+use crate::modules::auto_clicker::AutoClicker;
+use crate::modules::block_esp::BlockEsp;
+use crate::modules::chat_triggers::ChatTriggers;
+use crate::modules::chest_stealer::ChestStealer;
+use crate::modules::elytra_fly::ElytraFly;
+use crate::modules::esp::Esp;
+use crate::modules::flight::Flight;
+use crate::modules::inventory_manager::InventoryManager;
+use crate::modules::kill_aura::KillAura;
+use crate::modules::no_fall::NoFall;
+use crate::modules::nuker::Nuker;
+use crate::modules::scaffold::Scaffold;
+use crate::modules::speed::Speed;
+use crate::modules::speed_mine::SpeedMine;
+use crate::modules::stats_display::StatsDisplay;
+use crate::modules::velocity::Velocity;
+use crate::modules::water_walk::WaterWalk;
+use crate::modules::{Module, ModuleInfo, SettingValue};
+use crate::config;
+use crate::notifications;
+use crate::profiling;
+use crate::restoration;
+use crate::timing;
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+use crate::velocity::VelocityIntent;
+
+static MANAGER: Mutex<ModuleManager> = Mutex::new(ModuleManager::new());
+
+pub fn manager() -> MutexGuard<'static, ModuleManager> {
+    MANAGER.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// A module behind its own lock, so its hooks can run without holding the manager.
+type SharedModule = Arc<Mutex<Box<dyn Module>>>;
+
+struct Entry {
+    info: ModuleInfo,
+    module: SharedModule,
+    enabled: bool,
+}
+
+// Owns the modules implementing `Module`, tracks which are enabled and forwards the bus events to
+// them. Modules are looked up by name, ignoring case.
+//
+// Each module has its own lock. The tick and packet handlers lock the manager only to collect the
+// enabled modules, so a long tick of one module, like a BlockESP search, does not hold up the packet
+// handlers of the others on the network thread.
+pub struct ModuleManager {
+    entries: Vec<Entry>,
+    subscribed: bool,
+}
+
+impl Default for ModuleManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModuleManager {
+    pub const fn new() -> Self {
+        Self { entries: Vec::new(), subscribed: false }
+    }
+
+    // Adds a module, disabled. A module with the same name replaces the registered one.
+    pub fn register(&mut self, module: Box<dyn Module>) {
+        let entry = Entry { info: module.info(), module: Arc::new(Mutex::new(module)), enabled: false };
+        match self.position(entry.info.name) {
+            Some(index) => self.entries[index] = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    // Info of every registered module, in registration order.
+    pub fn infos(&self) -> Vec<ModuleInfo> {
+        self.entries.iter().map(|entry| entry.info).collect()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.position(name).is_some_and(|index| self.entries[index].enabled)
+    }
+
+    // Names of the enabled modules, in registration order.
+    pub fn enabled(&self) -> Vec<&'static str> {
+        self.entries.iter().filter(|entry| entry.enabled).map(|entry| entry.info.name).collect()
+    }
+
+    // Enables or disables the module, running its hooks. Disabling also runs the restorations the
+    // module registered and drops the tasks and event handlers it left behind under its name.
+    // Returns whether the state changed, `false` if it already was in that state.
+    pub fn set_enabled(&mut self, env: &mut JNIEnv, client: &MinecraftClient, name: &str, enabled: bool) -> Result<bool> {
+        let index = self.position(name).ok_or_else(|| anyhow!("Unknown module {}", name))?;
+        if self.entries[index].enabled == enabled {
+            return Ok(false);
+        }
+
+        let name = self.entries[index].info.name;
+        let module = Arc::clone(&self.entries[index].module);
+        let mut module = lock(&module);
+        if enabled {
+            module.on_enable(env, client)?;
+            self.entries[index].enabled = true;
+        } else {
+            // Disabled even if the hook fails, a module that cannot be turned off is worse.
+            self.entries[index].enabled = false;
+            let disabled = module.on_disable(env, client);
+            restoration::restore_owner(env, name);
+            timing::scheduler().cancel_owner(name);
+            event::bus().unsubscribe_owner(name);
+            disabled?;
+        }
+
+        info!("{} {}", name, if enabled { "enabled" } else { "disabled" });
+        notifications::notify_toggle(name, enabled);
+        config::mark_dirty();
+        Ok(true)
+    }
+
+    // Flips the module and returns whether it is enabled now.
+    pub fn toggle(&mut self, env: &mut JNIEnv, client: &MinecraftClient, name: &str) -> Result<bool> {
+        let enabled = !self.is_enabled(name);
+        self.set_enabled(env, client, name, enabled)?;
+        Ok(enabled)
+    }
+
+    // Settings of the module, `None` if there is no such module.
+    pub fn settings(&self, name: &str) -> Option<Vec<(&'static str, SettingValue)>> {
+        self.position(name).map(|index| lock(&self.entries[index].module).settings())
+    }
+
+    pub fn set_setting(&mut self, name: &str, setting: &str, value: SettingValue) -> Result<()> {
+        let index = self.position(name).ok_or_else(|| anyhow!("Unknown module {}", name))?;
+        lock(&self.entries[index].module).set_setting(setting, value)?;
+        config::mark_dirty();
+        Ok(())
+    }
+
+    // The enabled modules with their names, for running their hooks after releasing the manager.
+    fn enabled_modules(&self) -> Vec<(&'static str, SharedModule)> {
+        self.entries.iter().filter(|entry| entry.enabled).map(|entry| (entry.info.name, Arc::clone(&entry.module))).collect()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.info.name.eq_ignore_ascii_case(name))
+    }
+}
+
+fn lock(module: &SharedModule) -> MutexGuard<'_, Box<dyn Module>> {
+    module.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Ticks the enabled modules and applies their velocity changes. A failing module is logged and does
+// not stop the others.
+fn tick(env: &mut JNIEnv, event: &TickEvent) -> Result<()> {
+    let modules = manager().enabled_modules();
+    let mut velocity = VelocityIntent::new();
+    for (name, module) in modules {
+        let start = Instant::now();
+        let result = lock(&module).on_tick(env, event, &mut velocity);
+        profiling::timings().record(name, start.elapsed());
+        if let Err(e) = result {
+            log_failure(env, name, "tick", e);
+        }
+    }
+
+    let Some(player) = event.snapshot.player else {
+        return Ok(());
+    };
+    velocity.apply(env, &event.client(), player.velocity)?;
+    Ok(())
+}
+
+// Runs the packet handlers of the enabled modules. A module disabled meanwhile may still get the
+// packet being handled, as with bus handlers.
+fn on_packet(env: &mut JNIEnv, event: &mut PacketEvent) {
+    let modules = manager().enabled_modules();
+    for (name, module) in modules {
+        if let Err(e) = lock(&module).on_packet(env, event) {
+            log_failure(env, name, "packet handler", e);
+        }
+    }
+}
+
+fn log_failure(env: &mut JNIEnv, module: &str, hook: &str, e: anyhow::Error) {
+    match take_java_exception(env) {
+        Some(exception) => error!("{} failed in its {}: {:?} ({})", module, hook, e, exception),
+        None => error!("{} failed in its {}: {:?}", module, hook, e),
+    }
+}
+
+// Registers the built-in modules and forwards tick and packet events to the manager. Does nothing
+// when called again.
+pub fn install() {
+    let mut manager = manager();
+    if manager.subscribed {
+        return;
+    }
+    manager.register(Box::new(Flight::new()));
+    manager.register(Box::new(KillAura::new()));
+    manager.register(Box::new(Velocity::new()));
+    manager.register(Box::new(Scaffold::new()));
+    manager.register(Box::new(Speed::new()));
+    manager.register(Box::new(NoFall::new()));
+    manager.register(Box::new(AutoClicker::new()));
+    manager.register(Box::new(Esp::new()));
+    manager.register(Box::new(BlockEsp::new()));
+    manager.register(Box::new(AirJump::new()));
+    manager.register(Box::new(ElytraFly::new()));
+    manager.register(Box::new(WaterWalk::new()));
+    manager.register(Box::new(Nuker::new()));
+    manager.register(Box::new(SpeedMine::new()));
+    manager.register(Box::new(ChatTriggers::new()));
+    manager.register(Box::new(InventoryManager::new()));
+    manager.register(Box::new(StatsDisplay::new()));
+    manager.register(Box::new(ChestStealer::new()));
+    manager.subscribed = true;
+    drop(manager);
+
+    let mut bus = event::bus();
+    bus.subscribe::<TickEvent, _>("ModuleManager", |env, event| tick(env, event));
+    bus.subscribe::<PacketEvent, _>("ModuleManager", |env, event| {
+        on_packet(env, event);
+        Ok(())
+    });
+}
@@ -0,0 +1,223 @@
+pub mod air_jump;
+pub mod auto_clicker;
+pub mod block_esp;
+pub mod chat_triggers;
+pub mod chest_stealer;
+pub mod elytra_fly;
+pub mod esp;
+pub mod flight;
+pub mod inventory_manager;
+pub mod kill_aura;
+pub mod manager;
+pub mod no_fall;
+pub mod nuker;
+pub mod scaffold;
+pub mod speed;
+pub mod speed_mine;
+pub mod stats_display;
+pub mod velocity;
+pub mod water_walk;
+
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::{PacketEvent, TickEvent};
+use crate::sdk::game::client::MinecraftClient;
+use crate::velocity::VelocityIntent;
+
+pub use manager::{manager, ModuleManager};
+
+// Groups modules for listings and, later, the panels of the overlay GUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    Movement,
+    Combat,
+    Render,
+    Player,
+    Misc,
+}
+
+impl Category {
+    // Every category in display order.
+    pub const ALL: [Category; 5] = [
+        Category::Movement,
+        Category::Combat,
+        Category::Render,
+        Category::Player,
+        Category::Misc,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Movement => "Movement",
+            Category::Combat => "Combat",
+            Category::Render => "Render",
+            Category::Player => "Player",
+            Category::Misc => "Misc",
+        }
+    }
+}
+
+// Static description of a module, declared by each module as its `INFO` constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModuleInfo {
+    pub name: &'static str,
+    pub category: Category,
+}
+
+// Value of a module setting. Settings keep their kind, a number setting only accepts numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+}
+
+impl SettingValue {
+    // Parses user input as a value of the same kind as `self`, e.g. for `.set flight.speed 1.5`.
+    // Booleans also accept `on`/`off`.
+    pub fn parse_like(&self, input: &str) -> Option<SettingValue> {
+        match self {
+            SettingValue::Bool(_) => match input.to_ascii_lowercase().as_str() {
+                "true" | "on" => Some(SettingValue::Bool(true)),
+                "false" | "off" => Some(SettingValue::Bool(false)),
+                _ => None,
+            },
+            SettingValue::Number(_) => input.parse().ok().filter(|number: &f64| number.is_finite()).map(SettingValue::Number),
+            SettingValue::Text(_) => Some(SettingValue::Text(input.to_string())),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SettingValue::Bool(_) => "boolean",
+            SettingValue::Number(_) => "number",
+            SettingValue::Text(_) => "text",
+        }
+    }
+}
+
+impl std::fmt::Display for SettingValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingValue::Bool(value) => write!(f, "{}", value),
+            SettingValue::Number(value) => write!(f, "{}", value),
+            SettingValue::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+// A setting picking one of a fixed set of named values, like a module's mode. It is a text setting
+// holding the name, so it needs nothing special from the config and commands.
+pub trait Choice: Copy + 'static {
+    // Every value in display order.
+    const ALL: &'static [Self];
+
+    // Name used in settings and the config.
+    fn name(self) -> &'static str;
+
+    // Parses a name returned by `name`, ignoring case.
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|choice| choice.name().eq_ignore_ascii_case(name))
+    }
+
+    fn to_setting(self) -> SettingValue {
+        SettingValue::Text(self.name().to_string())
+    }
+
+    // Reads the choice from a setting value of `module`, with an error listing the valid names.
+    fn from_setting(module: &str, setting: &str, value: &SettingValue) -> Result<Self> {
+        if let Some(choice) = match value {
+            SettingValue::Text(name) => Self::parse(name),
+            _ => None,
+        } {
+            return Ok(choice);
+        }
+        let names: Vec<_> = Self::ALL.iter().map(|choice| choice.name()).collect();
+        bail!("Invalid value {} for {}.{}, expected one of {}", value, module, setting, names.join(", "))
+    }
+}
+
+// A module the `ModuleManager` owns and switches on and off. The manager calls the hooks on the tick
+// thread, except `on_packet`, which runs on the Netty thread. Hooks run under the module's own lock
+// and may read the manager, but toggling modules or changing settings has to go through `dispatch`,
+// which would otherwise wait for the module that is running.
+pub trait Module: Send {
+    fn info(&self) -> ModuleInfo;
+
+    fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    fn category(&self) -> Category {
+        self.info().category
+    }
+
+    // Current settings by name, in display order.
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        Vec::new()
+    }
+
+    // Changes a setting. The value has the kind `settings` reports for it.
+    fn set_setting(&mut self, name: &str, _value: SettingValue) -> Result<()> {
+        bail!("{} has no setting {}", self.name(), name)
+    }
+
+    // Called when the module gets enabled. Failing keeps it disabled.
+    fn on_enable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        Ok(())
+    }
+
+    // Called when the module gets disabled, before the restorations it registered run.
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        Ok(())
+    }
+
+    // Called every tick while enabled. Velocity changes go through `intent`, which is applied once
+    // every module ticked.
+    fn on_tick(&mut self, _env: &mut JNIEnv, _event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        Ok(())
+    }
+
+    // Called for every packet while enabled.
+    fn on_packet(&mut self, _env: &mut JNIEnv, _event: &mut PacketEvent) -> Result<()> {
+        Ok(())
+    }
+}
+
+// Modules grouped by category in `Category::ALL` order, sorted by name within a category. `infos`
+// are normally the registered modules, `ModuleManager::infos`. Categories without modules are left
+// out, so the result is stable for the same set of modules.
+pub fn grouped(infos: &[ModuleInfo]) -> Vec<(Category, Vec<ModuleInfo>)> {
+    Category::ALL
+        .into_iter()
+        .filter_map(|category| {
+            let mut modules: Vec<_> = infos.iter().copied().filter(|info| info.category == category).collect();
+            if modules.is_empty() {
+                return None;
+            }
+            modules.sort_by_key(|info| info.name);
+            Some((category, modules))
+        })
+        .collect()
+}
+
+// Formats the module list grouped by category, one module per line with `[x]` marking the
+// enabled ones, e.g.:
+//
+//   Movement
+//     [x] AirJump
+//     [ ] ElytraFly
+pub fn format_list(infos: &[ModuleInfo], is_enabled: impl Fn(&ModuleInfo) -> bool) -> String {
+    let mut output = String::new();
+    for (category, modules) in grouped(infos) {
+        let _ = writeln!(output, "{}", category.name());
+        for info in modules {
+            let marker = if is_enabled(&info) { 'x' } else { ' ' };
+            let _ = writeln!(output, "  [{}] {}", marker, info.name);
+        }
+    }
+    output
+}
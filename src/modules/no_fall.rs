@@ -0,0 +1,131 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+use tracing::debug;
+
+use crate::event::{PacketDirection, PacketEvent, TickEvent};
+use crate::modules::{Category, Choice, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::packet::{OnGroundOnlyC2SPacket, PlayerMoveC2SPacket};
+use crate::velocity::VelocityIntent;
+
+// Falls shorter than this do no damage.
+const SAFE_FALL_DISTANCE: f32 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoFallMode {
+    // Marks every outgoing movement packet as on the ground once the fall gets close to doing damage.
+    // The server takes the fall so far as landed each time, so the fall is cut into harmless pieces.
+    Spoof,
+    // Sends a single on-ground packet within the last blocks before impact, which looks less
+    // suspicious than spoofing the whole fall.
+    Packet,
+}
+
+impl Choice for NoFallMode {
+    const ALL: &'static [NoFallMode] = &[NoFallMode::Spoof, NoFallMode::Packet];
+
+    fn name(self) -> &'static str {
+        match self {
+            NoFallMode::Spoof => "spoof",
+            NoFallMode::Packet => "packet",
+        }
+    }
+}
+
+// Cancels fall damage by telling the server the player is on the ground while falling. The fall
+// distance is read from the player every tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoFall {
+    pub mode: NoFallMode,
+    // How close to the ground, in blocks, the packet is sent in `NoFallMode::Packet`.
+    pub trigger_distance: f64,
+    // Fall distance of the player at the last tick, read by the packet hook on the Netty thread.
+    fall_distance: f32,
+}
+
+impl Default for NoFall {
+    fn default() -> Self {
+        Self { mode: NoFallMode::Spoof, trigger_distance: 2.5, fall_distance: 0.0 }
+    }
+}
+
+impl NoFall {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "NoFall", category: Category::Player };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Module for NoFall {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![("mode", self.mode.to_setting()), ("trigger_distance", SettingValue::Number(self.trigger_distance))]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("mode", value) => self.mode = NoFallMode::from_setting(Self::INFO.name, name, &value)?,
+            ("trigger_distance", SettingValue::Number(distance)) if distance > 0.0 => self.trigger_distance = distance,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.fall_distance = 0.0;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player_snapshot) = event.snapshot.player else {
+            self.fall_distance = 0.0;
+            return Ok(());
+        };
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let client = event.client();
+            let (Some(player), Some(world)) = (client.get_player(env)?, client.get_world(env)?) else {
+                self.fall_distance = 0.0;
+                return Ok(());
+            };
+
+            self.fall_distance = player.get_fall_distance(env)?;
+            if self.mode != NoFallMode::Packet || player_snapshot.on_ground || self.fall_distance < SAFE_FALL_DISTANCE {
+                return Ok(());
+            }
+
+            let Some(distance) = world.distance_to_ground(env, player_snapshot.position, self.trigger_distance)? else {
+                return Ok(());
+            };
+
+            // The integrated server barely checks movement, so there is nothing worth reporting.
+            if !event.snapshot.singleplayer {
+                debug!("Spoofing ground {:.2} blocks before impact", distance);
+            }
+
+            let network_handler = player.get_network_handler(env)?;
+            let packet = OnGroundOnlyC2SPacket::new(env, true)?;
+            network_handler.send_packet(env, &packet.inner)?;
+            Ok(())
+        })
+    }
+
+    fn on_packet(&mut self, env: &mut JNIEnv, event: &mut PacketEvent) -> Result<()> {
+        // Spoofed a little before the fall does damage, as it keeps growing until the packet is sent.
+        if self.mode != NoFallMode::Spoof || self.fall_distance < SAFE_FALL_DISTANCE - 1.0 {
+            return Ok(());
+        }
+        if event.direction != PacketDirection::Outbound || !event.is(env, PlayerMoveC2SPacket::CLASS)? {
+            return Ok(());
+        }
+
+        let packet = PlayerMoveC2SPacket::new(env.new_local_ref(&event.packet)?);
+        let result = packet.set_on_ground(env, true);
+        env.delete_local_ref(packet.inner)?;
+        Ok(result?)
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::debug;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo};
+use crate::sdk::game::block::BlockPos;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::direction::Direction;
+use crate::snapshot::TickSnapshot;
+use crate::velocity::VelocityIntent;
+
+// Breaks the block under the crosshair through the interaction manager, continuing the breaking
+// progress every tick until the block is air. Opening a screen, suspended automation or the chunk
+// unloading aborts it.
+#[derive(Debug, Default)]
+pub struct Nuker {
+    target: Option<(BlockPos, Direction)>,
+}
+
+impl Nuker {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "Nuker", category: Category::Player };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn target(&self) -> Option<BlockPos> {
+        self.target.map(|(pos, _)| pos)
+    }
+
+    pub fn tick(&mut self, env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot) -> Result<()> {
+        if !snapshot.in_world() {
+            self.target = None;
+            return Ok(());
+        }
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let (Some(world), Some(interaction_manager)) = (client.get_world(env)?, client.get_interaction_manager(env)?) else {
+                self.target = None;
+                return Ok(());
+            };
+
+            if snapshot.screen_open || snapshot.automation_suspended(None) {
+                if self.target.take().is_some() {
+                    interaction_manager.cancel_block_breaking(env)?;
+                }
+                return Ok(());
+            }
+
+            match self.target {
+                Some((pos, side)) => {
+                    if !world.is_chunk_loaded(env, pos)? {
+                        debug!("Aborting breaking {:?}, its chunk unloaded", pos);
+                        self.target = None;
+                        interaction_manager.cancel_block_breaking(env)?;
+                    } else if world.get_block_state(env, pos)?.is_air(env)? {
+                        self.target = None;
+                    } else {
+                        interaction_manager.update_block_breaking_progress(env, pos, side)?;
+                    }
+                }
+                None => {
+                    let Some((pos, side)) = client.get_crosshair_block(env)? else {
+                        return Ok(());
+                    };
+
+                    interaction_manager.attack_block(env, pos, side)?;
+
+                    // Blocks that break instantly are already gone after the attack.
+                    if !world.get_block_state(env, pos)?.is_air(env)? {
+                        self.target = Some((pos, side));
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl Module for Nuker {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    // Stops breaking the targeted block, the game would otherwise keep the half broken block around.
+    fn on_disable(&mut self, env: &mut JNIEnv, client: &MinecraftClient) -> Result<()> {
+        if self.target.take().is_none() {
+            return Ok(());
+        }
+        env.with_local_frame(4, |env| -> Result<()> {
+            if let Some(interaction_manager) = client.get_interaction_manager(env)? {
+                interaction_manager.cancel_block_breaking(env)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        self.tick(env, &event.client(), &event.snapshot)
+    }
+}
@@ -0,0 +1,194 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::rotation::rotations;
+use crate::sdk::game::block::BlockPos;
+use crate::sdk::game::direction::Direction;
+use crate::sdk::game::hand::Hand;
+use crate::sdk::game::inventory::HOTBAR_SIZE;
+use crate::sdk::game::player::ClientPlayerEntity;
+use crate::sdk::game::world::ClientWorld;
+use crate::sdk::math::rotations::rotation_to;
+use crate::sdk::math::DVec3;
+use crate::slot_switch::SlotSwitcher;
+use crate::velocity::VelocityIntent;
+
+// Places only once the server rotation is this close to the placement on both axes.
+const AIM_TOLERANCE: f32 = 15.0;
+
+// Upwards velocity the player gets after a block is placed under them while towering, the same as
+// a jump from the ground.
+const TOWER_VELOCITY: f64 = 0.42;
+
+// Requests rotations above combat modules, a block not placed in time means falling.
+const ROTATION_PRIORITY: i32 = 1;
+
+// Places blocks under the player while they walk. The block goes against a solid neighbor of the
+// block under the feet, or, when there is none, first under a horizontal neighbor to build a path
+// towards it. Blocks are taken from the hotbar, switching to the first slot holding one.
+#[derive(Debug)]
+pub struct Scaffold {
+    // Builds straight up while jumping without moving, placing a block and jumping again from it.
+    pub tower: bool,
+    // Allows sprinting. Servers often flag placing blocks while sprinting, so it is off by default.
+    pub sprint: bool,
+    // Degrees per tick the server rotation turns towards the placement.
+    pub turn_speed: f64,
+    slots: SlotSwitcher,
+}
+
+impl Default for Scaffold {
+    fn default() -> Self {
+        Self { tower: true, sprint: false, turn_speed: 180.0, slots: SlotSwitcher::new() }
+    }
+}
+
+impl Scaffold {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "Scaffold", category: Category::Player };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Selects a hotbar slot holding blocks, keeping the current one if it does. Returns `false` if the
+    // hotbar has no blocks.
+    fn select_blocks(&mut self, env: &mut JNIEnv, player: &ClientPlayerEntity) -> Result<bool> {
+        let inventory = player.get_inventory(env)?;
+        let selected = inventory.get_selected_slot(env)?;
+        let mut found = None;
+        for slot in std::iter::once(selected).chain((0..HOTBAR_SIZE).filter(|&slot| slot != selected)) {
+            let stack = inventory.get_stack(env, slot)?;
+            let is_block = stack.is_block_item(env)?;
+            env.delete_local_ref(stack.inner)?;
+            if is_block {
+                found = Some(slot);
+                break;
+            }
+        }
+        env.delete_local_ref(inventory.inner)?;
+
+        match found {
+            Some(slot) if slot != selected => {
+                self.slots.select_slot(env, player, slot)?;
+                Ok(true)
+            }
+            Some(_) => Ok(true),
+            None => Ok(false),
+        }
+    }
+}
+
+impl Module for Scaffold {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("tower", SettingValue::Bool(self.tower)),
+            ("sprint", SettingValue::Bool(self.sprint)),
+            ("turn_speed", SettingValue::Number(self.turn_speed)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("tower", SettingValue::Bool(tower)) => self.tower = tower,
+            ("sprint", SettingValue::Bool(sprint)) => self.sprint = sprint,
+            ("turn_speed", SettingValue::Number(speed)) if speed > 0.0 && speed <= 180.0 => self.turn_speed = speed,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player_snapshot) = event.snapshot.player.filter(|_| !event.snapshot.screen_open) else {
+            return Ok(());
+        };
+        if event.snapshot.automation_suspended(None) {
+            return Ok(());
+        }
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let client = event.client();
+            let (Some(player), Some(world), Some(interaction_manager)) =
+                (client.get_player(env)?, client.get_world(env)?, client.get_interaction_manager(env)?)
+            else {
+                return Ok(());
+            };
+
+            if !self.sprint && player.is_sprinting(env)? {
+                player.set_sprinting(env, false)?;
+            }
+
+            let below = BlockPos::of_floored(player_snapshot.position).down();
+            let Some((support, side)) = find_placement(env, &world, below)? else {
+                return Ok(());
+            };
+            if !self.select_blocks(env, &player)? {
+                return Ok(());
+            }
+
+            // The middle of the face placed against.
+            let (x, y, z) = side.vector();
+            let hit = support.to_center() + DVec3::new(x as f64, y as f64, z as f64) * 0.5;
+            let rotation = rotation_to(player_snapshot.eye_position, hit);
+            rotations().request(Self::INFO.name, ROTATION_PRIORITY, rotation, self.turn_speed as f32);
+            if !rotations().is_facing(rotation, AIM_TOLERANCE) {
+                return Ok(());
+            }
+
+            if !interaction_manager.interact_block(env, &player, Hand::MainHand, support, side, hit)? {
+                return Ok(());
+            }
+            player.swing_hand(env, Hand::MainHand)?;
+
+            let input = player.get_input(env)?;
+            let standing_still = input.get_movement_forward(env)? == 0.0 && input.get_movement_sideways(env)? == 0.0;
+            if self.tower && standing_still && input.is_jumping(env)? && support.neighbor(side) == below {
+                intent.set(Self::INFO.name, 0, DVec3::new(0.0, TOWER_VELOCITY, 0.0));
+            }
+            Ok(())
+        })
+    }
+}
+
+// Where to place a block to fill `below`: a solid block and the face of it to click. `None` if
+// `below` is already filled or nothing nearby can be placed against.
+fn find_placement(env: &mut JNIEnv, world: &ClientWorld, below: BlockPos) -> Result<Option<(BlockPos, Direction)>> {
+    if !is_replaceable(env, world, below)? {
+        return Ok(None);
+    }
+
+    let horizontal = [Direction::North, Direction::South, Direction::West, Direction::East];
+    let targets = std::iter::once(below).chain(horizontal.into_iter().map(|direction| below.neighbor(direction)));
+    for target in targets {
+        if target != below && !is_replaceable(env, world, target)? {
+            continue;
+        }
+        // The block above the target is where the player stands, never something to place against.
+        for direction in Direction::ALL.into_iter().filter(|&direction| direction != Direction::Up) {
+            let support = target.neighbor(direction);
+            if is_solid(env, world, support)? {
+                return Ok(Some((support, direction.opposite())));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn is_replaceable(env: &mut JNIEnv, world: &ClientWorld, pos: BlockPos) -> Result<bool> {
+    let state = world.get_block_state(env, pos)?;
+    let replaceable = state.is_replaceable(env);
+    env.delete_local_ref(state.inner)?;
+    Ok(replaceable?)
+}
+
+fn is_solid(env: &mut JNIEnv, world: &ClientWorld, pos: BlockPos) -> Result<bool> {
+    let state = world.get_block_state(env, pos)?;
+    let solid = state.is_solid(env);
+    env.delete_local_ref(state.inner)?;
+    Ok(solid?)
+}
@@ -0,0 +1,144 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::lagback::LagbackPause;
+use crate::modules::{Category, Choice, Module, ModuleInfo, SettingValue};
+use crate::movement_log;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::math::{movement, DVec3};
+use crate::velocity::VelocityIntent;
+
+// Upwards velocity of a jump from the ground without jump boost.
+const JUMP_VELOCITY: f64 = 0.42;
+
+// Height above the feet the way ahead is checked at. Anything lower is stepped up on.
+const WALL_CHECK_HEIGHT: f64 = 0.6;
+
+// Half the width of the player's hitbox, added to the distance checked ahead.
+const HALF_WIDTH: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeedMode {
+    // Moves at the set speed while on the ground, jumping and falling stay untouched.
+    Vanilla,
+    // Jumps whenever the player lands and keeps the set speed in the air, steering it towards the
+    // movement keys.
+    Hop,
+}
+
+impl Choice for SpeedMode {
+    const ALL: &'static [SpeedMode] = &[SpeedMode::Vanilla, SpeedMode::Hop];
+
+    fn name(self) -> &'static str {
+        match self {
+            SpeedMode::Vanilla => "vanilla",
+            SpeedMode::Hop => "hop",
+        }
+    }
+}
+
+// Moves faster than walking by replacing the horizontal velocity while a movement key is held.
+// Without input the player slows down as usual, and so does running into a wall, where pushing on
+// would only get the player flagged. After the server sets the player back it pauses for a while.
+#[derive(Debug)]
+pub struct Speed {
+    pub mode: SpeedMode,
+    // Horizontal speed in blocks per tick.
+    pub speed: f64,
+    lagback: LagbackPause,
+}
+
+impl Default for Speed {
+    fn default() -> Self {
+        Self { mode: SpeedMode::Vanilla, speed: 0.35, lagback: LagbackPause::new(20) }
+    }
+}
+
+impl Speed {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "Speed", category: Category::Movement };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Module for Speed {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("mode", self.mode.to_setting()),
+            ("speed", SettingValue::Number(self.speed)),
+            ("lagback_pause_ticks", SettingValue::Number(self.lagback.ticks as f64)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("mode", value) => self.mode = SpeedMode::from_setting(Self::INFO.name, name, &value)?,
+            ("speed", SettingValue::Number(speed)) if speed >= 0.0 => self.speed = speed,
+            ("lagback_pause_ticks", SettingValue::Number(ticks)) if (0.0..=200.0).contains(&ticks) && ticks.fract() == 0.0 => {
+                self.lagback.ticks = ticks as u32
+            }
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_enable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.lagback.subscribe(Self::INFO.name);
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.lagback.reset();
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, intent: &mut VelocityIntent) -> Result<()> {
+        if self.lagback.is_paused() {
+            return Ok(());
+        }
+        let Some(player_snapshot) = event.snapshot.player else {
+            return Ok(());
+        };
+        if self.mode == SpeedMode::Vanilla && !player_snapshot.on_ground {
+            return Ok(());
+        }
+
+        env.with_local_frame(8, |env| -> Result<()> {
+            let Some(player) = event.client().get_player(env)? else {
+                return Ok(());
+            };
+
+            let input = player.get_input(env)?;
+            let speed = movement_log::speed_ceiling_per_tick().map_or(self.speed, |ceiling| ceiling.min(self.speed));
+            let motion = movement::input_to_motion(
+                player_snapshot.yaw,
+                input.get_movement_forward(env)?,
+                input.get_movement_sideways(env)?,
+                speed,
+            );
+            if motion == DVec3::ZERO || player.as_entity_ref().is_horizontal_collision(env)? {
+                return Ok(());
+            }
+            let Some(world) = event.client().get_world(env)? else {
+                return Ok(());
+            };
+            let from = player_snapshot.position + DVec3::new(0.0, WALL_CHECK_HEIGHT, 0.0);
+            if !world.is_path_clear(env, from, motion, HALF_WIDTH + speed)? {
+                return Ok(());
+            }
+
+            let vertical = match self.mode {
+                SpeedMode::Hop if player_snapshot.on_ground => JUMP_VELOCITY,
+                _ => player_snapshot.velocity.y,
+            };
+            intent.set(Self::INFO.name, 0, DVec3::new(motion.x, vertical, motion.z));
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::hand::Hand;
+use crate::snapshot::TickSnapshot;
+use crate::velocity::VelocityIntent;
+
+// Removes the 5 tick delay the game forces between breaking one block and starting on the next by
+// clearing the interaction manager's breaking cooldown every tick. The game writes the cooldown
+// itself after every broken block, so nothing has to be restored when the module is turned off.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeedMine {
+    // Only clear the cooldown while a pickaxe is held in the main hand.
+    pub only_with_pickaxe: bool,
+}
+
+impl Default for SpeedMine {
+    fn default() -> Self {
+        Self { only_with_pickaxe: true }
+    }
+}
+
+impl SpeedMine {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "SpeedMine", category: Category::Player };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tick(&self, env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot) -> Result<()> {
+        if !snapshot.in_world() || snapshot.screen_open {
+            return Ok(());
+        }
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let (Some(player), Some(interaction_manager)) = (client.get_player(env)?, client.get_interaction_manager(env)?) else {
+                return Ok(());
+            };
+
+            if self.only_with_pickaxe {
+                let stack = player.as_living_entity_ref().get_stack_in_hand(env, Hand::MainHand)?;
+                if !stack.get_known_item(env)?.is_some_and(|item| item.is_pickaxe()) {
+                    return Ok(());
+                }
+            }
+
+            if interaction_manager.get_block_breaking_cooldown(env)? > 0 {
+                interaction_manager.set_block_breaking_cooldown(env, 0)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Module for SpeedMine {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![("only_with_pickaxe", SettingValue::Bool(self.only_with_pickaxe))]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("only_with_pickaxe", SettingValue::Bool(only_with_pickaxe)) => self.only_with_pickaxe = only_with_pickaxe,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        self.tick(env, &event.client(), &event.snapshot)
+    }
+}
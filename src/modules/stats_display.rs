@@ -0,0 +1,143 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::manager;
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::text::Text;
+use crate::sdk::math::DVec3;
+use crate::tps::tps;
+use crate::velocity::VelocityIntent;
+
+// Game ticks per second, to turn the movement of one tick into blocks per second.
+const TICKS_PER_SECOND: f64 = 20.0;
+
+// Shows a line of stats above the hotbar: coordinates, horizontal speed, FPS, the estimated server
+// TPS and the number of enabled modules. Each part can be turned off on its own.
+#[derive(Debug)]
+pub struct StatsDisplay {
+    // Ticks between updates of the line. The action bar keeps a message for a few seconds, so the line
+    // stays up between them.
+    pub interval: u32,
+    pub coordinates: bool,
+    pub speed: bool,
+    pub fps: bool,
+    pub tps: bool,
+    pub modules: bool,
+    last_position: Option<DVec3>,
+    ticks_until_update: u32,
+}
+
+impl Default for StatsDisplay {
+    fn default() -> Self {
+        Self {
+            interval: 5,
+            coordinates: true,
+            speed: true,
+            fps: true,
+            tps: true,
+            modules: true,
+            last_position: None,
+            ticks_until_update: 0,
+        }
+    }
+}
+
+impl StatsDisplay {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "StatsDisplay", category: Category::Render };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn format_line(&self, position: DVec3, blocks_per_second: f64, fps: i32) -> String {
+        let mut parts = Vec::new();
+        if self.coordinates {
+            parts.push(format!("§7XYZ §f{:.0} {:.0} {:.0}", position.x.floor(), position.y.floor(), position.z.floor()));
+        }
+        if self.speed {
+            parts.push(format!("§f{:.2} §7b/s", blocks_per_second));
+        }
+        if self.fps {
+            parts.push(format!("§f{} §7FPS", fps));
+        }
+        if self.tps {
+            parts.push(format!("§f{:.1} §7TPS", tps().estimated_tps()));
+        }
+        if self.modules {
+            parts.push(format!("§f{} §7modules", manager().enabled().len()));
+        }
+        parts.join(" §8| ")
+    }
+}
+
+impl Module for StatsDisplay {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![
+            ("interval", SettingValue::Number(self.interval as f64)),
+            ("coordinates", SettingValue::Bool(self.coordinates)),
+            ("speed", SettingValue::Bool(self.speed)),
+            ("fps", SettingValue::Bool(self.fps)),
+            ("tps", SettingValue::Bool(self.tps)),
+            ("modules", SettingValue::Bool(self.modules)),
+        ]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("interval", SettingValue::Number(interval)) if (1.0..=100.0).contains(&interval) && interval.fract() == 0.0 => {
+                self.interval = interval as u32
+            }
+            ("coordinates", SettingValue::Bool(coordinates)) => self.coordinates = coordinates,
+            ("speed", SettingValue::Bool(speed)) => self.speed = speed,
+            ("fps", SettingValue::Bool(fps)) => self.fps = fps,
+            ("tps", SettingValue::Bool(tps)) => self.tps = tps,
+            ("modules", SettingValue::Bool(modules)) => self.modules = modules,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.last_position = None;
+        self.ticks_until_update = 0;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player_snapshot) = event.snapshot.player else {
+            self.last_position = None;
+            return Ok(());
+        };
+
+        let position = player_snapshot.position;
+        let blocks_per_second = self.last_position.map_or(0.0, |last| (position - last).horizontal_length() * TICKS_PER_SECOND);
+        self.last_position = Some(position);
+
+        if self.ticks_until_update > 0 {
+            self.ticks_until_update -= 1;
+            return Ok(());
+        }
+        self.ticks_until_update = self.interval - 1;
+
+        env.with_local_frame(8, |env| -> Result<()> {
+            let client = event.client();
+            let Some(player) = client.get_player(env)? else {
+                return Ok(());
+            };
+            let fps = client.get_current_fps(env)?;
+            let line = self.format_line(position, blocks_per_second, fps);
+            if line.is_empty() {
+                return Ok(());
+            }
+            let text = Text::literal(env, &line)?;
+            player.send_message(env, &text, true)?;
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::event::{PacketDirection, PacketEvent, TickEvent};
+use crate::modules::{Category, Module, ModuleInfo, SettingValue};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::packet::{EntityVelocityUpdateS2CPacket, ExplosionS2CPacket};
+use crate::velocity::VelocityIntent;
+
+// Reduces knockback by scaling the velocity the server sends the player, from hits through
+// `EntityVelocityUpdateS2CPacket` and from explosions through `ExplosionS2CPacket`. At 0% on both axes
+// velocity updates are dropped entirely. Explosion packets are always let through, as they also
+// destroy the blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Velocity {
+    // Percentage of the horizontal knockback kept.
+    pub horizontal: f64,
+    // Percentage of the vertical knockback kept.
+    pub vertical: f64,
+    // Network ID of the local player, refreshed every tick. Packets run on the Netty thread, which
+    // cannot look it up without racing the world swap.
+    player_id: Option<i32>,
+}
+
+impl Default for Velocity {
+    fn default() -> Self {
+        Self { horizontal: 0.0, vertical: 0.0, player_id: None }
+    }
+}
+
+impl Velocity {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "Velocity", category: Category::Combat };
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn scale_update(&self, env: &mut JNIEnv, packet: &EntityVelocityUpdateS2CPacket, event: &mut PacketEvent) -> Result<()> {
+        if Some(packet.get_entity_id(env)?) != self.player_id {
+            return Ok(());
+        }
+        if self.horizontal == 0.0 && self.vertical == 0.0 {
+            event.cancel();
+            return Ok(());
+        }
+
+        let horizontal = self.horizontal / 100.0;
+        let vertical = self.vertical / 100.0;
+        let velocity_x = packet.get_velocity_x(env)?;
+        packet.set_velocity_x(env, (velocity_x as f64 * horizontal) as i32)?;
+        let velocity_y = packet.get_velocity_y(env)?;
+        packet.set_velocity_y(env, (velocity_y as f64 * vertical) as i32)?;
+        let velocity_z = packet.get_velocity_z(env)?;
+        packet.set_velocity_z(env, (velocity_z as f64 * horizontal) as i32)?;
+        Ok(())
+    }
+
+    fn scale_explosion(&self, env: &mut JNIEnv, packet: &ExplosionS2CPacket) -> Result<()> {
+        let horizontal = (self.horizontal / 100.0) as f32;
+        let vertical = (self.vertical / 100.0) as f32;
+        let velocity_x = packet.get_player_velocity_x(env)?;
+        packet.set_player_velocity_x(env, velocity_x * horizontal)?;
+        let velocity_y = packet.get_player_velocity_y(env)?;
+        packet.set_player_velocity_y(env, velocity_y * vertical)?;
+        let velocity_z = packet.get_player_velocity_z(env)?;
+        packet.set_player_velocity_z(env, velocity_z * horizontal)?;
+        Ok(())
+    }
+}
+
+impl Module for Velocity {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn settings(&self) -> Vec<(&'static str, SettingValue)> {
+        vec![("horizontal", SettingValue::Number(self.horizontal)), ("vertical", SettingValue::Number(self.vertical))]
+    }
+
+    fn set_setting(&mut self, name: &str, value: SettingValue) -> Result<()> {
+        match (name, value) {
+            ("horizontal", SettingValue::Number(percent)) if (0.0..=100.0).contains(&percent) => self.horizontal = percent,
+            ("vertical", SettingValue::Number(percent)) if (0.0..=100.0).contains(&percent) => self.vertical = percent,
+            (name, value) => bail!("Invalid value {} for {}.{}", value, Self::INFO.name, name),
+        }
+        Ok(())
+    }
+
+    fn on_disable(&mut self, _env: &mut JNIEnv, _client: &MinecraftClient) -> Result<()> {
+        self.player_id = None;
+        Ok(())
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, _intent: &mut VelocityIntent) -> Result<()> {
+        self.player_id = env.with_local_frame(4, |env| -> Result<Option<i32>> {
+            match event.client().get_player(env)? {
+                Some(player) => Ok(Some(player.get_id(env)?)),
+                None => Ok(None),
+            }
+        })?;
+        Ok(())
+    }
+
+    fn on_packet(&mut self, env: &mut JNIEnv, event: &mut PacketEvent) -> Result<()> {
+        if event.direction != PacketDirection::Inbound || self.player_id.is_none() {
+            return Ok(());
+        }
+
+        if event.is(env, EntityVelocityUpdateS2CPacket::CLASS)? {
+            let packet = EntityVelocityUpdateS2CPacket::new(env.new_local_ref(&event.packet)?);
+            let result = self.scale_update(env, &packet, event);
+            env.delete_local_ref(packet.inner)?;
+            result
+        } else if event.is(env, ExplosionS2CPacket::CLASS)? {
+            let packet = ExplosionS2CPacket::new(env.new_local_ref(&event.packet)?);
+            let result = self.scale_explosion(env, &packet);
+            env.delete_local_ref(packet.inner)?;
+            result
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use jni::JNIEnv;
+
+use crate::event::TickEvent;
+use crate::modules::{Category, Module, ModuleInfo};
+use crate::sdk::game::block::BlockPos;
+use crate::sdk::game::client::MinecraftClient;
+use crate::snapshot::TickSnapshot;
+use crate::velocity::VelocityIntent;
+
+// Upward velocity used to lift the player back to the surface. Kept small so falling into water
+// settles at the surface instead of bouncing off it.
+const SURFACE_LIFT_SPEED: f64 = 0.1;
+
+// Lets the player walk on water by cancelling downward movement at the surface. Sneaking sinks as
+// usual, and riding a boat or any other vehicle disables it.
+#[derive(Debug, Default)]
+pub struct WaterWalk;
+
+impl WaterWalk {
+    pub const INFO: ModuleInfo = ModuleInfo { name: "WaterWalk", category: Category::Movement };
+
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn tick(&self, env: &mut JNIEnv, client: &MinecraftClient, snapshot: &TickSnapshot, intent: &mut VelocityIntent) -> Result<()> {
+        let Some(player_snapshot) = snapshot.player else {
+            return Ok(());
+        };
+
+        env.with_local_frame(16, |env| -> Result<()> {
+            let (Some(player), Some(world)) = (client.get_player(env)?, client.get_world(env)?) else {
+                return Ok(());
+            };
+
+            let input = player.get_input(env)?;
+            if input.is_sneaking(env)? || player.has_vehicle(env)? {
+                return Ok(());
+            }
+
+            let feet = BlockPos::of_floored(player_snapshot.position);
+            let feet_state = world.get_block_state(env, feet)?;
+            if !feet_state.is_water(env)? {
+                return Ok(());
+            }
+
+            // Only hold the player at the top layer of water, deeper down they swim normally.
+            if world.get_block_state(env, feet.up())?.is_liquid(env)? {
+                return Ok(());
+            }
+
+            let surface = feet.y as f64 + feet_state.get_fluid_height(env)? as f64;
+            let below_surface = surface - player_snapshot.position.y;
+
+            let mut velocity = player_snapshot.velocity;
+            if below_surface > 0.0 {
+                velocity.y = (below_surface * 0.5).min(SURFACE_LIFT_SPEED);
+            } else if velocity.y < 0.0 {
+                velocity.y = 0.0;
+            } else {
+                return Ok(());
+            }
+
+            intent.set(Self::INFO.name, 0, velocity);
+            Ok(())
+        })
+    }
+}
+
+impl Module for WaterWalk {
+    fn info(&self) -> ModuleInfo {
+        Self::INFO
+    }
+
+    fn on_tick(&mut self, env: &mut JNIEnv, event: &TickEvent, intent: &mut VelocityIntent) -> Result<()> {
+        self.tick(env, &event.client(), &event.snapshot, intent)
+    }
+}
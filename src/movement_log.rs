@@ -0,0 +1,205 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use tracing::warn;
+
+use crate::sdk::math::DVec3;
+use crate::snapshot::TickSnapshot;
+use crate::tps::NORMAL_TPS;
+
+// Movement of more than this many blocks in one tick is a teleport (respawn, /tp, lagback) and not
+// recorded as speed.
+const TELEPORT_DISTANCE: f64 = 10.0;
+
+// Highest speed in blocks per second our own modules may move the player at, `None` for no limit.
+static SPEED_CEILING: Mutex<Option<f64>> = Mutex::new(None);
+
+pub fn speed_ceiling() -> Option<f64> {
+    *SPEED_CEILING.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn set_speed_ceiling(ceiling: Option<f64>) {
+    *SPEED_CEILING.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = ceiling;
+}
+
+// The ceiling as blocks per tick, which is the unit velocities are in.
+pub fn speed_ceiling_per_tick() -> Option<f64> {
+    speed_ceiling().map(|ceiling| ceiling / NORMAL_TPS)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MovementSample {
+    // Blocks per tick.
+    pub horizontal: f64,
+    pub vertical: f64,
+}
+
+// Records how far the player actually moved each tick. The speed comes from successive positions
+// instead of the velocity field, which keeps its value while the player is pushed against a wall.
+pub struct MovementLog {
+    samples: VecDeque<MovementSample>,
+    capacity: usize,
+    last_position: Option<DVec3>,
+}
+
+impl Default for MovementLog {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl MovementLog {
+    // `capacity` is how many ticks are kept.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            last_position: None,
+        }
+    }
+
+    // Records the movement since the previous snapshot. `driven_by_module` tells whether one of our
+    // modules moved the player this tick, which logs a warning if it exceeded the speed ceiling.
+    pub fn record(&mut self, snapshot: &TickSnapshot, driven_by_module: bool) {
+        let Some(player) = snapshot.player else {
+            self.last_position = None;
+            return;
+        };
+
+        let Some(last_position) = self.last_position.replace(player.position) else {
+            return;
+        };
+
+        let delta = player.position - last_position;
+        if delta.length() > TELEPORT_DISTANCE {
+            return;
+        }
+
+        let sample = MovementSample {
+            horizontal: delta.horizontal_length(),
+            vertical: delta.y,
+        };
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+
+        if driven_by_module {
+            if let Some(ceiling) = speed_ceiling() {
+                let bps = sample.horizontal * NORMAL_TPS;
+                if bps > ceiling {
+                    warn!("Modules moved the player at {:.2} blocks/s, above the ceiling of {:.2}", bps, ceiling);
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.last_position = None;
+    }
+
+    pub fn last_sample(&self) -> Option<MovementSample> {
+        self.samples.back().copied()
+    }
+
+    // Horizontal speed of the last tick in blocks per second.
+    pub fn current_bps(&self) -> f64 {
+        self.last_sample().map_or(0.0, |sample| sample.horizontal * NORMAL_TPS)
+    }
+
+    // Average horizontal speed over the last `ticks` ticks in blocks per second.
+    pub fn average_bps(&self, ticks: usize) -> f64 {
+        let count = ticks.min(self.samples.len());
+        if count == 0 {
+            return 0.0;
+        }
+
+        let total: f64 = self.samples.iter().rev().take(count).map(|sample| sample.horizontal).sum();
+        total / count as f64 * NORMAL_TPS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::PlayerSnapshot;
+
+    fn at(x: f64, y: f64, z: f64) -> TickSnapshot {
+        let position = DVec3::new(x, y, z);
+        TickSnapshot {
+            player: Some(PlayerSnapshot {
+                position,
+                eye_position: position,
+                velocity: DVec3::ZERO,
+                on_ground: true,
+                yaw: 0.0,
+                pitch: 0.0,
+                health: 20.0,
+                food_level: 20,
+                movement_speed: 0.1,
+            }),
+            ..TickSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn first_position_records_nothing() {
+        let mut log = MovementLog::new(4);
+        log.record(&at(0.0, 0.0, 0.0), false);
+        assert_eq!(log.last_sample(), None);
+        assert_eq!(log.current_bps(), 0.0);
+    }
+
+    #[test]
+    fn records_the_distance_between_ticks() {
+        let mut log = MovementLog::new(4);
+        log.record(&at(0.0, 64.0, 0.0), false);
+        log.record(&at(0.3, 64.5, 0.4), false);
+        let sample = log.last_sample().unwrap();
+        assert!((sample.horizontal - 0.5).abs() < 1e-9);
+        assert!((sample.vertical - 0.5).abs() < 1e-9);
+        assert!((log.current_bps() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn oldest_samples_are_dropped_at_capacity() {
+        let mut log = MovementLog::new(3);
+        let mut x = 0.0;
+        log.record(&at(x, 0.0, 0.0), false);
+        // Steps of 1, 2, 3 and 4 blocks, the first falls out.
+        for step in 1..=4 {
+            x += step as f64;
+            log.record(&at(x, 0.0, 0.0), false);
+        }
+        assert!((log.average_bps(10) - 3.0 * NORMAL_TPS).abs() < 1e-9);
+        assert!((log.average_bps(2) - 3.5 * NORMAL_TPS).abs() < 1e-9);
+        assert!((log.current_bps() - 4.0 * NORMAL_TPS).abs() < 1e-9);
+    }
+
+    #[test]
+    fn teleports_are_not_recorded() {
+        let mut log = MovementLog::new(4);
+        log.record(&at(0.0, 0.0, 0.0), false);
+        log.record(&at(100.0, 0.0, 0.0), false);
+        assert_eq!(log.last_sample(), None);
+        // Movement after the teleport is measured from the new position.
+        log.record(&at(101.0, 0.0, 0.0), false);
+        assert!((log.last_sample().unwrap().horizontal - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn leaving_the_world_restarts_from_the_next_position() {
+        let mut log = MovementLog::new(4);
+        log.record(&at(0.0, 0.0, 0.0), false);
+        log.record(&TickSnapshot::default(), false);
+        log.record(&at(5.0, 0.0, 0.0), false);
+        assert_eq!(log.last_sample(), None);
+    }
+
+    #[test]
+    fn average_of_an_empty_log_is_zero() {
+        assert_eq!(MovementLog::new(4).average_bps(10), 0.0);
+        assert_eq!(MovementLog::new(4).average_bps(0), 0.0);
+    }
+}
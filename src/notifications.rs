@@ -0,0 +1,84 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use anyhow::Result;
+use jni::JNIEnv;
+
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::text::Text;
+
+// Module toggles waiting to be shown, in the order the modules were first toggled. Toggles can
+// happen on any thread, but only the tick thread may talk to the game.
+static PENDING: Mutex<Vec<ToggleNotification>> = Mutex::new(Vec::new());
+
+// Put in front of messages the client prints into the chat, so they are told apart from server messages.
+const CHAT_PREFIX: &str = "§7[§bLiquidBounce§7]§r ";
+
+static SILENCED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ToggleNotification {
+    module: String,
+    enabled: bool,
+}
+
+pub fn is_silenced() -> bool {
+    SILENCED.load(Ordering::Relaxed)
+}
+
+pub fn set_silenced(silenced: bool) {
+    SILENCED.store(silenced, Ordering::Relaxed);
+}
+
+// Queues a confirmation for a module toggle. Toggling the same module again before the next dispatch
+// replaces its entry, so rapid toggling shows only the final state.
+pub fn notify_toggle(module: &str, enabled: bool) {
+    if is_silenced() {
+        return;
+    }
+
+    let mut pending = PENDING.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    match pending.iter_mut().find(|notification| notification.module == module) {
+        Some(notification) => notification.enabled = enabled,
+        None => pending.push(ToggleNotification { module: module.to_string(), enabled }),
+    }
+}
+
+// Shows the queued toggles above the hotbar, combined into one line. Must be called from the tick
+// thread. Notifications queued while no player exists are dropped.
+pub fn dispatch(env: &mut JNIEnv, client: &MinecraftClient) -> Result<()> {
+    let notifications = std::mem::take(&mut *PENDING.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    if notifications.is_empty() || is_silenced() {
+        return Ok(());
+    }
+
+    let message = notifications
+        .iter()
+        .map(|notification| {
+            let (color, state) = if notification.enabled { ("§a", "enabled") } else { ("§c", "disabled") };
+            format!("§f{} {}{}", notification.module, color, state)
+        })
+        .collect::<Vec<_>>()
+        .join("§7, ");
+
+    env.with_local_frame(8, |env| -> Result<()> {
+        let Some(player) = client.get_player(env)? else {
+            return Ok(());
+        };
+
+        let text = Text::literal(env, &message)?;
+        player.send_message(env, &text, true)?;
+        Ok(())
+    })
+}
+
+// Prints a status or debug message into the chat on this client only. Must be called from the tick
+// thread. Legacy `§` color codes in the message are rendered. Unlike toggle notifications it needs no
+// player, the chat keeps its messages while in menus.
+pub fn chat(env: &mut JNIEnv, client: &MinecraftClient, message: &str) -> Result<()> {
+    env.with_local_frame(8, |env| -> Result<()> {
+        let text = Text::literal(env, &format!("{}{}", CHAT_PREFIX, message))?;
+        client.get_chat_hud(env)?.add_message(env, &text)?;
+        Ok(())
+    })
+}
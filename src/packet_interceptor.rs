@@ -0,0 +1,225 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use jni::objects::{GlobalRef, JClass, JObject, JValue};
+use jni::sys::{jboolean, JNI_FALSE, JNI_TRUE};
+use jni::{JNIEnv, NativeMethod};
+use tracing::{debug, error, info};
+
+use crate::event::{self, PacketDirection, PacketEvent};
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::jni::class_file::{op, ClassWriter, ACC_PRIVATE, ACC_PUBLIC, ACC_STATIC};
+use crate::sdk::jni::string::ToJava;
+use crate::sdk::Result;
+
+// Sees every packet of the connection by sitting in its Netty pipeline. The handler is a small class
+// defined at runtime whose `channelRead` and `write` ask native methods whether to drop the packet
+// before passing it on, so packets are posted as `PacketEvent`s on the Netty thread.
+//
+// It is placed right before Minecraft's `packet_handler`, where inbound packets are already decoded
+// and outbound packets not yet encoded.
+
+const CLASS_NAME: &str = "net/ccbluex/liquidbounce/lite/PacketInterceptor";
+const SUPER_CLASS: &str = "io/netty/channel/ChannelDuplexHandler";
+// Name of the handler in the pipeline.
+const HANDLER_NAME: &str = "liquidbounce_packet_interceptor";
+// Name Minecraft gives its `ClientConnection` in the pipeline.
+const PACKET_HANDLER_NAME: &str = "packet_handler";
+
+const CALLBACK_SIGNATURE: &str = "(Ljava/lang/Object;)Z";
+
+static CLASS: Mutex<Option<GlobalRef>> = Mutex::new(None);
+
+static INBOUND: AtomicU64 = AtomicU64::new(0);
+static OUTBOUND: AtomicU64 = AtomicU64::new(0);
+static CANCELLED: AtomicU64 = AtomicU64::new(0);
+
+// Packets seen inbound and outbound and how many of them were cancelled since injection.
+pub fn counts() -> (u64, u64, u64) {
+    (INBOUND.load(Ordering::Relaxed), OUTBOUND.load(Ordering::Relaxed), CANCELLED.load(Ordering::Relaxed))
+}
+
+// Adds the interceptor to the current connection unless it is already there. Returns whether it was
+// added; `false` also when not connected. Every connection has its own pipeline, so this is called
+// again after joining a world or server.
+pub fn ensure_injected(env: &mut JNIEnv, client: &MinecraftClient) -> Result<bool> {
+    env.with_local_frame(16, |env| -> Result<bool> {
+        let Some(network_handler) = client.get_network_handler(env)? else {
+            return Ok(false);
+        };
+        let Some(channel) = network_handler.get_connection(env)?.get_channel(env)? else {
+            return Ok(false);
+        };
+
+        let pipeline = env.call_method(&channel, "pipeline", "()Lio/netty/channel/ChannelPipeline;", &[])?.l()?;
+        let name = HANDLER_NAME.to_java(env)?;
+        let existing = env
+            .call_method(&pipeline, "get", "(Ljava/lang/String;)Lio/netty/channel/ChannelHandler;", &[JValue::Object(&name)])?
+            .l()?;
+        if !existing.is_null() {
+            return Ok(false);
+        }
+
+        let class = interceptor_class(env)?;
+        let handler = env.new_object(&class, "()V", &[])?;
+        let base_name = PACKET_HANDLER_NAME.to_java(env)?;
+        env.call_method(
+            &pipeline,
+            "addBefore",
+            "(Ljava/lang/String;Ljava/lang/String;Lio/netty/channel/ChannelHandler;)Lio/netty/channel/ChannelPipeline;",
+            &[JValue::Object(&base_name), JValue::Object(&name), JValue::Object(&handler)],
+        )?;
+        info!("Packet interceptor added to the connection");
+        Ok(true)
+    })
+}
+
+// Defines the interceptor class on first use. It has to live in the game's class loader to see Netty.
+fn interceptor_class<'local>(env: &mut JNIEnv<'local>) -> Result<JClass<'local>> {
+    let mut class = CLASS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(class) = class.as_ref() {
+        return Ok(JClass::from(env.new_local_ref(class)?));
+    }
+
+    let client_class = crate::sdk::jni::cache::class(env, "net/minecraft/client/MinecraftClient")?;
+    let loader = env.call_method(&client_class, "getClassLoader", "()Ljava/lang/ClassLoader;", &[])?.l()?;
+    let defined = match env.define_class(CLASS_NAME, &loader, &class_bytes()) {
+        Ok(defined) => defined,
+        Err(e) => {
+            // Defined by an earlier injection of the client into the same game, the class is reused
+            // and its natives pointed at this copy below.
+            let Some(exception) = take_java_exception(env) else {
+                return Err(e.into());
+            };
+            debug!("Reusing the packet interceptor class: {}", exception);
+            let name = CLASS_NAME.replace('/', ".").to_java(env)?;
+            JClass::from(env.call_method(&loader, "loadClass", "(Ljava/lang/String;)Ljava/lang/Class;", &[JValue::Object(&name)])?.l()?)
+        }
+    };
+
+    env.register_native_methods(
+        &defined,
+        &[
+            NativeMethod { name: "onReceive".into(), sig: CALLBACK_SIGNATURE.into(), fn_ptr: on_receive as *mut _ },
+            NativeMethod { name: "onSend".into(), sig: CALLBACK_SIGNATURE.into(), fn_ptr: on_send as *mut _ },
+        ],
+    )?;
+    *class = Some(env.new_global_ref(&defined)?);
+    Ok(defined)
+}
+
+// The class, as if compiled from:
+//
+//   public class PacketInterceptor extends ChannelDuplexHandler {
+//       public void channelRead(ChannelHandlerContext ctx, Object msg) {
+//           if (!onReceive(msg)) super.channelRead(ctx, msg);
+//       }
+//
+//       public void write(ChannelHandlerContext ctx, Object msg, ChannelPromise promise) {
+//           if (onSend(msg)) promise.trySuccess();
+//           else super.write(ctx, msg, promise);
+//       }
+//
+//       private static native boolean onReceive(Object msg);
+//       private static native boolean onSend(Object msg);
+//   }
+//
+// Cancelled writes complete their promise, so whoever waits for the packet to be sent is not stuck.
+fn class_bytes() -> Vec<u8> {
+    const CONTEXT: &str = "Lio/netty/channel/ChannelHandlerContext;";
+    const PROMISE: &str = "Lio/netty/channel/ChannelPromise;";
+    let read_descriptor = format!("({}Ljava/lang/Object;)V", CONTEXT);
+    let write_descriptor = format!("({}Ljava/lang/Object;{})V", CONTEXT, PROMISE);
+
+    let mut writer = ClassWriter::new(CLASS_NAME, SUPER_CLASS);
+    let super_init = writer.method_ref(SUPER_CLASS, "<init>", "()V");
+    let super_read = writer.method_ref(SUPER_CLASS, "channelRead", &read_descriptor);
+    let super_write = writer.method_ref(SUPER_CLASS, "write", &write_descriptor);
+    let on_receive = writer.method_ref(CLASS_NAME, "onReceive", CALLBACK_SIGNATURE);
+    let on_send = writer.method_ref(CLASS_NAME, "onSend", CALLBACK_SIGNATURE);
+    let try_success = writer.interface_method_ref("io/netty/channel/ChannelPromise", "trySuccess", "()Z");
+
+    let [init_high, init_low] = super_init.to_be_bytes();
+    writer.method(ACC_PUBLIC, "<init>", "()V", 1, 1, vec![op::ALOAD_0, op::INVOKESPECIAL, init_high, init_low, op::RETURN]);
+
+    let [receive_high, receive_low] = on_receive.to_be_bytes();
+    let [read_high, read_low] = super_read.to_be_bytes();
+    #[rustfmt::skip]
+    let read = vec![
+        op::ALOAD_2,
+        op::INVOKESTATIC, receive_high, receive_low,
+        // To the final return, 9 bytes ahead.
+        op::IFNE, 0, 9,
+        op::ALOAD_0, op::ALOAD_1, op::ALOAD_2,
+        op::INVOKESPECIAL, read_high, read_low,
+        op::RETURN,
+    ];
+    writer.method(ACC_PUBLIC, "channelRead", &read_descriptor, 3, 3, read);
+
+    let [send_high, send_low] = on_send.to_be_bytes();
+    let [write_high, write_low] = super_write.to_be_bytes();
+    let [success_high, success_low] = try_success.to_be_bytes();
+    #[rustfmt::skip]
+    let write = vec![
+        op::ALOAD_2,
+        op::INVOKESTATIC, send_high, send_low,
+        // To the cancelled branch, 11 bytes ahead.
+        op::IFNE, 0, 11,
+        op::ALOAD_0, op::ALOAD_1, op::ALOAD_2, op::ALOAD_3,
+        op::INVOKESPECIAL, write_high, write_low,
+        op::RETURN,
+        op::ALOAD_3,
+        op::INVOKEINTERFACE, success_high, success_low, 1, 0,
+        op::POP,
+        op::RETURN,
+    ];
+    writer.method(ACC_PUBLIC, "write", &write_descriptor, 4, 4, write);
+
+    writer.native_method(ACC_PRIVATE | ACC_STATIC, "onReceive", CALLBACK_SIGNATURE);
+    writer.native_method(ACC_PRIVATE | ACC_STATIC, "onSend", CALLBACK_SIGNATURE);
+    writer.finish()
+}
+
+extern "system" fn on_receive<'local>(mut env: JNIEnv<'local>, _class: JClass<'local>, packet: JObject<'local>) -> jboolean {
+    INBOUND.fetch_add(1, Ordering::Relaxed);
+    dispatch(&mut env, PacketDirection::Inbound, &packet)
+}
+
+extern "system" fn on_send<'local>(mut env: JNIEnv<'local>, _class: JClass<'local>, packet: JObject<'local>) -> jboolean {
+    OUTBOUND.fetch_add(1, Ordering::Relaxed);
+    dispatch(&mut env, PacketDirection::Outbound, &packet)
+}
+
+// Posts the packet and returns whether it is cancelled. Called by the JVM, so nothing may unwind or
+// be left pending: panics and exceptions are logged and the packet passes.
+fn dispatch(env: &mut JNIEnv, direction: PacketDirection, packet: &JObject) -> jboolean {
+    if packet.is_null() || !event::bus().has_subscribers::<PacketEvent>() {
+        return JNI_FALSE;
+    }
+
+    let cancelled = catch_unwind(AssertUnwindSafe(|| -> Result<bool> {
+        let mut packet_event = PacketEvent::new(direction, env.new_global_ref(packet)?);
+        event::post(env, &mut packet_event);
+        Ok(packet_event.is_cancelled())
+    }));
+
+    match cancelled {
+        Ok(Ok(true)) => {
+            CANCELLED.fetch_add(1, Ordering::Relaxed);
+            JNI_TRUE
+        }
+        Ok(Ok(false)) => JNI_FALSE,
+        Ok(Err(e)) => {
+            let e = take_java_exception(env).unwrap_or(e);
+            error!("Dispatching a {:?} packet failed: {}", direction, e);
+            JNI_FALSE
+        }
+        Err(_) => {
+            let _ = env.exception_clear();
+            error!("A packet handler panicked, the {:?} packet passes", direction);
+            JNI_FALSE
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use jni::sys::{jint, jsize, JNI_OK};
+use jni::JavaVM;
+use tracing::{debug, info, warn};
+use windows::core::{s, PCSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::LibraryLoader::{GetModuleHandleA, GetProcAddress};
+use windows::Win32::UI::WindowsAndMessaging::{MessageBoxA, MB_ICONERROR, MB_OK};
+
+use crate::sdk;
+
+// Class that has to be loadable for the process to be a supported Minecraft client.
+const CLIENT_CLASS: &str = "net/minecraft/client/MinecraftClient";
+// Entry point of the client, which keeps its name in obfuscated builds. Looked for instead of the
+// client class there, as mappings are only loaded once the VM is selected and its version is known.
+const MAIN_CLASS: &str = "net/minecraft/client/main/Main";
+
+// Signature of `JNI_GetCreatedJavaVMs` as exported by `jvm.dll`.
+#[allow(non_camel_case_types)]
+type JNI_GetCreatedJavaVMs_Fn = unsafe extern "system" fn(vm_buf: *mut *mut jni::sys::JavaVM, buf_len: jsize, num_vms: *mut jsize) -> jint;
+
+// Checks run before anything else is set up, in order. Each one names what is wrong in terms a user
+// who injected into the wrong process can act on.
+#[derive(Debug, thiserror::Error)]
+pub enum PreflightError {
+    #[error("jvm.dll is not loaded in this process. Inject into the Minecraft Java process (javaw.exe) instead.")]
+    JvmNotLoaded,
+    #[error("jvm.dll does not export JNI_GetCreatedJavaVMs. The Java runtime is not supported.")]
+    MissingExport,
+    #[error("No Java VM has been created in this process yet (JNI_GetCreatedJavaVMs returned {code}, {count} VMs).")]
+    NoJavaVm { code: jint, count: jsize },
+    #[error("None of the {probed} Java VMs in this process runs a supported Minecraft client ({}).", rejections.join("; "))]
+    NotMinecraft { probed: usize, rejections: Vec<String> },
+}
+
+// Verifies that the DLL was injected into a supported Minecraft client: `jvm.dll` is loaded, it
+// exports `JNI_GetCreatedJavaVMs`, a VM exists and the client class can be found in one of them.
+pub fn check() -> Result<(), PreflightError> {
+    retrieve_java_vm().map(|_| ())
+}
+
+// Returns the Java VM running Minecraft. Some launchers host helper VMs in the same process, so every
+// VM is probed for the client class and the first one that has it is used. The calling thread stays
+// attached to the selected VM as a daemon and is detached from the rejected ones.
+pub fn retrieve_java_vm() -> Result<JavaVM, PreflightError> {
+    let jvm_dll_handle = unsafe { GetModuleHandleA(s!("jvm.dll")) }.map_err(|_| PreflightError::JvmNotLoaded)?;
+    let get_created_java_vms = unsafe { GetProcAddress(jvm_dll_handle, s!("JNI_GetCreatedJavaVMs")) }.ok_or(PreflightError::MissingExport)?;
+    let get_created_java_vms: JNI_GetCreatedJavaVMs_Fn = unsafe { std::mem::transmute(get_created_java_vms) };
+
+    // Ask for the count first, then fetch all of them into a buffer of that size.
+    let mut count: jsize = 0;
+    let code = unsafe { get_created_java_vms(std::ptr::null_mut(), 0, &mut count) };
+    if code != JNI_OK || count < 1 {
+        return Err(PreflightError::NoJavaVm { code, count });
+    }
+
+    let mut vms = vec![std::ptr::null_mut(); count as usize];
+    let code = unsafe { get_created_java_vms(vms.as_mut_ptr(), count, &mut count) };
+    if code != JNI_OK {
+        return Err(PreflightError::NoJavaVm { code, count });
+    }
+    // The count may have shrunk in between, never read past what was filled in.
+    vms.truncate(count.max(0) as usize);
+    debug!("Found {} Java VMs", vms.len());
+
+    select_java_vm(vms.into_iter().map(probe))
+}
+
+// Picks the first candidate that passed its probe. Rejected candidates are logged with their reason,
+// and if none passed the error lists all of them.
+fn select_java_vm<T>(candidates: impl IntoIterator<Item = Result<T, String>>) -> Result<T, PreflightError> {
+    let mut rejections = Vec::new();
+    for (index, candidate) in candidates.into_iter().enumerate() {
+        match candidate {
+            Ok(candidate) => {
+                info!("Using Java VM #{}", index);
+                return Ok(candidate);
+            }
+            Err(reason) => {
+                warn!("Rejected Java VM #{}: {}", index, reason);
+                rejections.push(format!("#{}: {}", index, reason));
+            }
+        }
+    }
+
+    Err(PreflightError::NotMinecraft { probed: rejections.len(), rejections })
+}
+
+// Attaches to the VM and looks for the client class (or its entry point in obfuscated builds),
+// detaching again if it is missing.
+fn probe(vm: *mut jni::sys::JavaVM) -> Result<JavaVM, String> {
+    let jvm = unsafe { JavaVM::from_raw(vm) }.map_err(|e| format!("invalid VM pointer: {}", e))?;
+    let mut env = jvm.attach_current_thread_as_daemon().map_err(|e| format!("attaching failed: {}", e))?;
+
+    let class = if sdk::mappings::uses_yarn_names(&mut env) { CLIENT_CLASS } else { MAIN_CLASS };
+    let found = match env.find_class(class) {
+        Ok(class) => {
+            let _ = env.delete_local_ref(class);
+            Ok(())
+        }
+        Err(_) => Err(sdk::Error::class_not_found(&mut env, class).to_string()),
+    };
+
+    match found {
+        Ok(()) => Ok(jvm),
+        Err(reason) => {
+            // SAFETY: the thread was attached above and no references into this VM are kept.
+            unsafe { jvm.detach_current_thread() };
+            Err(reason)
+        }
+    }
+}
+
+// Shows the failed check in a message box, the only feedback visible when no console was allocated.
+pub fn report(error: &PreflightError) {
+    let text = format!("{}\0", error);
+    unsafe {
+        MessageBoxA(HWND(0), PCSTR(text.as_ptr()), s!("LiquidBounce Lite"), MB_OK | MB_ICONERROR);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_passing_vm_is_selected() {
+        let probes = vec![Err("no client class".to_string()), Ok("minecraft"), Ok("other")];
+        assert_eq!(select_java_vm(probes).unwrap(), "minecraft");
+    }
+
+    #[test]
+    fn vms_after_the_selected_one_are_not_probed() {
+        let mut probed = 0;
+        let probes = (0..3).map(|index| {
+            probed += 1;
+            if index == 0 { Ok(index) } else { Err("unreachable".to_string()) }
+        });
+        assert_eq!(select_java_vm(probes).unwrap(), 0);
+        assert_eq!(probed, 1);
+    }
+
+    #[test]
+    fn every_rejection_is_reported() {
+        let probes: Vec<Result<(), String>> = vec![Err("attaching failed".to_string()), Err("no client class".to_string())];
+        match select_java_vm(probes) {
+            Err(PreflightError::NotMinecraft { probed, rejections }) => {
+                assert_eq!(probed, 2);
+                assert_eq!(rejections, ["#0: attaching failed", "#1: no client class"]);
+            }
+            other => panic!("expected NotMinecraft, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn no_candidates_is_an_error() {
+        let probes: Vec<Result<(), String>> = Vec::new();
+        assert!(matches!(select_java_vm(probes), Err(PreflightError::NotMinecraft { probed: 0, .. })));
+    }
+}
@@ -0,0 +1,257 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+use tracing::{info, warn};
+
+use crate::config::{self, Config};
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::text::Text;
+use crate::world_change::WorldChange;
+
+// Name of the profile used wherever no override matches.
+pub const DEFAULT_PROFILE: &str = "default";
+
+pub const FILE_NAME: &str = "profiles.tsv";
+
+// Directory in the client directory holding the saved profiles, one config file each.
+pub const PROFILES_DIR: &str = "profiles";
+
+static PROFILES: Mutex<ProfileSelector> = Mutex::new(ProfileSelector::new());
+
+pub fn profiles() -> MutexGuard<'static, ProfileSelector> {
+    PROFILES.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+pub fn path() -> PathBuf {
+    config::client_dir().join(FILE_NAME)
+}
+
+pub fn profile_path(name: &str) -> PathBuf {
+    config::client_dir().join(PROFILES_DIR).join(format!("{}.toml", name))
+}
+
+// Profile names become file names, so they are limited to letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_')
+}
+
+// Names of the saved profiles, sorted.
+pub fn saved_profiles() -> Result<Vec<String>> {
+    let entries = match fs::read_dir(config::client_dir().join(PROFILES_DIR)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "toml") {
+            if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()).filter(|name| is_valid_name(name)) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+// Saves the enabled modules and their settings as profile `name`. Keybinds and friends are the same
+// in every profile and stay in the config.
+pub fn save_profile(name: &str) -> Result<()> {
+    if !is_valid_name(name) {
+        bail!("Invalid profile name {}, use letters, digits, - and _", name);
+    }
+    let mut config = Config::capture();
+    config.friends.clear();
+    for module in &mut config.modules {
+        module.key = None;
+    }
+    config.save(&profile_path(name))?;
+    if profiles().active() == name {
+        profiles().mark_saved();
+    }
+    info!("Saved profile {}", name);
+    Ok(())
+}
+
+// Applies a switch `ProfileSelector::on_world_change` returned through the module manager. A profile
+// that was never saved leaves the modules as they are, except the default one, which is saved the
+// first time it is left so leaving the server has something to return to.
+pub fn apply_switch(env: &mut JNIEnv, client: &MinecraftClient, switch: &ProfileSwitch) -> Result<()> {
+    if switch.from == DEFAULT_PROFILE && !profile_path(DEFAULT_PROFILE).exists() {
+        save_profile(DEFAULT_PROFILE)?;
+    }
+
+    let Some(config) = Config::load(&profile_path(&switch.to))? else {
+        info!("Profile {} was never saved, keeping the current modules", switch.to);
+        return Ok(());
+    };
+    config.apply_modules(env, client);
+    // Applying marked the profile as changed.
+    profiles().mark_saved();
+    Ok(())
+}
+
+// A change of the active profile, to be applied by whoever owns the module states.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSwitch {
+    pub from: String,
+    pub to: String,
+    // Whether the profile being left had changes that were not saved.
+    pub discarded_changes: bool,
+}
+
+// Picks the settings profile for the server the client is on. Servers without an override use the
+// default profile, so leaving a server with an override switches back to the default one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSelector {
+    // Lowercased server address to profile name.
+    overrides: BTreeMap<String, String>,
+    active: Cow<'static, str>,
+    dirty: bool,
+}
+
+impl Default for ProfileSelector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProfileSelector {
+    pub const fn new() -> Self {
+        Self {
+            overrides: BTreeMap::new(),
+            active: Cow::Borrowed(DEFAULT_PROFILE),
+            dirty: false,
+        }
+    }
+
+    // Reads the overrides from `path`, one `server<TAB>profile` line each. A missing file means
+    // there are none, unreadable lines are skipped with a warning.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut selector = Self::new();
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(selector),
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match line.split_once('\t') {
+                Some((server, profile)) if !profile.trim().is_empty() => selector.set_override(server, profile.trim()),
+                _ => warn!("Skipping malformed profile line '{}'", line),
+            }
+        }
+        Ok(selector)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents: String = self.overrides.iter().map(|(server, profile)| format!("{}\t{}\n", server, profile)).collect();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    pub fn active(&self) -> &str {
+        &self.active
+    }
+
+    pub fn overrides(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.overrides.iter().map(|(server, profile)| (server.as_str(), profile.as_str()))
+    }
+
+    // Uses `profile` whenever the client joins `server`. Addresses are compared case-insensitively.
+    pub fn set_override(&mut self, server: &str, profile: &str) {
+        self.overrides.insert(server.trim().to_lowercase(), profile.to_string());
+    }
+
+    pub fn remove_override(&mut self, server: &str) -> bool {
+        self.overrides.remove(&server.trim().to_lowercase()).is_some()
+    }
+
+    // Profile to use on `server`, `None` being singleplayer or no world at all.
+    pub fn profile_for(&self, server: Option<&str>) -> &str {
+        server
+            .and_then(|server| self.overrides.get(&server.trim().to_lowercase()))
+            .map_or(DEFAULT_PROFILE, String::as_str)
+    }
+
+    // Records that the active profile was changed since it was last saved or applied.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    // Selects the profile for the server after a world change. When it differs from the active one
+    // the switch is returned for `apply_switch`, and if the profile being left had unsaved changes the
+    // player is told in chat so they are not lost silently.
+    pub fn on_world_change(&mut self, env: &mut JNIEnv, client: &MinecraftClient, change: WorldChange) -> Result<Option<ProfileSwitch>> {
+        let server = match change {
+            WorldChange::Left => None,
+            WorldChange::Joined | WorldChange::Switched => client.get_server_address(env)?,
+        };
+
+        let target = self.profile_for(server.as_deref()).to_string();
+        if target == self.active {
+            return Ok(None);
+        }
+
+        let from = std::mem::replace(&mut self.active, Cow::Owned(target.clone())).into_owned();
+        // A default profile that was never saved is saved by `apply_switch` instead.
+        let dirty = std::mem::take(&mut self.dirty);
+        let discarded_changes = dirty && (from != DEFAULT_PROFILE || profile_path(DEFAULT_PROFILE).exists());
+        let switch = ProfileSwitch { from, to: target, discarded_changes };
+        info!("Switching profile from {} to {}", switch.from, switch.to);
+
+        if switch.discarded_changes {
+            warn!("Profile {} had unsaved changes", switch.from);
+            // The world may already be gone on `Left`, then only the log line remains.
+            if let Some(player) = client.get_player(env)? {
+                let message = format!("§eProfile §f{}§e had unsaved changes, switched to §f{}", switch.from, switch.to);
+                let text = Text::literal(env, &message)?;
+                player.send_message(env, &text, false)?;
+                env.delete_local_ref(text.inner)?;
+                env.delete_local_ref(player.as_entity().inner)?;
+            }
+        }
+
+        Ok(Some(switch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_names_are_plain_file_names() {
+        assert!(is_valid_name("pvp_2-strict"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../config"));
+        assert!(!is_valid_name("hypixel.net"));
+    }
+
+    #[test]
+    fn servers_without_an_override_use_the_default_profile() {
+        let mut selector = ProfileSelector::new();
+        selector.set_override(" Play.Example.com ", "pvp");
+        assert_eq!(selector.profile_for(Some("play.example.com")), "pvp");
+        assert_eq!(selector.profile_for(Some("other.example.com")), DEFAULT_PROFILE);
+        assert_eq!(selector.profile_for(None), DEFAULT_PROFILE);
+    }
+}
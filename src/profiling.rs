@@ -0,0 +1,122 @@
+use std::cmp::Reverse;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+// Number of ticks a module's tick time is averaged over.
+const WINDOW_TICKS: usize = 100;
+
+// Average tick time above which a module is reported as slow.
+pub const DEFAULT_BUDGET: Duration = Duration::from_millis(2);
+
+static TIMINGS: Mutex<ModuleTimings> = Mutex::new(ModuleTimings::new(DEFAULT_BUDGET));
+
+pub fn timings() -> MutexGuard<'static, ModuleTimings> {
+    TIMINGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Tick times of one module. The window is a fixed array, so recording never allocates.
+struct ModuleTiming {
+    durations: [Duration; WINDOW_TICKS],
+    next: usize,
+    filled: usize,
+    total: Duration,
+    over_budget: bool,
+}
+
+impl ModuleTiming {
+    fn new() -> Self {
+        Self {
+            durations: [Duration::ZERO; WINDOW_TICKS],
+            next: 0,
+            filled: 0,
+            total: Duration::ZERO,
+            over_budget: false,
+        }
+    }
+
+    fn record(&mut self, duration: Duration) {
+        self.total = self.total - self.durations[self.next] + duration;
+        self.durations[self.next] = duration;
+        self.next = (self.next + 1) % WINDOW_TICKS;
+        self.filled = (self.filled + 1).min(WINDOW_TICKS);
+    }
+
+    fn average(&self) -> Duration {
+        if self.filled == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.filled as u32
+    }
+}
+
+// Per-module tick times, used to find the module that costs the most frame time.
+pub struct ModuleTimings {
+    budget: Duration,
+    modules: Vec<(&'static str, ModuleTiming)>,
+}
+
+impl ModuleTimings {
+    pub const fn new(budget: Duration) -> Self {
+        Self { budget, modules: Vec::new() }
+    }
+
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    pub fn set_budget(&mut self, budget: Duration) {
+        self.budget = budget;
+    }
+
+    // Runs one tick of a module and records how long it took.
+    pub fn measure<T>(&mut self, module: &'static str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(module, start.elapsed());
+        result
+    }
+
+    // Records a tick time. Once a full window has been recorded, a module whose average goes over
+    // the budget is logged once, and again only after it has dropped back under it.
+    pub fn record(&mut self, module: &'static str, duration: Duration) {
+        let index = match self.modules.iter().position(|(name, _)| *name == module) {
+            Some(index) => index,
+            None => {
+                self.modules.push((module, ModuleTiming::new()));
+                self.modules.len() - 1
+            }
+        };
+
+        let timing = &mut self.modules[index].1;
+        timing.record(duration);
+        if timing.filled < WINDOW_TICKS {
+            return;
+        }
+
+        let average = timing.average();
+        if average > self.budget && !timing.over_budget {
+            timing.over_budget = true;
+            warn!("Module {} takes {:.2} ms per tick on average, over the budget of {:.2} ms", module, average.as_secs_f64() * 1000.0, self.budget.as_secs_f64() * 1000.0);
+        } else if average <= self.budget {
+            timing.over_budget = false;
+        }
+    }
+
+    // Average tick time of a module over the window, `None` if it never ticked.
+    pub fn average(&self, module: &str) -> Option<Duration> {
+        self.modules.iter().find(|(name, _)| *name == module).map(|(_, timing)| timing.average())
+    }
+
+    // Average tick times of all modules, slowest first.
+    pub fn report(&self) -> Vec<(&'static str, Duration)> {
+        let mut report: Vec<_> = self.modules.iter().map(|(name, timing)| (*name, timing.average())).collect();
+        report.sort_by_key(|&(_, average)| Reverse(average));
+        report
+    }
+
+    pub fn clear(&mut self) {
+        self.modules.clear();
+    }
+}
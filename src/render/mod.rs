@@ -0,0 +1,75 @@
+use std::sync::{Mutex, MutexGuard};
+
+use crate::sdk::math::ScreenRect;
+
+pub mod overlay;
+
+static SCENE: Mutex<Scene> = Mutex::new(Scene::new());
+
+// Locks and returns the shapes drawn over the game.
+pub fn scene() -> MutexGuard<'static, Scene> {
+    SCENE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Color {
+    pub const WHITE: Color = Color::new(255, 255, 255);
+    pub const RED: Color = Color::new(255, 85, 85);
+    pub const GREEN: Color = Color::new(85, 255, 85);
+    pub const AQUA: Color = Color::new(85, 255, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+}
+
+// Something drawn over the game. Positions are in framebuffer pixels with the origin in the top
+// left corner, the same space `Projection` maps to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    // The outline of a rectangle.
+    Rect { rect: ScreenRect, color: Color },
+    Line { from: (f64, f64), to: (f64, f64), color: Color },
+    // Text with its top left corner at the position.
+    Text { position: (f64, f64), text: String, color: Color },
+}
+
+// Everything drawn over the game, as one layer of shapes per owner. Owners replace their layer
+// whenever they have new data, usually every tick, and renderers draw the latest layers every frame.
+#[derive(Debug, Default)]
+pub struct Scene {
+    layers: Vec<(&'static str, Vec<Shape>)>,
+}
+
+impl Scene {
+    pub const fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    // Replaces the layer of `owner`.
+    pub fn submit(&mut self, owner: &'static str, shapes: Vec<Shape>) {
+        match self.layers.iter_mut().find(|(layer_owner, _)| *layer_owner == owner) {
+            Some((_, layer)) => *layer = shapes,
+            None => self.layers.push((owner, shapes)),
+        }
+    }
+
+    pub fn clear(&mut self, owner: &str) {
+        self.layers.retain(|(layer_owner, _)| *layer_owner != owner);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.iter().all(|(_, layer)| layer.is_empty())
+    }
+
+    // Every shape, layers in the order they were first submitted.
+    pub fn shapes(&self) -> impl Iterator<Item = &Shape> {
+        self.layers.iter().flat_map(|(_, layer)| layer.iter())
+    }
+}
@@ -0,0 +1,221 @@
+use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
+use std::thread;
+
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+use tracing::{error, info};
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint, BitBlt, ClientToScreen, CreateCompatibleBitmap, CreateCompatibleDC, CreatePen, DeleteDC, DeleteObject, EndPaint,
+    FillRect, GetStockObject, InvalidateRect, LineTo, MoveToEx, Rectangle, SelectObject, SetBkMode, SetTextColor, TextOutW,
+    BLACK_BRUSH, HBRUSH, HDC, NULL_BRUSH, PAINTSTRUCT, PS_SOLID, SRCCOPY, TRANSPARENT,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetClientRect, GetForegroundWindow, GetMessageW, IsIconic,
+    IsWindow, PostQuitMessage, RegisterClassExW, SetLayeredWindowAttributes, SetTimer, SetWindowPos, ShowWindow, TranslateMessage,
+    HMENU, HWND_TOPMOST, LWA_COLORKEY, MSG, SWP_NOACTIVATE, SWP_SHOWWINDOW, SW_HIDE, WM_DESTROY, WM_ERASEBKGND, WM_PAINT, WM_TIMER,
+    WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_POPUP,
+};
+
+use super::{scene, Color, Shape};
+use crate::sdk::game::client::MinecraftClient;
+
+const CLASS_NAME: PCWSTR = w!("LiquidBounceLiteOverlay");
+
+// Redraws at about 60 frames per second. The shapes only change with the ticks, the frames in
+// between keep the overlay on top of the game window while it moves.
+const FRAME_INTERVAL_MS: u32 = 16;
+const FRAME_TIMER: usize = 1;
+
+// Pixels of this color are see-through, so nothing can be drawn in pure black.
+const TRANSPARENT_KEY: COLORREF = COLORREF(0);
+
+// Set once the overlay thread is started. It is never cleared, the overlay only stops when the game
+// window is gone, and then the game is shutting down.
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+// The game window the overlay covers.
+static GAME_WINDOW: AtomicIsize = AtomicIsize::new(0);
+
+// Starts the overlay, a transparent window that ignores the mouse, kept above the game window and
+// drawing the shapes of the scene. It runs on a thread of its own with its own message loop, as the
+// game's renderer cannot be hooked yet. The window is hidden while the scene is empty or the game is
+// not in the foreground. Does nothing if the overlay is already running.
+pub fn start(env: &mut JNIEnv, client: &MinecraftClient) -> Result<()> {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let window = client.get_window(env)?;
+    let hwnd = window.get_hwnd(env);
+    env.delete_local_ref(window.inner)?;
+    GAME_WINDOW.store(hwnd?.0, Ordering::SeqCst);
+
+    thread::Builder::new().name("overlay".into()).spawn(|| {
+        if let Err(e) = run() {
+            error!("Overlay stopped: {:?}", e);
+        }
+    })?;
+    Ok(())
+}
+
+fn run() -> Result<()> {
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+        let class = WNDCLASSEXW {
+            cbSize: std::mem::size_of::<WNDCLASSEXW>() as u32,
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance.into(),
+            lpszClassName: CLASS_NAME,
+            ..Default::default()
+        };
+        if RegisterClassExW(&class) == 0 {
+            bail!("Failed to register the overlay window class: {}", windows::core::Error::from_win32());
+        }
+
+        let hwnd = CreateWindowExW(
+            WS_EX_LAYERED | WS_EX_TRANSPARENT | WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+            CLASS_NAME,
+            w!("LiquidBounce Lite"),
+            WS_POPUP,
+            0,
+            0,
+            0,
+            0,
+            HWND::default(),
+            HMENU::default(),
+            instance,
+            None,
+        );
+        if hwnd.0 == 0 {
+            bail!("Failed to create the overlay window: {}", windows::core::Error::from_win32());
+        }
+        SetLayeredWindowAttributes(hwnd, TRANSPARENT_KEY, 0, LWA_COLORKEY)?;
+        if SetTimer(hwnd, FRAME_TIMER, FRAME_INTERVAL_MS, None) == 0 {
+            bail!("Failed to start the overlay frame timer: {}", windows::core::Error::from_win32());
+        }
+        info!("Overlay started");
+
+        // `GetMessageW` returns -1 on errors, which must end the loop as well.
+        let mut message = MSG::default();
+        while GetMessageW(&mut message, HWND::default(), 0, 0).0 > 0 {
+            TranslateMessage(&message);
+            DispatchMessageW(&message);
+        }
+    }
+    info!("Overlay closed with the game window");
+    Ok(())
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, message: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match message {
+        WM_TIMER => {
+            follow_game(hwnd);
+            LRESULT(0)
+        }
+        WM_PAINT => {
+            paint(hwnd);
+            LRESULT(0)
+        }
+        // The whole window is painted over every frame, erasing first only makes it flicker.
+        WM_ERASEBKGND => LRESULT(1),
+        WM_DESTROY => {
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, message, wparam, lparam),
+    }
+}
+
+// Moves the overlay onto the client area of the game window and schedules a redraw, or hides it when
+// there is nothing to show.
+unsafe fn follow_game(hwnd: HWND) {
+    let game = HWND(GAME_WINDOW.load(Ordering::SeqCst));
+    if !IsWindow(game).as_bool() {
+        let _ = DestroyWindow(hwnd);
+        return;
+    }
+
+    let mut client = RECT::default();
+    let mut origin = POINT::default();
+    let visible = GetForegroundWindow() == game
+        && !IsIconic(game).as_bool()
+        && !scene().is_empty()
+        && GetClientRect(game, &mut client).is_ok()
+        && ClientToScreen(game, &mut origin).as_bool();
+    if !visible {
+        ShowWindow(hwnd, SW_HIDE);
+        return;
+    }
+
+    let flags = SWP_NOACTIVATE | SWP_SHOWWINDOW;
+    if SetWindowPos(hwnd, HWND_TOPMOST, origin.x, origin.y, client.right, client.bottom, flags).is_ok() {
+        InvalidateRect(hwnd, None, false);
+    }
+}
+
+unsafe fn paint(hwnd: HWND) {
+    // Copied out, so the tick thread is never kept waiting on drawing.
+    let shapes: Vec<Shape> = scene().shapes().cloned().collect();
+
+    let mut paint = PAINTSTRUCT::default();
+    let hdc = BeginPaint(hwnd, &mut paint);
+    let mut bounds = RECT::default();
+    if GetClientRect(hwnd, &mut bounds).is_ok() {
+        // Drawn into a bitmap and copied over at once, drawing straight onto the window flickers.
+        let buffer = CreateCompatibleDC(hdc);
+        let bitmap = CreateCompatibleBitmap(hdc, bounds.right, bounds.bottom);
+        let previous_bitmap = SelectObject(buffer, bitmap);
+
+        FillRect(buffer, &bounds, HBRUSH(GetStockObject(BLACK_BRUSH).0));
+        SetBkMode(buffer, TRANSPARENT);
+        let previous_brush = SelectObject(buffer, GetStockObject(NULL_BRUSH));
+        for shape in &shapes {
+            draw(buffer, shape);
+        }
+        let _ = BitBlt(hdc, 0, 0, bounds.right, bounds.bottom, buffer, 0, 0, SRCCOPY);
+
+        SelectObject(buffer, previous_brush);
+        SelectObject(buffer, previous_bitmap);
+        DeleteObject(bitmap);
+        DeleteDC(buffer);
+    }
+    EndPaint(hwnd, &paint);
+}
+
+unsafe fn draw(hdc: HDC, shape: &Shape) {
+    match shape {
+        Shape::Rect { rect, color } => with_pen(hdc, *color, || {
+            // The right and bottom edges are exclusive.
+            Rectangle(hdc, rect.min_x as i32, rect.min_y as i32, rect.max_x as i32 + 1, rect.max_y as i32 + 1);
+        }),
+        Shape::Line { from, to, color } => with_pen(hdc, *color, || {
+            MoveToEx(hdc, from.0 as i32, from.1 as i32, None);
+            LineTo(hdc, to.0 as i32, to.1 as i32);
+        }),
+        Shape::Text { position, text, color } => {
+            SetTextColor(hdc, to_colorref(*color));
+            let wide: Vec<u16> = text.encode_utf16().collect();
+            TextOutW(hdc, position.0 as i32, position.1 as i32, &wide);
+        }
+    }
+}
+
+unsafe fn with_pen(hdc: HDC, color: Color, draw: impl FnOnce()) {
+    let pen = CreatePen(PS_SOLID, 1, to_colorref(color));
+    let previous = SelectObject(hdc, pen);
+    draw();
+    SelectObject(hdc, previous);
+    DeleteObject(pen);
+}
+
+fn to_colorref(color: Color) -> COLORREF {
+    let colorref = COLORREF(color.r as u32 | (color.g as u32) << 8 | (color.b as u32) << 16);
+    // Black would be keyed out, the closest visible color is drawn instead.
+    if colorref == TRANSPARENT_KEY {
+        return COLORREF(1);
+    }
+    colorref
+}
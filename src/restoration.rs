@@ -0,0 +1,122 @@
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::{debug, error};
+
+use crate::sdk::error::take_java_exception;
+
+static RESTORATIONS: Mutex<RestorationRegistry> = Mutex::new(RestorationRegistry::new());
+
+pub fn restorations() -> MutexGuard<'static, RestorationRegistry> {
+    RESTORATIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Puts back game state a module changed. It runs on the tick thread and has to be idempotent: the
+// game may already have reset the state itself, and it must still be safe to run then.
+pub type UndoCallback = Box<dyn for<'local> FnOnce(&mut JNIEnv<'local>) -> Result<()> + Send>;
+
+struct Restoration {
+    id: u64,
+    owner: &'static str,
+    description: String,
+    undo: UndoCallback,
+}
+
+// Undo actions for game state that outlives a tick (gamma, abilities, step height, cooldowns).
+// Whoever changes such state registers how to put it back, so it is restored when the module is
+// disabled, when the client loop stops or panics and when the DLL is unloaded, even if the module
+// itself never gets to clean up.
+pub struct RestorationRegistry {
+    entries: Vec<Restoration>,
+    next_id: u64,
+}
+
+impl Default for RestorationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RestorationRegistry {
+    pub const fn new() -> Self {
+        Self { entries: Vec::new(), next_id: 1 }
+    }
+
+    // Registers an undo action and returns its id, which can be used to drop it once the module
+    // restored the state on its own.
+    pub fn register<F>(&mut self, owner: &'static str, description: impl Into<String>, undo: F) -> u64
+    where
+        F: for<'local> FnOnce(&mut JNIEnv<'local>) -> Result<()> + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.entries.push(Restoration {
+            id,
+            owner,
+            description: description.into(),
+            undo: Box::new(undo),
+        });
+        id
+    }
+
+    // Drops an undo action without running it. Returns whether it was registered.
+    pub fn unregister(&mut self, id: u64) -> bool {
+        let count = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.len() != count
+    }
+
+    // Owner and description of every registered action, oldest first.
+    pub fn list(&self) -> Vec<(&'static str, &str)> {
+        self.entries.iter().map(|entry| (entry.owner, entry.description.as_str())).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn take(&mut self, owner: Option<&str>) -> Vec<Restoration> {
+        let (taken, kept) = std::mem::take(&mut self.entries)
+            .into_iter()
+            .partition(|entry| owner.is_none_or(|owner| entry.owner == owner));
+        self.entries = kept;
+        taken
+    }
+}
+
+// Runs and removes the undo actions of `owner`, e.g. when the module gets disabled.
+pub fn restore_owner(env: &mut JNIEnv, owner: &str) -> usize {
+    // Taken out first so undo actions may register or unregister without deadlocking.
+    let entries = restorations().take(Some(owner));
+    run(env, entries)
+}
+
+// Runs and removes every undo action, on eject, restart or request of the user.
+pub fn restore_all(env: &mut JNIEnv) -> usize {
+    let entries = restorations().take(None);
+    run(env, entries)
+}
+
+// Runs the actions newest first, undoing changes in the reverse order they were made. A failing
+// action is logged and does not stop the rest. Returns how many succeeded.
+fn run(env: &mut JNIEnv, entries: Vec<Restoration>) -> usize {
+    let mut restored = 0;
+    for entry in entries.into_iter().rev() {
+        match (entry.undo)(env) {
+            Ok(()) => {
+                debug!("Restored {} of {}", entry.description, entry.owner);
+                restored += 1;
+            }
+            Err(e) => match take_java_exception(env) {
+                Some(exception) => error!("Restoring {} of {} failed: {:?} ({})", entry.description, entry.owner, e, exception),
+                None => error!("Restoring {} of {} failed: {:?}", entry.description, entry.owner, e),
+            },
+        }
+    }
+    restored
+}
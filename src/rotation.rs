@@ -0,0 +1,235 @@
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::Result;
+use jni::JNIEnv;
+
+use crate::event::{self, PacketDirection, PacketEvent, TickEvent};
+use crate::restoration::restorations;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::packet::PlayerMoveC2SPacket;
+use crate::sdk::game::player::ClientPlayerEntity;
+use crate::sdk::math::rotations::{angle_difference, limit_rotation_change, rotation_to};
+use crate::sdk::math::DVec3;
+use crate::timing::Scheduler;
+
+// Snaps the player's rotation for an action and puts it back afterwards. The action has to happen
+// between `begin` and the restore, for example using an item, whose packet carries the rotation:
+//
+//     let transaction = RotationTransaction::begin(env, &player, yaw, pitch)?;
+//     player.use_held_item(env, &interaction_manager, Hand::MainHand)?;
+//     transaction.restore_next_tick(scheduler, "Pearl");
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotationTransaction {
+    original_yaw: f32,
+    original_pitch: f32,
+}
+
+impl RotationTransaction {
+    // Remembers the current rotation and turns the player to `yaw` and `pitch`.
+    pub fn begin(env: &mut JNIEnv, player: &ClientPlayerEntity, yaw: f32, pitch: f32) -> Result<Self> {
+        let transaction = Self {
+            original_yaw: player.get_yaw(env)?,
+            original_pitch: player.get_pitch(env)?,
+        };
+
+        player.set_yaw(env, yaw)?;
+        player.set_pitch(env, pitch.clamp(-90.0, 90.0))?;
+        Ok(transaction)
+    }
+
+    pub fn original_rotation(&self) -> (f32, f32) {
+        (self.original_yaw, self.original_pitch)
+    }
+
+    // Restores the rotation right away.
+    pub fn restore(self, env: &mut JNIEnv, player: &ClientPlayerEntity) -> Result<()> {
+        player.set_yaw(env, self.original_yaw)?;
+        player.set_pitch(env, self.original_pitch)?;
+        Ok(())
+    }
+
+    // Restores the rotation on the next tick, after the game sent the snapped rotation to the server
+    // with its movement packet. Nothing is restored if the player is gone by then. The restore is
+    // also registered under `owner`, so disabling the module or stopping the client before the next
+    // tick, which drops the scheduled task, still puts the rotation back.
+    pub fn restore_next_tick(self, scheduler: &mut Scheduler, owner: &'static str) {
+        let id = restorations().register(owner, "snapped rotation", move |env| self.restore_client_player(env));
+        scheduler.schedule_in_ticks(owner, 1, move |env| {
+            if !restorations().unregister(id) {
+                return Ok(());
+            }
+            self.restore_client_player(env)
+        });
+    }
+
+    fn restore_client_player(self, env: &mut JNIEnv) -> Result<()> {
+        let client = MinecraftClient::get_instance(env)?;
+        if let Some(player) = client.get_player(env)? {
+            self.restore(env, &player)?;
+            env.delete_local_ref(player.as_entity().inner)?;
+        }
+        env.delete_local_ref(client.inner)?;
+        Ok(())
+    }
+}
+
+// Turn speed in degrees per tick for requests without a reason to turn slower, reaching any rotation
+// within five ticks.
+pub const DEFAULT_TURN_SPEED: f32 = 40.0;
+
+// A released server rotation this close to the player's own on both axes hands control back.
+const HAND_BACK_ANGLE: f32 = 1.0;
+
+static ROTATIONS: Mutex<RotationManager> = Mutex::new(RotationManager::new());
+
+pub fn rotations() -> MutexGuard<'static, RotationManager> {
+    ROTATIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Request {
+    owner: &'static str,
+    priority: i32,
+    rotation: (f32, f32),
+    speed: f32,
+}
+
+// Owns the rotation sent to the server, which can differ from the one the player sees. Modules
+// request a rotation in every tick they need one and the request with the highest priority wins
+// (the earliest on ties). The server rotation turns towards it by at most the request's speed per
+// tick. Once nothing is requested it turns back to the player's own rotation at the same speed and
+// hands control back.
+#[derive(Debug)]
+pub struct RotationManager {
+    requests: Vec<Request>,
+    // `None` while the server sees the player's own rotation.
+    server: Option<(f32, f32)>,
+    // Owner and speed of the request the server rotation turned towards last.
+    current: Option<(&'static str, f32)>,
+    subscribed: bool,
+}
+
+impl Default for RotationManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RotationManager {
+    pub const fn new() -> Self {
+        Self { requests: Vec::new(), server: None, current: None, subscribed: false }
+    }
+
+    // Requests `rotation` (yaw, pitch) for this tick, turning at most `speed` degrees per tick.
+    pub fn request(&mut self, owner: &'static str, priority: i32, rotation: (f32, f32), speed: f32) {
+        self.requests.push(Request { owner, priority, rotation: (rotation.0, rotation.1.clamp(-90.0, 90.0)), speed: speed.abs() });
+    }
+
+    // Requests the rotation looking from `eyes` at `target` for this tick.
+    pub fn look_at(&mut self, owner: &'static str, priority: i32, eyes: DVec3, target: DVec3, speed: f32) {
+        self.request(owner, priority, rotation_to(eyes, target), speed);
+    }
+
+    // The rotation the server sees, `None` while it is the player's own.
+    pub fn server_rotation(&self) -> Option<(f32, f32)> {
+        self.server
+    }
+
+    // Owner of the request the server rotation is turning towards, `None` while nothing is requested.
+    pub fn owner(&self) -> Option<&'static str> {
+        self.current.map(|(owner, _)| owner)
+    }
+
+    // Whether the server rotation is within `tolerance` degrees of `rotation` on both axes, so a
+    // module can wait with its action until it is turned far enough.
+    pub fn is_facing(&self, rotation: (f32, f32), tolerance: f32) -> bool {
+        self.server.is_some_and(|server| {
+            angle_difference(rotation.0, server.0).abs() <= tolerance && (rotation.1 - server.1).abs() <= tolerance
+        })
+    }
+
+    // Turns the server rotation one tick further and clears the requests. `player` is the player's own
+    // rotation. Returns the new server rotation, `None` once control is handed back.
+    pub fn update(&mut self, player: (f32, f32)) -> Option<(f32, f32)> {
+        // `max_by_key` keeps the last maximum, scanning in reverse makes the earliest one win ties.
+        let winner = self.requests.iter().rev().max_by_key(|request| request.priority).copied();
+        self.requests.clear();
+
+        let start = self.server.unwrap_or(player);
+        self.server = match winner {
+            Some(request) => {
+                self.current = Some((request.owner, request.speed));
+                Some(limit_rotation_change(start, request.rotation, request.speed))
+            }
+            None => {
+                let speed = self.current.take().map_or(DEFAULT_TURN_SPEED, |(_, speed)| speed);
+                let next = limit_rotation_change(start, player, speed);
+                let handed_back = angle_difference(player.0, next.0).abs() <= HAND_BACK_ANGLE
+                    && (player.1 - next.1).abs() <= HAND_BACK_ANGLE;
+                (self.server.is_some() && !handed_back).then_some(next)
+            }
+        };
+        self.server
+    }
+
+    pub fn reset(&mut self) {
+        self.requests.clear();
+        self.server = None;
+        self.current = None;
+    }
+
+    fn tick(&mut self, env: &mut JNIEnv, event: &TickEvent) -> Result<()> {
+        let Some(player_snapshot) = event.snapshot.player else {
+            self.reset();
+            return Ok(());
+        };
+
+        let was_controlling = self.server.is_some();
+        if self.update((player_snapshot.yaw, player_snapshot.pitch)).is_none() && !was_controlling {
+            return Ok(());
+        }
+
+        // No rotation equals NaN, so the game sends a look packet in its next movement update. While
+        // controlling it carries the server rotation, after handing back the player's own.
+        env.with_local_frame(4, |env| -> Result<()> {
+            if let Some(player) = event.client().get_player(env)? {
+                player.set_last_sent_rotation(env, f32::NAN, f32::NAN)?;
+            }
+            Ok(())
+        })
+    }
+
+    // Puts the server rotation into outgoing movement packets that carry a rotation.
+    fn on_packet(&self, env: &mut JNIEnv, event: &mut PacketEvent) -> Result<()> {
+        let Some((yaw, pitch)) = self.server else {
+            return Ok(());
+        };
+        if event.direction != PacketDirection::Outbound || !event.is(env, PlayerMoveC2SPacket::CLASS)? {
+            return Ok(());
+        }
+
+        let packet = PlayerMoveC2SPacket::new(env.new_local_ref(&event.packet)?);
+        if packet.changes_look(env)? {
+            packet.set_yaw(env, yaw)?;
+            packet.set_pitch(env, pitch)?;
+        }
+        env.delete_local_ref(packet.inner)?;
+        Ok(())
+    }
+}
+
+// Turns the server rotation every tick and rewrites outgoing movement packets. Installed after the
+// module manager, so the requests modules make in their tick are turned towards in the same one.
+// Does nothing when called again.
+pub fn install() {
+    let mut rotations = rotations();
+    if rotations.subscribed {
+        return;
+    }
+    rotations.subscribed = true;
+    drop(rotations);
+
+    let mut bus = event::bus();
+    bus.subscribe::<TickEvent, _>("RotationManager", |env, event| self::rotations().tick(env, event));
+    bus.subscribe::<PacketEvent, _>("RotationManager", |env, event| self::rotations().on_packet(env, event));
+}
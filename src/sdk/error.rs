@@ -0,0 +1,149 @@
+use jni::errors::JniError;
+use jni::objects::{JObject, JString, JThrowable, JValue};
+use jni::JNIEnv;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// Errors returned by the SDK. Callers can branch on the kind of failure instead of matching messages,
+// and the type still converts into `anyhow::Error` for top-level functions.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Class {yarn} not found (looked up as {remapped})")]
+    ClassNotFound { yarn: String, remapped: String },
+    #[error("Method {class}.{method}{sig} not found")]
+    MethodNotFound { class: String, method: String, sig: String },
+    #[error("Field {class}.{field}:{sig} not found")]
+    FieldNotFound { class: String, field: String, sig: String },
+    #[error("Java exception {class}: {message}")]
+    JavaException { class: String, message: String, stacktrace: String },
+    #[error("Unexpected null object: {context}")]
+    NullObject { context: String },
+    #[error("Unexpected value: {context}")]
+    InvalidValue { context: String },
+    #[error("Object is not an instance of {class}")]
+    InvalidCast { class: String },
+    #[error("Mappings are not available")]
+    MappingUnavailable,
+    #[error("The current thread is not attached to the JVM")]
+    WrongThread,
+    #[error(transparent)]
+    Jni(jni::errors::Error),
+}
+
+impl From<jni::errors::Error> for Error {
+    fn from(error: jni::errors::Error) -> Self {
+        // The failing class is not known at this point, `with_exception` fills in the details
+        // from the pending Java exception where possible.
+        match error {
+            jni::errors::Error::MethodNotFound { name, sig } => Error::MethodNotFound {
+                class: String::from("<unknown>"),
+                method: name,
+                sig,
+            },
+            jni::errors::Error::FieldNotFound { name, sig } => Error::FieldNotFound {
+                class: String::from("<unknown>"),
+                field: name,
+                sig,
+            },
+            jni::errors::Error::JniCall(JniError::ThreadDetached) => Error::WrongThread,
+            error => Error::Jni(error),
+        }
+    }
+}
+
+impl Error {
+    pub fn null_object(context: impl Into<String>) -> Self {
+        Error::NullObject { context: context.into() }
+    }
+
+    // Builds a `ClassNotFound` error for a failed class lookup and clears the pending Java exception.
+    pub fn class_not_found(env: &mut JNIEnv, yarn: &str) -> Self {
+        let _ = env.exception_clear();
+        Error::ClassNotFound {
+            yarn: yarn.to_string(),
+            remapped: crate::sdk::mappings::class(yarn),
+        }
+    }
+
+    // Replaces the error with the details of the Java exception that caused it and clears the exception,
+    // so the thread can keep making JNI calls. Errors without a pending exception are returned as they are.
+    // Must be called wherever SDK errors are handled instead of propagated.
+    pub fn with_exception(self, env: &mut JNIEnv) -> Self {
+        match take_java_exception(env) {
+            Some(exception) => match self {
+                // The lookup errors are more useful than the `NoSuchMethodError` behind them.
+                Error::MethodNotFound { .. } | Error::FieldNotFound { .. } | Error::ClassNotFound { .. } => self,
+                _ => exception,
+            },
+            None => self,
+        }
+    }
+
+    pub fn is_java_exception(&self, class: &str) -> bool {
+        matches!(self, Error::JavaException { class: exception_class, .. } if exception_class == class)
+    }
+}
+
+// Takes the pending Java exception, if any, clears it and describes it as an `Error::JavaException`.
+pub fn take_java_exception(env: &mut JNIEnv) -> Option<Error> {
+    if !env.exception_check().unwrap_or(false) {
+        return None;
+    }
+
+    let throwable = env.exception_occurred().ok()?;
+    env.exception_clear().ok()?;
+
+    let error = describe_throwable(env, &throwable).unwrap_or_else(|_| {
+        // Describing the exception threw again, nothing more can be learned from it.
+        let _ = env.exception_clear();
+        Error::JavaException {
+            class: String::from("<unknown>"),
+            message: String::new(),
+            stacktrace: String::new(),
+        }
+    });
+    let _ = env.delete_local_ref(throwable);
+
+    Some(error)
+}
+
+fn describe_throwable(env: &mut JNIEnv, throwable: &JThrowable) -> jni::errors::Result<Error> {
+    let class = env.call_method(throwable, "getClass", "()Ljava/lang/Class;", &[])?.l()?;
+    let class_name = call_string_method(env, &class, "getName")?;
+    env.delete_local_ref(class)?;
+
+    let message = call_string_method(env, throwable, "getMessage")?;
+
+    // Print the stack trace into a string through a `StringWriter`.
+    let string_writer = env.new_object("java/io/StringWriter", "()V", &[])?;
+    let print_writer = env.new_object("java/io/PrintWriter", "(Ljava/io/Writer;)V", &[JValue::Object(&string_writer)])?;
+    env.call_method(throwable, "printStackTrace", "(Ljava/io/PrintWriter;)V", &[JValue::Object(&print_writer)])?;
+    let stacktrace = call_string_method(env, &string_writer, "toString")?;
+    env.delete_local_ref(print_writer)?;
+    env.delete_local_ref(string_writer)?;
+
+    // Obfuscated names are unreadable in logs, translate them back to yarn where mappings are loaded.
+    let (class_name, stacktrace) = match crate::sdk::mappings::installed() {
+        Some(mappings) => (mappings.unmap_binary_name(&class_name), mappings.unmap_stacktrace(&stacktrace)),
+        None => (class_name, stacktrace),
+    };
+
+    Ok(Error::JavaException {
+        class: class_name,
+        message,
+        stacktrace,
+    })
+}
+
+// Calls a `()Ljava/lang/String;` method, a null result becomes an empty string.
+fn call_string_method(env: &mut JNIEnv, object: &JObject, method: &str) -> jni::errors::Result<String> {
+    let string = env.call_method(object, method, "()Ljava/lang/String;", &[])?.l()?;
+    if string.is_null() {
+        return Ok(String::new());
+    }
+
+    let string = JString::from(string);
+    let rust_string: String = env.get_string(&string)?.into();
+    env.delete_local_ref(string)?;
+    Ok(rust_string)
+}
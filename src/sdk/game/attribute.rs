@@ -0,0 +1,101 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::{version, GameVersion, Result};
+
+// Entity attributes our modules read, named after their constants in `EntityAttributes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownAttribute {
+    MaxHealth,
+    MovementSpeed,
+    AttackDamage,
+    AttackSpeed,
+}
+
+impl KnownAttribute {
+    fn field_name(self) -> &'static str {
+        match self {
+            KnownAttribute::MaxHealth => "GENERIC_MAX_HEALTH",
+            KnownAttribute::MovementSpeed => "GENERIC_MOVEMENT_SPEED",
+            KnownAttribute::AttackDamage => "GENERIC_ATTACK_DAMAGE",
+            KnownAttribute::AttackSpeed => "GENERIC_ATTACK_SPEED",
+        }
+    }
+
+    // Resolves the attribute as the attribute container is keyed by it, a registry entry since 1.20.5
+    // and the attribute itself before.
+    pub fn to_java<'local>(self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::get_static_field(
+            env,
+            "net/minecraft/entity/attribute/EntityAttributes",
+            self.field_name(),
+            attribute_signature(),
+        )?.l()?)
+    }
+}
+
+fn attribute_signature() -> &'static str {
+    if version::at_least(GameVersion::V1_20_5) {
+        "Lnet/minecraft/registry/entry/RegistryEntry;"
+    } else {
+        "Lnet/minecraft/entity/attribute/EntityAttribute;"
+    }
+}
+
+// Wrapper for `net/minecraft/entity/attribute/AttributeContainer`, the attributes of a living entity.
+pub struct AttributeContainer<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> AttributeContainer<'a> {
+    pub const CLASS: &'static str = "net/minecraft/entity/attribute/AttributeContainer";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the instance of the attribute, `None` if the entity does not have it.
+    pub fn get_instance<'local>(&self, env: &mut JNIEnv<'local>, attribute: KnownAttribute) -> Result<Option<EntityAttributeInstance<'local>>> {
+        let java_attribute = attribute.to_java(env)?;
+        let instance = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "getCustomInstance",
+            &format!("({})Lnet/minecraft/entity/attribute/EntityAttributeInstance;", attribute_signature()),
+            &[JValue::Object(&java_attribute)],
+        );
+        env.delete_local_ref(java_attribute)?;
+
+        let instance = instance?.l()?;
+        if instance.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(EntityAttributeInstance::new(instance)))
+    }
+}
+
+// Wrapper for `net/minecraft/entity/attribute/EntityAttributeInstance`, one attribute of an entity
+// together with the modifiers applied to it.
+pub struct EntityAttributeInstance<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> EntityAttributeInstance<'a> {
+    pub const CLASS: &'static str = "net/minecraft/entity/attribute/EntityAttributeInstance";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // The value with every modifier applied, e.g. sprinting and status effects for the movement speed.
+    pub fn get_value(&self, env: &mut JNIEnv) -> Result<f64> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getValue", "()D", &[])?.d()?)
+    }
+
+    // The value before any modifier.
+    pub fn get_base_value(&self, env: &mut JNIEnv) -> Result<f64> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getBaseValue", "()D", &[])?.d()?)
+    }
+}
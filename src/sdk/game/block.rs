@@ -0,0 +1,257 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::direction::Direction;
+use super::vec3d;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+use crate::sdk::math::DVec3;
+
+// Position of a block, kept as plain integers on the Rust side and only turned into a
+// `net/minecraft/util/math/BlockPos` when a Java call needs one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BlockPos {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    // The block containing the point.
+    pub fn of_floored(pos: DVec3) -> Self {
+        Self::from_doubles(pos.x, pos.y, pos.z)
+    }
+
+    pub fn from_doubles(x: f64, y: f64, z: f64) -> Self {
+        Self::new(x.floor() as i32, y.floor() as i32, z.floor() as i32)
+    }
+
+    // The block containing the point of a `net/minecraft/util/math/Vec3d`.
+    pub fn from_vec3d(env: &mut JNIEnv, vec3d: &JObject) -> Result<Self> {
+        Ok(Self::of_floored(vec3d::to_dvec3(env, vec3d)?))
+    }
+
+    // Center of the block, where interactions with it usually aim.
+    pub fn to_center(self) -> DVec3 {
+        DVec3::new(self.x as f64 + 0.5, self.y as f64 + 0.5, self.z as f64 + 0.5)
+    }
+
+    pub fn up(self) -> Self {
+        Self::new(self.x, self.y + 1, self.z)
+    }
+
+    pub fn down(self) -> Self {
+        Self::new(self.x, self.y - 1, self.z)
+    }
+
+    pub fn offset(self, x: i32, y: i32, z: i32) -> Self {
+        Self::new(self.x + x, self.y + y, self.z + z)
+    }
+
+    // The block next to this one on the `direction` face.
+    pub fn neighbor(self, direction: Direction) -> Self {
+        let (x, y, z) = direction.vector();
+        self.offset(x, y, z)
+    }
+
+    pub fn distance_squared(self, other: BlockPos) -> i64 {
+        let (x, y, z) = ((self.x - other.x) as i64, (self.y - other.y) as i64, (self.z - other.z) as i64);
+        x * x + y * y + z * z
+    }
+
+    // Reads any `net/minecraft/util/math/BlockPos`, including mutable ones.
+    pub fn from_java(env: &mut JNIEnv, pos: &JObject) -> Result<Self> {
+        Ok(Self::new(
+            remap::call_method(env, pos, "net/minecraft/util/math/BlockPos", "getX", "()I", &[])?.i()?,
+            remap::call_method(env, pos, "net/minecraft/util/math/BlockPos", "getY", "()I", &[])?.i()?,
+            remap::call_method(env, pos, "net/minecraft/util/math/BlockPos", "getZ", "()I", &[])?.i()?,
+        ))
+    }
+
+    pub fn to_java<'local>(self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        remap::new_object(
+            env,
+            "net/minecraft/util/math/BlockPos",
+            "(III)V",
+            &[JValue::Int(self.x), JValue::Int(self.y), JValue::Int(self.z)],
+        )
+    }
+}
+
+// Blocks our modules search for, identified by their registry id like `KnownItem`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownBlock {
+    Chest,
+    TrappedChest,
+    EnderChest,
+    Barrel,
+    ShulkerBox,
+    Spawner,
+    CoalOre,
+    IronOre,
+    GoldOre,
+    RedstoneOre,
+    LapisOre,
+    DiamondOre,
+    EmeraldOre,
+    DeepslateIronOre,
+    DeepslateGoldOre,
+    DeepslateDiamondOre,
+    AncientDebris,
+}
+
+impl KnownBlock {
+    pub const ALL: [KnownBlock; 17] = [
+        KnownBlock::Chest,
+        KnownBlock::TrappedChest,
+        KnownBlock::EnderChest,
+        KnownBlock::Barrel,
+        KnownBlock::ShulkerBox,
+        KnownBlock::Spawner,
+        KnownBlock::CoalOre,
+        KnownBlock::IronOre,
+        KnownBlock::GoldOre,
+        KnownBlock::RedstoneOre,
+        KnownBlock::LapisOre,
+        KnownBlock::DiamondOre,
+        KnownBlock::EmeraldOre,
+        KnownBlock::DeepslateIronOre,
+        KnownBlock::DeepslateGoldOre,
+        KnownBlock::DeepslateDiamondOre,
+        KnownBlock::AncientDebris,
+    ];
+
+    // The registry identifier of the block.
+    pub fn id(self) -> &'static str {
+        match self {
+            KnownBlock::Chest => "minecraft:chest",
+            KnownBlock::TrappedChest => "minecraft:trapped_chest",
+            KnownBlock::EnderChest => "minecraft:ender_chest",
+            KnownBlock::Barrel => "minecraft:barrel",
+            KnownBlock::ShulkerBox => "minecraft:shulker_box",
+            KnownBlock::Spawner => "minecraft:spawner",
+            KnownBlock::CoalOre => "minecraft:coal_ore",
+            KnownBlock::IronOre => "minecraft:iron_ore",
+            KnownBlock::GoldOre => "minecraft:gold_ore",
+            KnownBlock::RedstoneOre => "minecraft:redstone_ore",
+            KnownBlock::LapisOre => "minecraft:lapis_ore",
+            KnownBlock::DiamondOre => "minecraft:diamond_ore",
+            KnownBlock::EmeraldOre => "minecraft:emerald_ore",
+            KnownBlock::DeepslateIronOre => "minecraft:deepslate_iron_ore",
+            KnownBlock::DeepslateGoldOre => "minecraft:deepslate_gold_ore",
+            KnownBlock::DeepslateDiamondOre => "minecraft:deepslate_diamond_ore",
+            KnownBlock::AncientDebris => "minecraft:ancient_debris",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|block| block.id() == id)
+    }
+
+    pub fn is_container(self) -> bool {
+        matches!(
+            self,
+            KnownBlock::Chest | KnownBlock::TrappedChest | KnownBlock::EnderChest | KnownBlock::Barrel | KnownBlock::ShulkerBox
+        )
+    }
+
+    pub fn is_ore(self) -> bool {
+        !self.is_container() && self != KnownBlock::Spawner
+    }
+}
+
+// Wrapper for `net/minecraft/block/BlockState`, a block together with its properties.
+pub struct BlockState<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> BlockState<'a> {
+    pub const CLASS: &'static str = "net/minecraft/block/BlockState";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the `net/minecraft/block/Block` this is a state of. Blocks are singletons, so two states
+    // are of the same block if their blocks are the same object.
+    pub fn get_block<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getBlock", "()Lnet/minecraft/block/Block;", &[])?.l()?)
+    }
+
+    pub fn is_air(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isAir", "()Z", &[])?.z()?)
+    }
+
+    // Whether placing a block here replaces this one, true for air, fluids and e.g. tall grass.
+    pub fn is_replaceable(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isReplaceable", "()Z", &[])?.z()?)
+    }
+
+    // Whether the block is solid enough to stand on and place against. It follows the game's own
+    // `isSolid`, which treats e.g. glass and leaves as solid but not flowers or snow layers.
+    pub fn is_solid(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isSolid", "()Z", &[])?.z()?)
+    }
+
+    // Base time to break the block, -1 for unbreakable blocks like bedrock. Some blocks depend on
+    // their position, which is why the world and position are needed.
+    pub fn get_hardness(&self, env: &mut JNIEnv, world: &JObject, pos: BlockPos) -> Result<f32> {
+        let block_pos = pos.to_java(env)?;
+        let hardness = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "getHardness",
+            "(Lnet/minecraft/world/BlockView;Lnet/minecraft/util/math/BlockPos;)F",
+            &[JValue::Object(world), JValue::Object(&block_pos)],
+        );
+        env.delete_local_ref(block_pos)?;
+        Ok(hardness?.f()?)
+    }
+
+    // Whether the block contains any fluid, including waterlogged blocks.
+    pub fn is_liquid(&self, env: &mut JNIEnv) -> Result<bool> {
+        let fluid_state = self.get_fluid_state(env)?;
+        let empty = remap::call_method(env, &fluid_state, "net/minecraft/fluid/FluidState", "isEmpty", "()Z", &[])?.z()?;
+        env.delete_local_ref(fluid_state)?;
+        Ok(!empty)
+    }
+
+    pub fn is_water(&self, env: &mut JNIEnv) -> Result<bool> {
+        let fluid_state = self.get_fluid_state(env)?;
+        let water_tag = remap::get_static_field(
+            env,
+            "net/minecraft/registry/tag/FluidTags",
+            "WATER",
+            "Lnet/minecraft/registry/tag/TagKey;",
+        )?.l()?;
+        let water = remap::call_method(
+            env,
+            &fluid_state,
+            "net/minecraft/fluid/FluidState",
+            "isIn",
+            "(Lnet/minecraft/registry/tag/TagKey;)Z",
+            &[JValue::Object(&water_tag)],
+        )?.z()?;
+        env.delete_local_ref(water_tag)?;
+        env.delete_local_ref(fluid_state)?;
+        Ok(water)
+    }
+
+    // Height of the fluid surface above the bottom of the block, from 0 for no fluid to 1 for a full
+    // block. A source block is slightly below 1.
+    pub fn get_fluid_height(&self, env: &mut JNIEnv) -> Result<f32> {
+        let fluid_state = self.get_fluid_state(env)?;
+        let height = remap::call_method(env, &fluid_state, "net/minecraft/fluid/FluidState", "getHeight", "()F", &[])?.f()?;
+        env.delete_local_ref(fluid_state)?;
+        Ok(height)
+    }
+
+    fn get_fluid_state<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getFluidState", "()Lnet/minecraft/fluid/FluidState;", &[])?.l()?)
+    }
+}
@@ -0,0 +1,93 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::client::MinecraftClient;
+use super::macros::java_class;
+use super::vec3d::to_dvec3;
+use crate::sdk::math::{DVec3, Projection};
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/client/render/GameRenderer`, which owns the camera and builds the
+// matrices the world is rendered with.
+pub struct GameRenderer<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> GameRenderer<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/render/GameRenderer";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_camera<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Camera<'local>> {
+        let camera = remap::call_method(env, &self.inner, Self::CLASS, "getCamera", "()Lnet/minecraft/client/render/Camera;", &[])?.l()?;
+        Ok(Camera::new(camera))
+    }
+
+    // The field of view the world is rendered with, including the sprinting and potion effects on top
+    // of the option.
+    pub fn get_fov(&self, env: &mut JNIEnv, camera: &Camera) -> Result<f64> {
+        let fov = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "getFov",
+            "(Lnet/minecraft/client/render/Camera;FZ)D",
+            &[JValue::Object(&camera.inner), JValue::Float(1.0), JValue::Bool(1)],
+        )?.d()?;
+        Ok(fov)
+    }
+
+    // Builds the perspective matrix for the given field of view, returned in column-major order.
+    pub fn get_basic_projection_matrix(&self, env: &mut JNIEnv, fov: f64) -> Result<[f32; 16]> {
+        let matrix = remap::call_method(env, &self.inner, Self::CLASS, "getBasicProjectionMatrix", "(D)Lorg/joml/Matrix4f;", &[JValue::Double(fov)])?.l()?;
+        let array = env.new_float_array(16)?;
+        let filled = env.call_method(&matrix, "get", "([F)[F", &[JValue::Object(&array)])?.l()?;
+        env.delete_local_ref(filled)?;
+        env.delete_local_ref(matrix)?;
+
+        let mut values = [0.0; 16];
+        env.get_float_array_region(&array, 0, &mut values)?;
+        env.delete_local_ref(array)?;
+        Ok(values)
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/client/render/Camera`. Its position and rotation are the ones of the
+    // last rendered frame, interpolated between ticks.
+    pub struct Camera("net/minecraft/client/render/Camera") {
+        method get_yaw: "getYaw" -> f32;
+        method get_pitch: "getPitch" -> f32;
+    }
+}
+
+impl Camera<'_> {
+    pub fn get_pos(&self, env: &mut JNIEnv) -> Result<DVec3> {
+        let pos = remap::call_method(env, &self.inner, Self::CLASS, "getPos", "()Lnet/minecraft/util/math/Vec3d;", &[])?.l()?;
+        let vec = to_dvec3(env, &pos)?;
+        env.delete_local_ref(pos)?;
+        Ok(vec)
+    }
+}
+
+// Captures the camera and matrices of the last rendered frame, for projecting world positions onto
+// the screen.
+pub fn capture_projection(env: &mut JNIEnv, client: &MinecraftClient) -> Result<Projection> {
+    env.with_local_frame(8, |env| -> Result<Projection> {
+        let game_renderer = client.get_game_renderer(env)?;
+        let camera = game_renderer.get_camera(env)?;
+        let fov = game_renderer.get_fov(env, &camera)?;
+        let window = client.get_window(env)?;
+        Ok(Projection {
+            camera_pos: camera.get_pos(env)?,
+            yaw: camera.get_yaw(env)?,
+            pitch: camera.get_pitch(env)?,
+            matrix: game_renderer.get_basic_projection_matrix(env, fov)?,
+            width: window.get_framebuffer_width(env)? as f64,
+            height: window.get_framebuffer_height(env)? as f64,
+        })
+    })
+}
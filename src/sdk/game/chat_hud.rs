@@ -0,0 +1,81 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::text::Text;
+use crate::sdk::jni::collections::JavaList;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/client/gui/hud/InGameHud`, everything drawn over the world while playing.
+pub struct InGameHud<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> InGameHud<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/gui/hud/InGameHud";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_chat_hud<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ChatHud<'local>> {
+        let chat_hud = remap::call_method(env, &self.inner, Self::CLASS, "getChatHud", "()Lnet/minecraft/client/gui/hud/ChatHud;", &[])?.l()?;
+        Ok(ChatHud::new(chat_hud))
+    }
+}
+
+// Wrapper for `net/minecraft/client/gui/hud/ChatHud`, the chat shown above the hotbar.
+pub struct ChatHud<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ChatHud<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/gui/hud/ChatHud";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns up to `limit` received lines, newest first. Each line is a new local reference.
+    pub fn get_messages<'local>(&self, env: &mut JNIEnv<'local>, limit: usize) -> Result<Vec<ChatHudLine<'local>>> {
+        let messages = JavaList::new(remap::get_field(env, &self.inner, Self::CLASS, "messages", "Ljava/util/List;")?.l()?);
+
+        let mut lines = Vec::new();
+        for index in 0..messages.len(env)?.min(limit) {
+            lines.push(ChatHudLine::new(messages.get(env, index)?));
+        }
+        env.delete_local_ref(messages.inner)?;
+
+        Ok(lines)
+    }
+
+    // Shows a message in the chat on this client only, nothing is sent to the server.
+    pub fn add_message(&self, env: &mut JNIEnv, message: &Text) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "addMessage", "(Lnet/minecraft/text/Text;)V", &[JValue::Object(&message.inner)])?;
+        Ok(())
+    }
+}
+
+// Wrapper for `net/minecraft/client/gui/hud/ChatHudLine`, one received chat message. A new line
+// object is created for every message, so identical messages are still different objects.
+pub struct ChatHudLine<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ChatHudLine<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/gui/hud/ChatHudLine";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_content<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Text<'local>> {
+        let content = remap::call_method(env, &self.inner, Self::CLASS, "content", "()Lnet/minecraft/text/Text;", &[])?.l()?;
+        Ok(Text::new(content))
+    }
+
+    // The client tick the message was received in.
+    pub fn get_creation_tick(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "creationTick", "()I", &[])?.i()?)
+    }
+}
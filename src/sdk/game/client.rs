@@ -0,0 +1,240 @@
+use jni::objects::{JObject, JString};
+use jni::JNIEnv;
+
+use super::block::BlockPos;
+use super::camera::GameRenderer;
+use super::chat_hud::{ChatHud, InGameHud};
+use super::direction::Direction;
+use super::entity::Entity;
+use super::interaction_manager::ClientPlayerInteractionManager;
+use super::network::ClientPlayNetworkHandler;
+use super::options::GameOptions;
+use super::player::ClientPlayerEntity;
+use super::player_list::GameMode;
+use super::session::Session;
+use super::window::Window;
+use super::world::ClientWorld;
+use crate::sdk::jni::remap;
+use crate::sdk::{Error, Result};
+
+// Wrapper for `net/minecraft/client/MinecraftClient`, the root of everything client-side.
+pub struct MinecraftClient<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> MinecraftClient<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/MinecraftClient";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Reads the static `instance` field holding the running client.
+    pub fn get_instance(env: &mut JNIEnv<'a>) -> Result<Self> {
+        let instance = remap::get_static_field(
+            env,
+            "net/minecraft/client/MinecraftClient",
+            "instance",
+            "Lnet/minecraft/client/MinecraftClient;",
+        )?.l()?;
+        if instance.is_null() {
+            return Err(Error::null_object("MinecraftClient.instance"));
+        }
+        Ok(Self::new(instance))
+    }
+
+    // Name of the running game version, e.g. `1.21.1`.
+    pub fn get_game_version(env: &mut JNIEnv) -> Result<String> {
+        let game_version = remap::call_static_method(env, "net/minecraft/SharedConstants", "getGameVersion", "()Lnet/minecraft/GameVersion;", &[])?.l()?;
+        let name = JString::from(remap::call_method(env, &game_version, "net/minecraft/GameVersion", "getName", "()Ljava/lang/String;", &[])?.l()?);
+        env.delete_local_ref(game_version)?;
+
+        let name_string: String = env.get_string(&name)?.into();
+        env.delete_local_ref(name)?;
+        Ok(name_string)
+    }
+
+    // Returns the local player, which is only present while a world is loaded.
+    pub fn get_player<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<ClientPlayerEntity<'local>>> {
+        let player = remap::get_field(env, &self.inner, Self::CLASS, "player", "Lnet/minecraft/client/network/ClientPlayerEntity;")?.l()?;
+        if player.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(ClientPlayerEntity::new(player)))
+    }
+
+    // Returns the world the client is in, which is only present while playing.
+    pub fn get_world<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<ClientWorld<'local>>> {
+        let world = remap::get_field(env, &self.inner, Self::CLASS, "world", "Lnet/minecraft/client/world/ClientWorld;")?.l()?;
+        if world.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(ClientWorld::new(world)))
+    }
+
+    // Returns the connection to the server, which is only present while playing.
+    pub fn get_network_handler<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<ClientPlayNetworkHandler<'local>>> {
+        let network_handler = remap::call_method(env, &self.inner, Self::CLASS, "getNetworkHandler", "()Lnet/minecraft/client/network/ClientPlayNetworkHandler;", &[])?.l()?;
+        if network_handler.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(ClientPlayNetworkHandler::new(network_handler)))
+    }
+
+    pub fn get_options<'local>(&self, env: &mut JNIEnv<'local>) -> Result<GameOptions<'local>> {
+        let options = remap::get_field(env, &self.inner, Self::CLASS, "options", "Lnet/minecraft/client/option/GameOptions;")?.l()?;
+        Ok(GameOptions::new(options))
+    }
+
+    pub fn get_window<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Window<'local>> {
+        let window = remap::call_method(env, &self.inner, Self::CLASS, "getWindow", "()Lnet/minecraft/client/util/Window;", &[])?.l()?;
+        Ok(Window::new(window))
+    }
+
+    pub fn get_game_renderer<'local>(&self, env: &mut JNIEnv<'local>) -> Result<GameRenderer<'local>> {
+        let game_renderer = remap::get_field(env, &self.inner, Self::CLASS, "gameRenderer", "Lnet/minecraft/client/render/GameRenderer;")?.l()?;
+        Ok(GameRenderer::new(game_renderer))
+    }
+
+    // Returns the block under the crosshair and the face looked at, `None` when aiming at an entity
+    // or at nothing within reach.
+    pub fn get_crosshair_block(&self, env: &mut JNIEnv) -> Result<Option<(BlockPos, Direction)>> {
+        let hit_result = remap::get_field(env, &self.inner, Self::CLASS, "crosshairTarget", "Lnet/minecraft/util/hit/HitResult;")?.l()?;
+        if hit_result.is_null() || !remap::is_instance_of(env, &hit_result, "net/minecraft/util/hit/BlockHitResult")? {
+            env.delete_local_ref(hit_result)?;
+            return Ok(None);
+        }
+
+        // A miss is also a `BlockHitResult`, only with the `MISS` type.
+        let hit_type = remap::call_method(env, &hit_result, "net/minecraft/util/hit/HitResult", "getType", "()Lnet/minecraft/util/hit/HitResult$Type;", &[])?.l()?;
+        let is_block = env.call_method(&hit_type, "ordinal", "()I", &[])?.i()? == 1;
+        env.delete_local_ref(hit_type)?;
+        if !is_block {
+            env.delete_local_ref(hit_result)?;
+            return Ok(None);
+        }
+
+        let java_pos = remap::call_method(env, &hit_result, "net/minecraft/util/hit/BlockHitResult", "getBlockPos", "()Lnet/minecraft/util/math/BlockPos;", &[])?.l()?;
+        let pos = BlockPos::from_java(env, &java_pos)?;
+        env.delete_local_ref(java_pos)?;
+
+        let java_side = remap::call_method(env, &hit_result, "net/minecraft/util/hit/BlockHitResult", "getSide", "()Lnet/minecraft/util/math/Direction;", &[])?.l()?;
+        let side = Direction::from_java(env, &java_side)?;
+        env.delete_local_ref(java_side)?;
+        env.delete_local_ref(hit_result)?;
+
+        Ok(Some((pos, side)))
+    }
+
+    // Returns the entity under the crosshair, `None` when aiming at a block or at nothing within reach.
+    pub fn get_crosshair_entity<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<Entity<'local>>> {
+        let hit_result = remap::get_field(env, &self.inner, Self::CLASS, "crosshairTarget", "Lnet/minecraft/util/hit/HitResult;")?.l()?;
+        if hit_result.is_null() || !remap::is_instance_of(env, &hit_result, "net/minecraft/util/hit/EntityHitResult")? {
+            env.delete_local_ref(hit_result)?;
+            return Ok(None);
+        }
+
+        let entity = remap::call_method(env, &hit_result, "net/minecraft/util/hit/EntityHitResult", "getEntity", "()Lnet/minecraft/entity/Entity;", &[])?.l()?;
+        env.delete_local_ref(hit_result)?;
+        Ok(Some(Entity::new(entity)))
+    }
+
+    pub fn get_session<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Session<'local>> {
+        let session = remap::call_method(env, &self.inner, Self::CLASS, "getSession", "()Lnet/minecraft/client/session/Session;", &[])?.l()?;
+        Ok(Session::new(session))
+    }
+
+    pub fn get_in_game_hud<'local>(&self, env: &mut JNIEnv<'local>) -> Result<InGameHud<'local>> {
+        let in_game_hud = remap::get_field(env, &self.inner, Self::CLASS, "inGameHud", "Lnet/minecraft/client/gui/hud/InGameHud;")?.l()?;
+        Ok(InGameHud::new(in_game_hud))
+    }
+
+    pub fn get_chat_hud<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ChatHud<'local>> {
+        let in_game_hud = self.get_in_game_hud(env)?;
+        let chat_hud = in_game_hud.get_chat_hud(env)?;
+        env.delete_local_ref(in_game_hud.inner)?;
+        Ok(chat_hud)
+    }
+
+    // Frames rendered in the last full second, as the debug screen shows.
+    pub fn get_current_fps(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getCurrentFps", "()I", &[])?.i()?)
+    }
+
+    pub fn is_window_focused(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isWindowFocused", "()Z", &[])?.z()?)
+    }
+
+    // Returns whether the integrated server is paused. This is only ever true in singleplayer.
+    pub fn is_paused(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isPaused", "()Z", &[])?.z()?)
+    }
+
+    // Returns whether the player is in a singleplayer world, including one opened to LAN.
+    pub fn is_in_singleplayer(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isInSingleplayer", "()Z", &[])?.z()?)
+    }
+
+    // Returns whether the integrated server is running, which stays true shortly after leaving a
+    // singleplayer world while it shuts down.
+    pub fn is_integrated_server_running(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isIntegratedServerRunning", "()Z", &[])?.z()?)
+    }
+
+    // Returns whether any screen (inventory, chat, menus) is open on top of the world.
+    pub fn is_screen_open(&self, env: &mut JNIEnv) -> Result<bool> {
+        let screen = remap::get_field(env, &self.inner, Self::CLASS, "currentScreen", "Lnet/minecraft/client/gui/screen/Screen;")?.l()?;
+        let open = !screen.is_null();
+        env.delete_local_ref(screen)?;
+        Ok(open)
+    }
+
+    // Returns whether the escape menu is open. Unlike `is_paused` this also works in multiplayer,
+    // where the menu opens but the game keeps running.
+    pub fn is_game_menu_open(&self, env: &mut JNIEnv) -> Result<bool> {
+        let screen = remap::get_field(env, &self.inner, Self::CLASS, "currentScreen", "Lnet/minecraft/client/gui/screen/Screen;")?.l()?;
+        let open = !screen.is_null() && remap::is_instance_of(env, &screen, "net/minecraft/client/gui/screen/GameMenuScreen")?;
+        env.delete_local_ref(screen)?;
+        Ok(open)
+    }
+
+    // Address of the multiplayer server, `None` in singleplayer and the title screen.
+    pub fn get_server_address(&self, env: &mut JNIEnv) -> Result<Option<String>> {
+        let server_info = remap::call_method(env, &self.inner, Self::CLASS, "getCurrentServerEntry", "()Lnet/minecraft/client/network/ServerInfo;", &[])?.l()?;
+        if server_info.is_null() {
+            return Ok(None);
+        }
+
+        let address = JString::from(remap::get_field(env, &server_info, "net/minecraft/client/network/ServerInfo", "address", "Ljava/lang/String;")?.l()?);
+        let address_string: String = env.get_string(&address)?.into();
+        env.delete_local_ref(address)?;
+        env.delete_local_ref(server_info)?;
+        Ok(Some(address_string))
+    }
+
+    // Game mode of the local player as known to the interaction manager, `None` outside a world.
+    pub fn get_game_mode(&self, env: &mut JNIEnv) -> Result<Option<GameMode>> {
+        let Some(interaction_manager) = self.get_interaction_manager(env)? else {
+            return Ok(None);
+        };
+
+        let game_mode = interaction_manager.get_current_game_mode(env);
+        env.delete_local_ref(interaction_manager.inner)?;
+        game_mode
+    }
+
+    // Returns the interaction manager, which is only present while a world is loaded.
+    pub fn get_interaction_manager<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<ClientPlayerInteractionManager<'local>>> {
+        let interaction_manager = remap::get_field(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "interactionManager",
+            "Lnet/minecraft/client/network/ClientPlayerInteractionManager;",
+        )?.l()?;
+        if interaction_manager.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(ClientPlayerInteractionManager::new(interaction_manager)))
+    }
+}
@@ -0,0 +1,72 @@
+use jni::objects::JObject;
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::{Error, Result};
+
+// Mirror of the `net/minecraft/util/math/Direction` enum, the six block faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Down,
+    Up,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 6] = [Direction::Down, Direction::Up, Direction::North, Direction::South, Direction::West, Direction::East];
+
+    // Unit offset towards the face, north being towards negative z.
+    pub fn vector(self) -> (i32, i32, i32) {
+        match self {
+            Direction::Down => (0, -1, 0),
+            Direction::Up => (0, 1, 0),
+            Direction::North => (0, 0, -1),
+            Direction::South => (0, 0, 1),
+            Direction::West => (-1, 0, 0),
+            Direction::East => (1, 0, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Down => Direction::Up,
+            Direction::Up => Direction::Down,
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::East => Direction::West,
+        }
+    }
+
+    fn java_name(self) -> &'static str {
+        match self {
+            Direction::Down => "DOWN",
+            Direction::Up => "UP",
+            Direction::North => "NORTH",
+            Direction::South => "SOUTH",
+            Direction::West => "WEST",
+            Direction::East => "EAST",
+        }
+    }
+
+    // Translates a Java `Direction` constant into the Rust enum using its ordinal.
+    pub fn from_java(env: &mut JNIEnv, direction: &JObject) -> Result<Self> {
+        match env.call_method(direction, "ordinal", "()I", &[])?.i()? {
+            0 => Ok(Direction::Down),
+            1 => Ok(Direction::Up),
+            2 => Ok(Direction::North),
+            3 => Ok(Direction::South),
+            4 => Ok(Direction::West),
+            5 => Ok(Direction::East),
+            ordinal => Err(Error::InvalidValue { context: format!("unknown Direction ordinal {}", ordinal) }),
+        }
+    }
+
+    // Resolves the Java `Direction` constant for this direction.
+    pub fn to_java<'local>(self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::get_static_field(env, "net/minecraft/util/math/Direction", self.java_name(), "Lnet/minecraft/util/math/Direction;")?.l()?)
+    }
+}
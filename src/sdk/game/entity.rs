@@ -0,0 +1,161 @@
+use jni::objects::{JClass, JObject, JString, JValue};
+use jni::JNIEnv;
+
+use super::macros::{call_primitive, get_primitive_field};
+use super::vec3d;
+use crate::sdk::jni::cache;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+use crate::sdk::math::{DVec3, RustBox};
+
+// Wrapper for `net/minecraft/entity/Entity`, the base of every entity in the world.
+pub struct Entity<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Entity<'a> {
+    pub const CLASS: &'static str = "net/minecraft/entity/Entity";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the network ID of the entity, which is unique per world.
+    pub fn get_id(&self, env: &mut JNIEnv) -> Result<i32> {
+        call_primitive(env, &self.inner, Self::CLASS, "getId")
+    }
+
+    // Whether the entity has been removed from the world (killed, despawned or unloaded).
+    // Cached references to removed entities must be dropped.
+    pub fn is_removed(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isRemoved", "()Z", &[])?.z()?)
+    }
+
+    // Whether the entity is a player, the local one or another.
+    pub fn is_player(&self, env: &mut JNIEnv) -> Result<bool> {
+        let class = cache::class(env, "net/minecraft/entity/player/PlayerEntity")?;
+        Ok(env.is_instance_of(&self.inner, <&JClass>::from(class.as_obj()))?)
+    }
+
+    pub fn get_uuid_string(&self, env: &mut JNIEnv) -> Result<String> {
+        let uuid = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "getUuidAsString", "()Ljava/lang/String;", &[])?.l()?);
+        let uuid_string: String = env.get_string(&uuid)?.into();
+        env.delete_local_ref(uuid)?;
+        Ok(uuid_string)
+    }
+
+    // Returns the display name as plain text, for players this is their profile name.
+    pub fn get_name(&self, env: &mut JNIEnv) -> Result<String> {
+        let text = remap::call_method(env, &self.inner, Self::CLASS, "getName", "()Lnet/minecraft/text/Text;", &[])?.l()?;
+        let name = JString::from(remap::call_method(env, &text, "net/minecraft/text/Text", "getString", "()Ljava/lang/String;", &[])?.l()?);
+        env.delete_local_ref(text)?;
+
+        let name_string: String = env.get_string(&name)?.into();
+        env.delete_local_ref(name)?;
+        Ok(name_string)
+    }
+
+    pub fn get_pos(&self, env: &mut JNIEnv) -> Result<DVec3> {
+        let pos = remap::call_method(env, &self.inner, Self::CLASS, "getPos", "()Lnet/minecraft/util/math/Vec3d;", &[])?.l()?;
+        let vec = vec3d::to_dvec3(env, &pos)?;
+        env.delete_local_ref(pos)?;
+        Ok(vec)
+    }
+
+    // Position of the eyes, which is where attacks and rotations originate from.
+    pub fn get_eye_pos(&self, env: &mut JNIEnv) -> Result<DVec3> {
+        let eye_pos = remap::call_method(env, &self.inner, Self::CLASS, "getEyePos", "()Lnet/minecraft/util/math/Vec3d;", &[])?.l()?;
+        let vec = vec3d::to_dvec3(env, &eye_pos)?;
+        env.delete_local_ref(eye_pos)?;
+        Ok(vec)
+    }
+
+    // Hitbox of the entity in world coordinates.
+    pub fn get_bounding_box(&self, env: &mut JNIEnv) -> Result<RustBox> {
+        let bounding_box = remap::call_method(env, &self.inner, Self::CLASS, "getBoundingBox", "()Lnet/minecraft/util/math/Box;", &[])?.l()?;
+        let mut corners = [0.0; 6];
+        for (corner, field) in corners.iter_mut().zip(["minX", "minY", "minZ", "maxX", "maxY", "maxZ"]) {
+            *corner = remap::get_field(env, &bounding_box, "net/minecraft/util/math/Box", field, "D")?.d()?;
+        }
+        env.delete_local_ref(bounding_box)?;
+
+        let [min_x, min_y, min_z, max_x, max_y, max_z] = corners;
+        Ok(RustBox::new(DVec3::new(min_x, min_y, min_z), DVec3::new(max_x, max_y, max_z)))
+    }
+
+    pub fn get_velocity(&self, env: &mut JNIEnv) -> Result<DVec3> {
+        let velocity = remap::call_method(env, &self.inner, Self::CLASS, "getVelocity", "()Lnet/minecraft/util/math/Vec3d;", &[])?.l()?;
+        let vec = vec3d::to_dvec3(env, &velocity)?;
+        env.delete_local_ref(velocity)?;
+        Ok(vec)
+    }
+
+    pub fn set_velocity(&self, env: &mut JNIEnv, velocity: DVec3) -> Result<()> {
+        remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "setVelocity",
+            "(DDD)V",
+            &[JValue::Double(velocity.x), JValue::Double(velocity.y), JValue::Double(velocity.z)],
+        )?;
+        Ok(())
+    }
+
+    // Ticks the entity has existed for, the game counts it up once per tick.
+    pub fn get_age(&self, env: &mut JNIEnv) -> Result<i32> {
+        get_primitive_field(env, &self.inner, Self::CLASS, "age")
+    }
+
+    // Blocks fallen since the entity last stood on the ground, reset on landing and in water.
+    pub fn get_fall_distance(&self, env: &mut JNIEnv) -> Result<f32> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "fallDistance", "F")?.f()?)
+    }
+
+    pub fn is_on_ground(&self, env: &mut JNIEnv) -> Result<bool> {
+        call_primitive(env, &self.inner, Self::CLASS, "isOnGround")
+    }
+
+    pub fn get_yaw(&self, env: &mut JNIEnv) -> Result<f32> {
+        call_primitive(env, &self.inner, Self::CLASS, "getYaw")
+    }
+
+    pub fn get_pitch(&self, env: &mut JNIEnv) -> Result<f32> {
+        call_primitive(env, &self.inner, Self::CLASS, "getPitch")
+    }
+
+    pub fn set_yaw(&self, env: &mut JNIEnv, yaw: f32) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "setYaw", "(F)V", &[JValue::Float(yaw)])?;
+        Ok(())
+    }
+
+    pub fn set_pitch(&self, env: &mut JNIEnv, pitch: f32) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "setPitch", "(F)V", &[JValue::Float(pitch)])?;
+        Ok(())
+    }
+
+    pub fn is_sneaking(&self, env: &mut JNIEnv) -> Result<bool> {
+        call_primitive(env, &self.inner, Self::CLASS, "isSneaking")
+    }
+
+    pub fn is_sprinting(&self, env: &mut JNIEnv) -> Result<bool> {
+        call_primitive(env, &self.inner, Self::CLASS, "isSprinting")
+    }
+
+    // Sets the sprinting flag. For the local player the server learns about it with the next movement
+    // packet, and the game resets it when the player stops moving forward.
+    pub fn set_sprinting(&self, env: &mut JNIEnv, sprinting: bool) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "setSprinting", "(Z)V", &[JValue::Bool(sprinting.into())])?;
+        Ok(())
+    }
+
+    // Whether the entity is riding something, e.g. a boat or a horse.
+    pub fn has_vehicle(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "hasVehicle", "()Z", &[])?.z()?)
+    }
+
+    // Whether the entity ran into a wall during its last movement.
+    pub fn is_horizontal_collision(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "horizontalCollision", "Z")?.z()?)
+    }
+}
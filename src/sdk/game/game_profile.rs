@@ -0,0 +1,37 @@
+use jni::objects::{JObject, JString};
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `com/mojang/authlib/GameProfile`, the account behind a player. Authlib is a library
+// and not part of the obfuscated game, so its names are the same in every environment.
+pub struct GameProfile<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> GameProfile<'a> {
+    pub const CLASS: &'static str = "com/mojang/authlib/GameProfile";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_name(&self, env: &mut JNIEnv) -> Result<String> {
+        let name = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "getName", "()Ljava/lang/String;", &[])?.l()?);
+        let name_string: String = env.get_string(&name)?.into();
+        env.delete_local_ref(name)?;
+        Ok(name_string)
+    }
+
+    // Returns the account UUID in its hyphenated form.
+    pub fn get_id_string(&self, env: &mut JNIEnv) -> Result<String> {
+        let id = remap::call_method(env, &self.inner, Self::CLASS, "getId", "()Ljava/util/UUID;", &[])?.l()?;
+        let id_string = JString::from(env.call_method(&id, "toString", "()Ljava/lang/String;", &[])?.l()?);
+        env.delete_local_ref(id)?;
+
+        let rust_string: String = env.get_string(&id_string)?.into();
+        env.delete_local_ref(id_string)?;
+        Ok(rust_string)
+    }
+}
@@ -0,0 +1,35 @@
+use jni::objects::JObject;
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::{Error, Result};
+
+// Mirror of the `net/minecraft/util/Hand` enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    MainHand,
+    OffHand,
+}
+
+impl Hand {
+    fn java_name(self) -> &'static str {
+        match self {
+            Hand::MainHand => "MAIN_HAND",
+            Hand::OffHand => "OFF_HAND",
+        }
+    }
+
+    // Translates a Java `Hand` constant into the Rust enum using its ordinal.
+    pub fn from_java(env: &mut JNIEnv, hand: &JObject) -> Result<Self> {
+        match env.call_method(hand, "ordinal", "()I", &[])?.i()? {
+            0 => Ok(Hand::MainHand),
+            1 => Ok(Hand::OffHand),
+            ordinal => Err(Error::InvalidValue { context: format!("unknown Hand ordinal {}", ordinal) }),
+        }
+    }
+
+    // Resolves the Java `Hand` constant for this hand.
+    pub fn to_java<'local>(self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::get_static_field(env, "net/minecraft/util/Hand", self.java_name(), "Lnet/minecraft/util/Hand;")?.l()?)
+    }
+}
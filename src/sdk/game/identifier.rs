@@ -0,0 +1,36 @@
+use jni::objects::{JObject, JString, JValue};
+use jni::JNIEnv;
+
+use crate::sdk::jni::string::ToJava;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/util/Identifier`, a `namespace:path` pair naming registry entries,
+// textures, sounds and more.
+pub struct Identifier<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Identifier<'a> {
+    pub const CLASS: &'static str = "net/minecraft/util/Identifier";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Creates an identifier from a `namespace:path` string, the namespace defaults to `minecraft`.
+    // Fails with the game's `InvalidIdentifierException` for characters that are not allowed.
+    pub fn from_string(env: &mut JNIEnv<'a>, id: &str) -> Result<Self> {
+        let id_string = id.to_java(env)?;
+        let identifier = remap::new_object(env, "net/minecraft/util/Identifier", "(Ljava/lang/String;)V", &[JValue::Object(&id_string)])?;
+        Ok(Self::new(identifier))
+    }
+
+    // Returns the identifier as `namespace:path`.
+    pub fn to_string(&self, env: &mut JNIEnv) -> Result<String> {
+        let id = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "toString", "()Ljava/lang/String;", &[])?.l()?);
+        let id_string: String = env.get_string(&id)?.into();
+        env.delete_local_ref(id)?;
+        Ok(id_string)
+    }
+}
@@ -0,0 +1,185 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::block::BlockPos;
+use super::direction::Direction;
+use super::entity::Entity;
+use super::hand::Hand;
+use super::player::ClientPlayerEntity;
+use super::player_list::GameMode;
+use super::screen_handler::SlotActionType;
+use super::vec3d;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+use crate::sdk::math::DVec3;
+
+// Wrapper for `net/minecraft/client/network/ClientPlayerInteractionManager`, which performs the
+// player's attacks, block breaking and item use the same way mouse input does.
+pub struct ClientPlayerInteractionManager<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ClientPlayerInteractionManager<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/network/ClientPlayerInteractionManager";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Uses the item in `hand` like a right click into the air, e.g. throws a pearl or starts eating.
+    // Returns whether the use was accepted.
+    pub fn interact_item(&self, env: &mut JNIEnv, player: &ClientPlayerEntity, hand: Hand) -> Result<bool> {
+        let java_hand = hand.to_java(env)?;
+        let action_result = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "interactItem",
+            "(Lnet/minecraft/entity/player/PlayerEntity;Lnet/minecraft/util/Hand;)Lnet/minecraft/util/ActionResult;",
+            &[JValue::Object(&player.inner), JValue::Object(&java_hand)],
+        )?.l()?;
+        env.delete_local_ref(java_hand)?;
+        is_accepted(env, action_result)
+    }
+
+    // Attacks the entity like a left click on it: sends the attack to the server and applies the hit
+    // locally. The swing animation is not part of it, see `LivingEntity::swing_hand`.
+    pub fn attack_entity(&self, env: &mut JNIEnv, player: &ClientPlayerEntity, target: &Entity) -> Result<()> {
+        remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "attackEntity",
+            "(Lnet/minecraft/entity/player/PlayerEntity;Lnet/minecraft/entity/Entity;)V",
+            &[JValue::Object(&player.inner), JValue::Object(&target.inner)],
+        )?;
+        Ok(())
+    }
+
+    // Interacts with the entity like a right click on it, e.g. trading with a villager or mounting a
+    // boat. Returns whether the interaction was accepted.
+    pub fn interact_entity(&self, env: &mut JNIEnv, player: &ClientPlayerEntity, target: &Entity, hand: Hand) -> Result<bool> {
+        let java_hand = hand.to_java(env)?;
+        let action_result = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "interactEntity",
+            "(Lnet/minecraft/entity/player/PlayerEntity;Lnet/minecraft/entity/Entity;Lnet/minecraft/util/Hand;)Lnet/minecraft/util/ActionResult;",
+            &[JValue::Object(&player.inner), JValue::Object(&target.inner), JValue::Object(&java_hand)],
+        );
+        env.delete_local_ref(java_hand)?;
+        is_accepted(env, action_result?.l()?)
+    }
+
+    // Right clicks the `side` face of the block at `pos` at the point `hit`, which places the held
+    // block against it or opens it. Returns whether the interaction was accepted.
+    pub fn interact_block(&self, env: &mut JNIEnv, player: &ClientPlayerEntity, hand: Hand, pos: BlockPos, side: Direction, hit: DVec3) -> Result<bool> {
+        env.with_local_frame(8, |env| -> Result<bool> {
+            let java_hand = hand.to_java(env)?;
+            let java_hit = vec3d::from_dvec3(env, hit)?;
+            let java_side = side.to_java(env)?;
+            let java_pos = pos.to_java(env)?;
+            let hit_result = remap::new_object(
+                env,
+                "net/minecraft/util/hit/BlockHitResult",
+                "(Lnet/minecraft/util/math/Vec3d;Lnet/minecraft/util/math/Direction;Lnet/minecraft/util/math/BlockPos;Z)V",
+                &[JValue::Object(&java_hit), JValue::Object(&java_side), JValue::Object(&java_pos), JValue::Bool(false.into())],
+            )?;
+
+            let action_result = remap::call_method(
+                env,
+                &self.inner,
+                Self::CLASS,
+                "interactBlock",
+                "(Lnet/minecraft/client/network/ClientPlayerEntity;Lnet/minecraft/util/Hand;Lnet/minecraft/util/hit/BlockHitResult;)Lnet/minecraft/util/ActionResult;",
+                &[JValue::Object(&player.inner), JValue::Object(&java_hand), JValue::Object(&hit_result)],
+            )?.l()?;
+            is_accepted(env, action_result)
+        })
+    }
+
+    // Starts breaking the block at `pos` from the given face, like the first tick of holding the
+    // attack key on it. Blocks that break instantly (creative mode, grass) are broken right away.
+    pub fn attack_block(&self, env: &mut JNIEnv, pos: BlockPos, side: Direction) -> Result<bool> {
+        self.call_block_method(env, "attackBlock", pos, side)
+    }
+
+    // Continues breaking the block at `pos`, must be called every tick until the block is gone.
+    pub fn update_block_breaking_progress(&self, env: &mut JNIEnv, pos: BlockPos, side: Direction) -> Result<bool> {
+        self.call_block_method(env, "updateBlockBreakingProgress", pos, side)
+    }
+
+    pub fn cancel_block_breaking(&self, env: &mut JNIEnv) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "cancelBlockBreaking", "()V", &[])?;
+        Ok(())
+    }
+
+    // Clicks a slot of the screen handler with the given sync id the way the game does for mouse and
+    // key input, the click is applied locally and sent to the server.
+    pub fn click_slot(&self, env: &mut JNIEnv, sync_id: i32, slot_id: i32, button: i32, action: SlotActionType, player: &ClientPlayerEntity) -> Result<()> {
+        let java_action = action.to_java(env)?;
+        let result = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "clickSlot",
+            "(IIILnet/minecraft/screen/slot/SlotActionType;Lnet/minecraft/entity/player/PlayerEntity;)V",
+            &[
+                JValue::Int(sync_id),
+                JValue::Int(slot_id),
+                JValue::Int(button),
+                JValue::Object(&java_action),
+                JValue::Object(&player.inner),
+            ],
+        );
+        env.delete_local_ref(java_action)?;
+        result?;
+        Ok(())
+    }
+
+    // Ticks left before the next block can be damaged. The game sets it to 5 after a block breaks
+    // and counts it down every tick.
+    pub fn get_block_breaking_cooldown(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "blockBreakingCooldown", "I")?.i()?)
+    }
+
+    pub fn set_block_breaking_cooldown(&self, env: &mut JNIEnv, ticks: i32) -> Result<()> {
+        remap::set_field(env, &self.inner, Self::CLASS, "blockBreakingCooldown", "I", JValue::Int(ticks))?;
+        Ok(())
+    }
+
+    fn call_block_method(&self, env: &mut JNIEnv, method: &str, pos: BlockPos, side: Direction) -> Result<bool> {
+        let java_pos = pos.to_java(env)?;
+        let java_side = side.to_java(env)?;
+        let result = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            method,
+            "(Lnet/minecraft/util/math/BlockPos;Lnet/minecraft/util/math/Direction;)Z",
+            &[JValue::Object(&java_pos), JValue::Object(&java_side)],
+        );
+        env.delete_local_ref(java_side)?;
+        env.delete_local_ref(java_pos)?;
+        Ok(result?.z()?)
+    }
+
+    pub fn get_current_game_mode(&self, env: &mut JNIEnv) -> Result<Option<GameMode>> {
+        let game_mode = remap::call_method(env, &self.inner, Self::CLASS, "getCurrentGameMode", "()Lnet/minecraft/world/GameMode;", &[])?.l()?;
+        if game_mode.is_null() {
+            return Ok(None);
+        }
+
+        let id = remap::call_method(env, &game_mode, "net/minecraft/world/GameMode", "getId", "()I", &[])?.i()?;
+        env.delete_local_ref(game_mode)?;
+        Ok(GameMode::from_id(id))
+    }
+}
+
+// Reads whether a `net/minecraft/util/ActionResult` was accepted and deletes it.
+fn is_accepted(env: &mut JNIEnv, action_result: JObject) -> Result<bool> {
+    let accepted = remap::call_method(env, &action_result, "net/minecraft/util/ActionResult", "isAccepted", "()Z", &[])?.z()?;
+    env.delete_local_ref(action_result)?;
+    Ok(accepted)
+}
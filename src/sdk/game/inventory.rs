@@ -0,0 +1,53 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::item::ItemStack;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Inventory indices of the hotbar (0-8) and the main inventory including the hotbar (0-35). Armor
+// follows at 36-39 and the offhand at 40. These are not the slot ids of the screen handler.
+pub const HOTBAR_SIZE: i32 = 9;
+pub const MAIN_SIZE: i32 = 36;
+pub const OFFHAND_INDEX: i32 = 40;
+
+// Wrapper for `net/minecraft/entity/player/PlayerInventory`.
+pub struct PlayerInventory<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> PlayerInventory<'a> {
+    pub const CLASS: &'static str = "net/minecraft/entity/player/PlayerInventory";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the selected hotbar slot (0-8).
+    pub fn get_selected_slot(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "selectedSlot", "I")?.i()?)
+    }
+
+    // Selects a hotbar slot on the client only, the server is not informed.
+    pub fn set_selected_slot(&self, env: &mut JNIEnv, slot: i32) -> Result<()> {
+        remap::set_field(env, &self.inner, Self::CLASS, "selectedSlot", "I", JValue::Int(slot))?;
+        Ok(())
+    }
+
+    // Returns the stack at an inventory index, see `MAIN_SIZE`. Empty slots hold the empty stack.
+    pub fn get_stack<'local>(&self, env: &mut JNIEnv<'local>, index: i32) -> Result<ItemStack<'local>> {
+        let stack = remap::call_method(env, &self.inner, Self::CLASS, "getStack", "(I)Lnet/minecraft/item/ItemStack;", &[JValue::Int(index)])?.l()?;
+        Ok(ItemStack::new(stack))
+    }
+
+    // Returns the stack in the selected hotbar slot.
+    pub fn get_main_hand_stack<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ItemStack<'local>> {
+        let stack = remap::call_method(env, &self.inner, Self::CLASS, "getMainHandStack", "()Lnet/minecraft/item/ItemStack;", &[])?.l()?;
+        Ok(ItemStack::new(stack))
+    }
+
+    // Number of inventory indices, armor and offhand included.
+    pub fn size(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "size", "()I", &[])?.i()?)
+    }
+}
@@ -0,0 +1,228 @@
+use jni::objects::{JClass, JObject};
+use jni::JNIEnv;
+
+use super::registry::Registries;
+use crate::sdk::jni::cache;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Items our modules care about, identified by their registry id. Comparing registry ids is the portable
+// way to identify items without relying on numeric ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownItem {
+    TotemOfUndying,
+    EnderPearl,
+    GoldenApple,
+    EnchantedGoldenApple,
+    Cobblestone,
+    Stone,
+    Dirt,
+    OakPlanks,
+    Obsidian,
+    WoodenSword,
+    StoneSword,
+    IronSword,
+    GoldenSword,
+    DiamondSword,
+    NetheriteSword,
+    WoodenAxe,
+    StoneAxe,
+    IronAxe,
+    GoldenAxe,
+    DiamondAxe,
+    NetheriteAxe,
+    WoodenPickaxe,
+    StonePickaxe,
+    IronPickaxe,
+    GoldenPickaxe,
+    DiamondPickaxe,
+    NetheritePickaxe,
+}
+
+impl KnownItem {
+    pub const ALL: [KnownItem; 27] = [
+        KnownItem::TotemOfUndying,
+        KnownItem::EnderPearl,
+        KnownItem::GoldenApple,
+        KnownItem::EnchantedGoldenApple,
+        KnownItem::Cobblestone,
+        KnownItem::Stone,
+        KnownItem::Dirt,
+        KnownItem::OakPlanks,
+        KnownItem::Obsidian,
+        KnownItem::WoodenSword,
+        KnownItem::StoneSword,
+        KnownItem::IronSword,
+        KnownItem::GoldenSword,
+        KnownItem::DiamondSword,
+        KnownItem::NetheriteSword,
+        KnownItem::WoodenAxe,
+        KnownItem::StoneAxe,
+        KnownItem::IronAxe,
+        KnownItem::GoldenAxe,
+        KnownItem::DiamondAxe,
+        KnownItem::NetheriteAxe,
+        KnownItem::WoodenPickaxe,
+        KnownItem::StonePickaxe,
+        KnownItem::IronPickaxe,
+        KnownItem::GoldenPickaxe,
+        KnownItem::DiamondPickaxe,
+        KnownItem::NetheritePickaxe,
+    ];
+
+    // The registry identifier of the item.
+    pub fn id(self) -> &'static str {
+        match self {
+            KnownItem::TotemOfUndying => "minecraft:totem_of_undying",
+            KnownItem::EnderPearl => "minecraft:ender_pearl",
+            KnownItem::GoldenApple => "minecraft:golden_apple",
+            KnownItem::EnchantedGoldenApple => "minecraft:enchanted_golden_apple",
+            KnownItem::Cobblestone => "minecraft:cobblestone",
+            KnownItem::Stone => "minecraft:stone",
+            KnownItem::Dirt => "minecraft:dirt",
+            KnownItem::OakPlanks => "minecraft:oak_planks",
+            KnownItem::Obsidian => "minecraft:obsidian",
+            KnownItem::WoodenSword => "minecraft:wooden_sword",
+            KnownItem::StoneSword => "minecraft:stone_sword",
+            KnownItem::IronSword => "minecraft:iron_sword",
+            KnownItem::GoldenSword => "minecraft:golden_sword",
+            KnownItem::DiamondSword => "minecraft:diamond_sword",
+            KnownItem::NetheriteSword => "minecraft:netherite_sword",
+            KnownItem::WoodenAxe => "minecraft:wooden_axe",
+            KnownItem::StoneAxe => "minecraft:stone_axe",
+            KnownItem::IronAxe => "minecraft:iron_axe",
+            KnownItem::GoldenAxe => "minecraft:golden_axe",
+            KnownItem::DiamondAxe => "minecraft:diamond_axe",
+            KnownItem::NetheriteAxe => "minecraft:netherite_axe",
+            KnownItem::WoodenPickaxe => "minecraft:wooden_pickaxe",
+            KnownItem::StonePickaxe => "minecraft:stone_pickaxe",
+            KnownItem::IronPickaxe => "minecraft:iron_pickaxe",
+            KnownItem::GoldenPickaxe => "minecraft:golden_pickaxe",
+            KnownItem::DiamondPickaxe => "minecraft:diamond_pickaxe",
+            KnownItem::NetheritePickaxe => "minecraft:netherite_pickaxe",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|item| item.id() == id)
+    }
+
+    pub fn is_sword(self) -> bool {
+        matches!(
+            self,
+            KnownItem::WoodenSword
+                | KnownItem::StoneSword
+                | KnownItem::IronSword
+                | KnownItem::GoldenSword
+                | KnownItem::DiamondSword
+                | KnownItem::NetheriteSword
+        )
+    }
+
+    pub fn is_axe(self) -> bool {
+        matches!(
+            self,
+            KnownItem::WoodenAxe
+                | KnownItem::StoneAxe
+                | KnownItem::IronAxe
+                | KnownItem::GoldenAxe
+                | KnownItem::DiamondAxe
+                | KnownItem::NetheriteAxe
+        )
+    }
+
+    pub fn is_pickaxe(self) -> bool {
+        matches!(
+            self,
+            KnownItem::WoodenPickaxe
+                | KnownItem::StonePickaxe
+                | KnownItem::IronPickaxe
+                | KnownItem::GoldenPickaxe
+                | KnownItem::DiamondPickaxe
+                | KnownItem::NetheritePickaxe
+        )
+    }
+
+    // Whether the item places a full block, used to pick blocks for building.
+    pub fn is_block(self) -> bool {
+        matches!(
+            self,
+            KnownItem::Cobblestone
+                | KnownItem::Stone
+                | KnownItem::Dirt
+                | KnownItem::OakPlanks
+                | KnownItem::Obsidian
+        )
+    }
+}
+
+// Wrapper for `net/minecraft/item/ItemStack`.
+pub struct ItemStack<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ItemStack<'a> {
+    pub const CLASS: &'static str = "net/minecraft/item/ItemStack";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn is_empty(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isEmpty", "()Z", &[])?.z()?)
+    }
+
+    pub fn get_count(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getCount", "()I", &[])?.i()?)
+    }
+
+    // Whether the item wears down with use, like tools, weapons and armor.
+    pub fn is_damageable(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isDamageable", "()Z", &[])?.z()?)
+    }
+
+    // Durability used up so far, 0 for a new item.
+    pub fn get_damage(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getDamage", "()I", &[])?.i()?)
+    }
+
+    // Durability of a new item, 0 for items that cannot be damaged.
+    pub fn get_max_damage(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getMaxDamage", "()I", &[])?.i()?)
+    }
+
+    // Returns the `net/minecraft/item/Item` of the stack.
+    pub fn get_item<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getItem", "()Lnet/minecraft/item/Item;", &[])?.l()?)
+    }
+
+    // Whether the item places a block, any block including torches and flowers. Empty stacks hold air,
+    // which is not a block item.
+    pub fn is_block_item(&self, env: &mut JNIEnv) -> Result<bool> {
+        let item = self.get_item(env)?;
+        let class = cache::class(env, "net/minecraft/item/BlockItem")?;
+        let is_block_item = env.is_instance_of(&item, <&JClass>::from(class.as_obj()));
+        env.delete_local_ref(item)?;
+        Ok(is_block_item?)
+    }
+
+    // Returns the registry identifier of the item, e.g. `minecraft:totem_of_undying`.
+    pub fn get_item_id(&self, env: &mut JNIEnv) -> Result<String> {
+        let item = self.get_item(env)?;
+        let item_registry = Registries::item(env)?;
+        let id = item_registry.get_id(env, &item)?;
+        env.delete_local_ref(item_registry.inner)?;
+        env.delete_local_ref(item)?;
+        Ok(id)
+    }
+
+    // Checks whether the stack holds the given item.
+    pub fn is(&self, env: &mut JNIEnv, item: KnownItem) -> Result<bool> {
+        Ok(self.get_item_id(env)? == item.id())
+    }
+
+    // Returns the known item held by the stack, if it is one of them.
+    pub fn get_known_item(&self, env: &mut JNIEnv) -> Result<Option<KnownItem>> {
+        Ok(KnownItem::from_id(&self.get_item_id(env)?))
+    }
+}
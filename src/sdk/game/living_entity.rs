@@ -0,0 +1,183 @@
+use std::ops::Deref;
+
+use jni::objects::{JClass, JObject, JValue};
+use jni::JNIEnv;
+
+use super::attribute::{AttributeContainer, KnownAttribute};
+use super::entity::Entity;
+use super::hand::Hand;
+use super::item::ItemStack;
+use super::status_effect::{ActiveEffect, StatusEffectInstance};
+use crate::sdk::jni::cache;
+use crate::sdk::jni::collections::JavaMap;
+use crate::sdk::jni::remap;
+use crate::sdk::{Error, Result};
+
+// Wrapper for `net/minecraft/entity/LivingEntity`, every entity that has health and can use items.
+// It dereferences to `Entity`.
+pub struct LivingEntity<'a> {
+    entity: Entity<'a>,
+}
+
+impl<'a> LivingEntity<'a> {
+    pub const CLASS: &'static str = "net/minecraft/entity/LivingEntity";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { entity: Entity::new(inner) }
+    }
+
+    // Treats an entity as a living entity, the caller has to know it actually is one.
+    pub fn from_entity(entity: Entity<'a>) -> Self {
+        Self { entity }
+    }
+
+    // Whether the entity is a living entity, e.g. to skip items and projectiles while scanning.
+    pub fn is_living(env: &mut JNIEnv, entity: &Entity) -> Result<bool> {
+        let class = cache::class(env, Self::CLASS)?;
+        Ok(env.is_instance_of(&entity.inner, <&JClass>::from(class.as_obj()))?)
+    }
+
+    // Casts an entity to a living entity, returning `Error::InvalidCast` for any other entity instead
+    // of calling methods it lacks, which would throw in Java.
+    pub fn cast(env: &mut JNIEnv, entity: Entity<'a>) -> Result<Self> {
+        if !Self::is_living(env, &entity)? {
+            return Err(Error::InvalidCast { class: Self::CLASS.to_string() });
+        }
+        Ok(Self { entity })
+    }
+
+    pub fn as_entity_ref(&self) -> &Entity<'a> {
+        &self.entity
+    }
+
+    pub fn as_entity(self) -> Entity<'a> {
+        self.entity
+    }
+
+    pub fn get_health(&self, env: &mut JNIEnv) -> Result<f32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getHealth", "()F", &[])?.f()?)
+    }
+
+    pub fn get_max_health(&self, env: &mut JNIEnv) -> Result<f32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getMaxHealth", "()F", &[])?.f()?)
+    }
+
+    // Ticks left of the red flash after taking damage, counting down from 10.
+    pub fn get_hurt_time(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "hurtTime", "I")?.i()?)
+    }
+
+    // Whether the entity has no health left, it stays in the world for its death animation.
+    pub fn is_dead(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isDead", "()Z", &[])?.z()?)
+    }
+
+    // The status effects on the entity, read from its map of active effects keyed by effect type.
+    // Other players only have the effects the server sends, which are those with visible particles.
+    pub fn get_status_effects(&self, env: &mut JNIEnv) -> Result<Vec<ActiveEffect>> {
+        let effects = JavaMap::new(remap::call_method(env, &self.inner, Self::CLASS, "getActiveStatusEffects", "()Ljava/util/Map;", &[])?.l()?);
+        let mut active = Vec::new();
+        let result = effects.for_each_entry(env, |env, _effect_type, effect| {
+            let instance = StatusEffectInstance::new(env.new_local_ref(effect)?);
+            let effect = instance.to_active_effect(env);
+            env.delete_local_ref(instance.inner)?;
+            active.push(effect?);
+            Ok(())
+        });
+        env.delete_local_ref(effects.inner)?;
+        result?;
+        Ok(active)
+    }
+
+    // The stack being used, e.g. the food being eaten or the raised shield. Empty when not using an item.
+    pub fn get_active_item<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ItemStack<'local>> {
+        let stack = remap::call_method(env, &self.inner, Self::CLASS, "getActiveItem", "()Lnet/minecraft/item/ItemStack;", &[])?.l()?;
+        Ok(ItemStack::new(stack))
+    }
+
+    pub fn get_attributes<'local>(&self, env: &mut JNIEnv<'local>) -> Result<AttributeContainer<'local>> {
+        let attributes = remap::call_method(env, &self.inner, Self::CLASS, "getAttributes", "()Lnet/minecraft/entity/attribute/AttributeContainer;", &[])?.l()?;
+        Ok(AttributeContainer::new(attributes))
+    }
+
+    // Returns the value of the attribute with all modifiers applied. Every living entity has the
+    // attributes in `KnownAttribute`, so a missing one is an error.
+    pub fn get_attribute_value(&self, env: &mut JNIEnv, attribute: KnownAttribute) -> Result<f64> {
+        let attributes = self.get_attributes(env)?;
+        let instance = attributes.get_instance(env, attribute)?;
+        env.delete_local_ref(attributes.inner)?;
+
+        let Some(instance) = instance else {
+            return Err(Error::null_object(format!("attribute instance of {:?}", attribute)));
+        };
+        let value = instance.get_value(env)?;
+        env.delete_local_ref(instance.inner)?;
+        Ok(value)
+    }
+
+    // Returns the stack held in `hand`, an empty hand holds the empty stack rather than null.
+    pub fn get_stack_in_hand<'local>(&self, env: &mut JNIEnv<'local>, hand: Hand) -> Result<ItemStack<'local>> {
+        let java_hand = hand.to_java(env)?;
+        let stack = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "getStackInHand",
+            "(Lnet/minecraft/util/Hand;)Lnet/minecraft/item/ItemStack;",
+            &[JValue::Object(&java_hand)],
+        )?.l()?;
+        env.delete_local_ref(java_hand)?;
+        Ok(ItemStack::new(stack))
+    }
+
+    // Jumps with the entity's jump strength, regardless of whether it stands on the ground.
+    pub fn jump(&self, env: &mut JNIEnv) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "jump", "()V", &[])?;
+        Ok(())
+    }
+
+    // Plays the swing animation of `hand`. For the local player this also tells the server, as a real
+    // attack or block interaction would.
+    pub fn swing_hand(&self, env: &mut JNIEnv, hand: Hand) -> Result<()> {
+        let java_hand = hand.to_java(env)?;
+        let result = remap::call_method(env, &self.inner, Self::CLASS, "swingHand", "(Lnet/minecraft/util/Hand;)V", &[JValue::Object(&java_hand)]);
+        env.delete_local_ref(java_hand)?;
+        result?;
+        Ok(())
+    }
+
+    // Whether the entity is gliding with an elytra.
+    pub fn is_fall_flying(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isFallFlying", "()Z", &[])?.z()?)
+    }
+
+    // Whether the entity is eating, drinking, blocking with a shield or drawing a bow.
+    pub fn is_using_item(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isUsingItem", "()Z", &[])?.z()?)
+    }
+
+    // Remaining ticks until the item in use finishes, e.g. until food is eaten.
+    pub fn get_item_use_time_left(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getItemUseTimeLeft", "()I", &[])?.i()?)
+    }
+
+    // The hand holding the item in use.
+    pub fn get_active_hand(&self, env: &mut JNIEnv) -> Result<Option<Hand>> {
+        let hand = remap::call_method(env, &self.inner, Self::CLASS, "getActiveHand", "()Lnet/minecraft/util/Hand;", &[])?.l()?;
+        if hand.is_null() {
+            return Ok(None);
+        }
+
+        let active_hand = Hand::from_java(env, &hand)?;
+        env.delete_local_ref(hand)?;
+        Ok(Some(active_hand))
+    }
+}
+
+impl<'a> Deref for LivingEntity<'a> {
+    type Target = Entity<'a>;
+
+    fn deref(&self) -> &Entity<'a> {
+        &self.entity
+    }
+}
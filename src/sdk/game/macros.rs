@@ -0,0 +1,152 @@
+use jni::objects::{JObject, JValue, JValueOwned};
+use jni::signature::{Primitive, ReturnType};
+use jni::JNIEnv;
+
+use crate::sdk::jni::cache;
+use crate::sdk::Result;
+
+// Primitive Java types a generated accessor can read or write, with their JNI type signature.
+pub trait JavaPrimitive: Sized {
+    const SIGNATURE: &'static str;
+    const PRIMITIVE: Primitive;
+
+    fn from_value(value: JValueOwned) -> jni::errors::Result<Self>;
+
+    fn to_value(self) -> JValue<'static, 'static>;
+}
+
+impl JavaPrimitive for bool {
+    const SIGNATURE: &'static str = "Z";
+    const PRIMITIVE: Primitive = Primitive::Boolean;
+
+    fn from_value(value: JValueOwned) -> jni::errors::Result<Self> {
+        value.z()
+    }
+
+    fn to_value(self) -> JValue<'static, 'static> {
+        JValue::Bool(self.into())
+    }
+}
+
+impl JavaPrimitive for i32 {
+    const SIGNATURE: &'static str = "I";
+    const PRIMITIVE: Primitive = Primitive::Int;
+
+    fn from_value(value: JValueOwned) -> jni::errors::Result<Self> {
+        value.i()
+    }
+
+    fn to_value(self) -> JValue<'static, 'static> {
+        JValue::Int(self)
+    }
+}
+
+impl JavaPrimitive for i64 {
+    const SIGNATURE: &'static str = "J";
+    const PRIMITIVE: Primitive = Primitive::Long;
+
+    fn from_value(value: JValueOwned) -> jni::errors::Result<Self> {
+        value.j()
+    }
+
+    fn to_value(self) -> JValue<'static, 'static> {
+        JValue::Long(self)
+    }
+}
+
+impl JavaPrimitive for f32 {
+    const SIGNATURE: &'static str = "F";
+    const PRIMITIVE: Primitive = Primitive::Float;
+
+    fn from_value(value: JValueOwned) -> jni::errors::Result<Self> {
+        value.f()
+    }
+
+    fn to_value(self) -> JValue<'static, 'static> {
+        JValue::Float(self)
+    }
+}
+
+impl JavaPrimitive for f64 {
+    const SIGNATURE: &'static str = "D";
+    const PRIMITIVE: Primitive = Primitive::Double;
+
+    fn from_value(value: JValueOwned) -> jni::errors::Result<Self> {
+        value.d()
+    }
+
+    fn to_value(self) -> JValue<'static, 'static> {
+        JValue::Double(self)
+    }
+}
+
+// Calls a method without arguments returning a primitive, the backing of generated `method`
+// accessors. Method and field IDs come from the cache, so only the first call looks them up by name.
+pub fn call_primitive<T: JavaPrimitive>(env: &mut JNIEnv, object: &JObject, class: &str, method: &str) -> Result<T> {
+    let method_id = cache::method_id(env, class, method, &format!("(){}", T::SIGNATURE))?;
+    // SAFETY: the method was resolved with this return type and takes no arguments, and `object` is
+    // an instance of `class` as the wrapper holding it guarantees.
+    let value = unsafe { env.call_method_unchecked(object, method_id, ReturnType::Primitive(T::PRIMITIVE), &[]) }?;
+    Ok(T::from_value(value)?)
+}
+
+pub fn get_primitive_field<T: JavaPrimitive>(env: &mut JNIEnv, object: &JObject, class: &str, field: &str) -> Result<T> {
+    let field_id = cache::field_id(env, class, field, T::SIGNATURE)?;
+    Ok(T::from_value(env.get_field_unchecked(object, field_id, ReturnType::Primitive(T::PRIMITIVE))?)?)
+}
+
+pub fn set_primitive_field<T: JavaPrimitive>(env: &mut JNIEnv, object: &JObject, class: &str, field: &str, value: T) -> Result<()> {
+    let field_id = cache::field_id(env, class, field, T::SIGNATURE)?;
+    env.set_field_unchecked(object, field_id, value.to_value())?;
+    Ok(())
+}
+
+// Generates a wrapper struct holding the Java object, its `new` constructor and accessors for
+// primitive members. Each member line names its kind, the Rust method, the Java name and the type:
+//
+//   java_class! {
+//       // Wrapper for `net/minecraft/entity/player/HungerManager`.
+//       pub struct HungerManager("net/minecraft/entity/player/HungerManager") {
+//           method get_food_level: "getFoodLevel" -> i32;
+//           getter is_flying: "flying" -> bool;
+//           setter set_flying: "flying" -> bool;
+//       }
+//   }
+//
+// `method` calls a method without arguments, `getter` reads a field and `setter` writes it. Anything
+// taking arguments or returning objects is written by hand in a separate `impl` block.
+macro_rules! java_class {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident($class:literal) { $($kind:ident $member:ident : $java:literal -> $ty:ty;)* }) => {
+        $(#[$meta])*
+        $vis struct $name<'a> {
+            pub inner: jni::objects::JObject<'a>,
+        }
+
+        impl<'a> $name<'a> {
+            pub const CLASS: &'static str = $class;
+
+            pub fn new(inner: jni::objects::JObject<'a>) -> Self {
+                Self { inner }
+            }
+
+            $($crate::sdk::game::macros::java_class!(@member $kind $member $java $ty);)*
+        }
+    };
+    (@member method $member:ident $java:literal $ty:ty) => {
+        pub fn $member(&self, env: &mut jni::JNIEnv) -> $crate::sdk::Result<$ty> {
+            $crate::sdk::game::macros::call_primitive(env, &self.inner, Self::CLASS, $java)
+        }
+    };
+    (@member getter $member:ident $java:literal $ty:ty) => {
+        pub fn $member(&self, env: &mut jni::JNIEnv) -> $crate::sdk::Result<$ty> {
+            $crate::sdk::game::macros::get_primitive_field(env, &self.inner, Self::CLASS, $java)
+        }
+    };
+    (@member setter $member:ident $java:literal $ty:ty) => {
+        pub fn $member(&self, env: &mut jni::JNIEnv, value: $ty) -> $crate::sdk::Result<()> {
+            $crate::sdk::game::macros::set_primitive_field(env, &self.inner, Self::CLASS, $java, value)
+        }
+    };
+}
+
+pub(crate) use java_class;
@@ -0,0 +1,31 @@
+// Wrappers around Minecraft classes. Each wrapper holds the Java object it represents and takes the
+// JNI environment of the calling thread for every call.
+pub mod attribute;
+pub mod block;
+pub mod camera;
+pub mod chat_hud;
+pub mod client;
+pub mod direction;
+pub mod entity;
+pub mod game_profile;
+pub mod hand;
+pub mod identifier;
+pub mod interaction_manager;
+pub mod inventory;
+pub mod item;
+pub mod living_entity;
+pub mod macros;
+pub mod network;
+pub mod options;
+pub mod packet;
+pub mod packet_log;
+pub mod player;
+pub mod player_list;
+pub mod registry;
+pub mod screen_handler;
+pub mod session;
+pub mod status_effect;
+pub mod text;
+pub mod vec3d;
+pub mod window;
+pub mod world;
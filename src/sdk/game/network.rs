@@ -0,0 +1,72 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::packet_log;
+use crate::sdk::jni::string::ToJava;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/client/network/ClientPlayNetworkHandler`, the connection to the server.
+pub struct ClientPlayNetworkHandler<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ClientPlayNetworkHandler<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/network/ClientPlayNetworkHandler";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Sends a packet to the server. `packet` must be a `net/minecraft/network/packet/Packet`.
+    pub fn send_packet(&self, env: &mut JNIEnv, packet: &JObject) -> Result<()> {
+        packet_log::log_outgoing(env, packet);
+        remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "sendPacket",
+            "(Lnet/minecraft/network/packet/Packet;)V",
+            &[JValue::Object(packet)],
+        )?;
+        Ok(())
+    }
+
+    pub fn get_connection<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ClientConnection<'local>> {
+        let connection = remap::call_method(env, &self.inner, Self::CLASS, "getConnection", "()Lnet/minecraft/network/ClientConnection;", &[])?.l()?;
+        Ok(ClientConnection::new(connection))
+    }
+
+    // Sends a chat message as if typed by the player.
+    pub fn send_chat_message(&self, env: &mut JNIEnv, message: &str) -> Result<()> {
+        let message = message.to_java(env)?;
+        remap::call_method(env, &self.inner, Self::CLASS, "sendChatMessage", "(Ljava/lang/String;)V", &[JValue::Object(&message)])?;
+        Ok(())
+    }
+
+    // Sends a server command, `command` is given without the leading slash.
+    pub fn send_chat_command(&self, env: &mut JNIEnv, command: &str) -> Result<()> {
+        let command = command.to_java(env)?;
+        remap::call_method(env, &self.inner, Self::CLASS, "sendChatCommand", "(Ljava/lang/String;)V", &[JValue::Object(&command)])?;
+        Ok(())
+    }
+}
+
+// Wrapper for `net/minecraft/network/ClientConnection`, the Netty connection below the network handler.
+pub struct ClientConnection<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ClientConnection<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/ClientConnection";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the `io/netty/channel/Channel`, `None` before the connection was opened.
+    pub fn get_channel<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<JObject<'local>>> {
+        let channel = remap::get_field(env, &self.inner, Self::CLASS, "channel", "Lio/netty/channel/Channel;")?.l()?;
+        Ok((!channel.is_null()).then_some(channel))
+    }
+}
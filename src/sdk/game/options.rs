@@ -0,0 +1,238 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::macros::java_class;
+use crate::sdk::jni::boxing;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Key bindings the client reads or presses, named after their `GameOptions` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Forward,
+    Back,
+    Left,
+    Right,
+    Jump,
+    Sneak,
+    Sprint,
+    Attack,
+    Use,
+    Inventory,
+    Drop,
+    PickItem,
+}
+
+impl Key {
+    pub const MOVEMENT: [Key; 6] = [Key::Forward, Key::Back, Key::Left, Key::Right, Key::Jump, Key::Sprint];
+
+    fn field_name(self) -> &'static str {
+        match self {
+            Key::Forward => "forwardKey",
+            Key::Back => "backKey",
+            Key::Left => "leftKey",
+            Key::Right => "rightKey",
+            Key::Jump => "jumpKey",
+            Key::Sneak => "sneakKey",
+            Key::Sprint => "sprintKey",
+            Key::Attack => "attackKey",
+            Key::Use => "useKey",
+            Key::Inventory => "inventoryKey",
+            Key::Drop => "dropKey",
+            Key::PickItem => "pickItemKey",
+        }
+    }
+}
+
+// Wrapper for `net/minecraft/client/option/GameOptions`, the client settings.
+pub struct GameOptions<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> GameOptions<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/option/GameOptions";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    fn get_option<'local>(&self, env: &mut JNIEnv<'local>, getter: &str) -> Result<SimpleOption<'local>> {
+        let option = remap::call_method(env, &self.inner, Self::CLASS, getter, "()Lnet/minecraft/client/option/SimpleOption;", &[])?.l()?;
+        Ok(SimpleOption::new(option))
+    }
+
+    // Field of view in degrees (30-110).
+    pub fn get_fov(&self, env: &mut JNIEnv) -> Result<i32> {
+        let option = self.get_option(env, "getFov")?;
+        let fov = option.get_int(env)?;
+        env.delete_local_ref(option.inner)?;
+        Ok(fov)
+    }
+
+    // Sets the field of view. Values outside of 30-110 are rejected by the game.
+    pub fn set_fov(&self, env: &mut JNIEnv, fov: i32) -> Result<()> {
+        let option = self.get_option(env, "getFov")?;
+        option.set_int(env, fov)?;
+        env.delete_local_ref(option.inner)?;
+        Ok(())
+    }
+
+    pub fn get_key<'local>(&self, env: &mut JNIEnv<'local>, key: Key) -> Result<KeyBinding<'local>> {
+        let binding = remap::get_field(env, &self.inner, Self::CLASS, key.field_name(), "Lnet/minecraft/client/option/KeyBinding;")?.l()?;
+        Ok(KeyBinding::new(binding))
+    }
+
+    // Whether the key is held down, by the player or by `set_key_pressed`.
+    pub fn is_key_pressed(&self, env: &mut JNIEnv, key: Key) -> Result<bool> {
+        let binding = self.get_key(env, key)?;
+        let pressed = binding.is_pressed(env)?;
+        env.delete_local_ref(binding.inner)?;
+        Ok(pressed)
+    }
+
+    pub fn set_key_pressed(&self, env: &mut JNIEnv, key: Key, pressed: bool) -> Result<()> {
+        let binding = self.get_key(env, key)?;
+        binding.set_pressed(env, pressed)?;
+        env.delete_local_ref(binding.inner)?;
+        Ok(())
+    }
+
+    // Queues a click of the key, which the game handles in its next input handling like a real one,
+    // e.g. an attack on whatever the crosshair targets.
+    pub fn click_key(&self, env: &mut JNIEnv, key: Key) -> Result<()> {
+        let binding = self.get_key(env, key)?;
+        let times_pressed = binding.get_times_pressed(env)?;
+        binding.set_times_pressed(env, times_pressed + 1)?;
+        env.delete_local_ref(binding.inner)?;
+        Ok(())
+    }
+
+    // Brightness, 0.0 (moody) to 1.0 (bright).
+    pub fn get_gamma(&self, env: &mut JNIEnv) -> Result<f64> {
+        let option = self.get_option(env, "getGamma")?;
+        let gamma = option.get_double(env)?;
+        env.delete_local_ref(option.inner)?;
+        Ok(gamma)
+    }
+
+    // Sets the brightness without the game's 0.0-1.0 validation, so values like 15.0 for fullbright
+    // stick. The value is saved with the options, so changes should be registered for restoration.
+    pub fn set_gamma(&self, env: &mut JNIEnv, gamma: f64) -> Result<()> {
+        let option = self.get_option(env, "getGamma")?;
+        option.set_double_unchecked(env, gamma)?;
+        env.delete_local_ref(option.inner)?;
+        Ok(())
+    }
+
+    // Render distance in chunks.
+    pub fn get_view_distance(&self, env: &mut JNIEnv) -> Result<i32> {
+        let option = self.get_option(env, "getViewDistance")?;
+        let view_distance = option.get_int(env)?;
+        env.delete_local_ref(option.inner)?;
+        Ok(view_distance)
+    }
+}
+
+// Wrapper for `net/minecraft/client/option/SimpleOption`, the generic container behind most settings.
+// Its value is a boxed Java object, so the typed accessors box and unbox it through `sdk::jni::boxing`.
+pub struct SimpleOption<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> SimpleOption<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/option/SimpleOption";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    fn get_value<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getValue", "()Ljava/lang/Object;", &[])?.l()?)
+    }
+
+    fn set_value(&self, env: &mut JNIEnv, value: &JObject) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "setValue", "(Ljava/lang/Object;)V", &[JValue::Object(value)])?;
+        Ok(())
+    }
+
+    // Reads an option backed by `java/lang/Integer`.
+    pub fn get_int(&self, env: &mut JNIEnv) -> Result<i32> {
+        let value = self.get_value(env)?;
+        let int = boxing::unbox_int(env, &value)?;
+        env.delete_local_ref(value)?;
+        Ok(int)
+    }
+
+    // Writes an option backed by `java/lang/Integer`.
+    pub fn set_int(&self, env: &mut JNIEnv, value: i32) -> Result<()> {
+        let boxed = boxing::box_int(env, value)?;
+        self.set_value(env, &boxed)?;
+        env.delete_local_ref(boxed)?;
+        Ok(())
+    }
+
+    // Reads an option backed by `java/lang/Double`.
+    pub fn get_double(&self, env: &mut JNIEnv) -> Result<f64> {
+        let value = self.get_value(env)?;
+        let double = boxing::unbox_double(env, &value)?;
+        env.delete_local_ref(value)?;
+        Ok(double)
+    }
+
+    // Writes an option backed by `java/lang/Double`.
+    pub fn set_double(&self, env: &mut JNIEnv, value: f64) -> Result<()> {
+        let boxed = boxing::box_double(env, value)?;
+        self.set_value(env, &boxed)?;
+        env.delete_local_ref(boxed)?;
+        Ok(())
+    }
+
+    // Writes an option backed by `java/lang/Double` directly into its field, bypassing the validation
+    // `setValue` does and the change callback it runs.
+    pub fn set_double_unchecked(&self, env: &mut JNIEnv, value: f64) -> Result<()> {
+        let boxed = boxing::box_double(env, value)?;
+        remap::set_field(env, &self.inner, Self::CLASS, "value", "Ljava/lang/Object;", JValue::Object(&boxed))?;
+        env.delete_local_ref(boxed)?;
+        Ok(())
+    }
+
+    // Reads an option backed by `java/lang/Boolean`.
+    pub fn get_bool(&self, env: &mut JNIEnv) -> Result<bool> {
+        let value = self.get_value(env)?;
+        let bool = boxing::unbox_bool(env, &value)?;
+        env.delete_local_ref(value)?;
+        Ok(bool)
+    }
+
+    // Writes an option backed by `java/lang/Boolean`.
+    pub fn set_bool(&self, env: &mut JNIEnv, value: bool) -> Result<()> {
+        let boxed = boxing::box_bool(env, value)?;
+        self.set_value(env, &boxed)?;
+        env.delete_local_ref(boxed)?;
+        Ok(())
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/client/option/KeyBinding`. The game updates the pressed state from the
+    // keyboard on key events only, so a key pressed here stays pressed until released here or by the player.
+    pub struct KeyBinding("net/minecraft/client/option/KeyBinding") {
+        method is_pressed: "isPressed" -> bool;
+        // Presses not yet handled by the game, each one is handled like a click of the key.
+        getter get_times_pressed: "timesPressed" -> i32;
+        setter set_times_pressed: "timesPressed" -> i32;
+    }
+}
+
+impl KeyBinding<'_> {
+    pub fn set_pressed(&self, env: &mut JNIEnv, pressed: bool) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "setPressed", "(Z)V", &[JValue::Bool(pressed.into())])?;
+        Ok(())
+    }
+
+    // Whether the key was pressed since the last call, consuming one press. Used by the game for
+    // actions that repeat per press, like opening the inventory.
+    pub fn was_pressed(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "wasPressed", "()Z", &[])?.z()?)
+    }
+}
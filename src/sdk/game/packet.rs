@@ -0,0 +1,266 @@
+use jni::objects::{JObject, JString, JValue};
+use jni::JNIEnv;
+
+use crate::sdk::game::entity::Entity;
+use crate::sdk::game::hand::Hand;
+use crate::sdk::game::macros::java_class;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+use crate::sdk::math::DVec3;
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/UpdateSelectedSlotC2SPacket`, which tells the
+// server which hotbar slot is held.
+pub struct UpdateSelectedSlotC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> UpdateSelectedSlotC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/UpdateSelectedSlotC2SPacket";
+
+    pub fn new(env: &mut JNIEnv<'a>, slot: i32) -> Result<Self> {
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/UpdateSelectedSlotC2SPacket",
+            "(I)V",
+            &[JValue::Int(slot)],
+        )?;
+        Ok(Self { inner: packet })
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/HandSwingC2SPacket`, which plays the swing
+// animation for other players.
+pub struct HandSwingC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> HandSwingC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/HandSwingC2SPacket";
+
+    pub fn new(env: &mut JNIEnv<'a>, hand: Hand) -> Result<Self> {
+        let java_hand = hand.to_java(env)?;
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/HandSwingC2SPacket",
+            "(Lnet/minecraft/util/Hand;)V",
+            &[JValue::Object(&java_hand)],
+        )?;
+        env.delete_local_ref(java_hand)?;
+        Ok(Self { inner: packet })
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/PlayerInteractEntityC2SPacket`.
+pub struct PlayerInteractEntityC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> PlayerInteractEntityC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/PlayerInteractEntityC2SPacket";
+
+    // Builds an attack packet through the static `attack` factory. The server only counts the hit
+    // if the attacker is in range, the swing has to be sent separately.
+    pub fn attack(env: &mut JNIEnv<'a>, target: &Entity, sneaking: bool) -> Result<Self> {
+        let packet = remap::call_static_method(
+            env,
+            "net/minecraft/network/packet/c2s/play/PlayerInteractEntityC2SPacket",
+            "attack",
+            "(Lnet/minecraft/entity/Entity;Z)Lnet/minecraft/network/packet/c2s/play/PlayerInteractEntityC2SPacket;",
+            &[JValue::Object(&target.inner), JValue::Bool(sneaking.into())],
+        )?.l()?;
+        Ok(Self { inner: packet })
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket`, any of the movement
+    // packets below. The rotation fields only mean something when `changes_look` is set.
+    pub struct PlayerMoveC2SPacket("net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket") {
+        getter changes_look: "changeLook" -> bool;
+        getter is_on_ground: "onGround" -> bool;
+        setter set_on_ground: "onGround" -> bool;
+        getter get_yaw: "yaw" -> f32;
+        setter set_yaw: "yaw" -> f32;
+        getter get_pitch: "pitch" -> f32;
+        setter set_pitch: "pitch" -> f32;
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$OnGroundOnly`, a movement
+// packet that only carries the on-ground flag.
+pub struct OnGroundOnlyC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> OnGroundOnlyC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$OnGroundOnly";
+
+    pub fn new(env: &mut JNIEnv<'a>, on_ground: bool) -> Result<Self> {
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$OnGroundOnly",
+            "(Z)V",
+            &[JValue::Bool(on_ground.into())],
+        )?;
+        Ok(Self { inner: packet })
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$PositionAndOnGround`, a
+// movement packet without rotation.
+pub struct PositionAndOnGroundC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> PositionAndOnGroundC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$PositionAndOnGround";
+
+    pub fn new(env: &mut JNIEnv<'a>, pos: DVec3, on_ground: bool) -> Result<Self> {
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$PositionAndOnGround",
+            "(DDDZ)V",
+            &[JValue::Double(pos.x), JValue::Double(pos.y), JValue::Double(pos.z), JValue::Bool(on_ground.into())],
+        )?;
+        Ok(Self { inner: packet })
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$LookAndOnGround`, a movement
+// packet with the rotation but without the position.
+pub struct LookAndOnGroundC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> LookAndOnGroundC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$LookAndOnGround";
+
+    pub fn new(env: &mut JNIEnv<'a>, yaw: f32, pitch: f32, on_ground: bool) -> Result<Self> {
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$LookAndOnGround",
+            "(FFZ)V",
+            &[JValue::Float(yaw), JValue::Float(pitch), JValue::Bool(on_ground.into())],
+        )?;
+        Ok(Self { inner: packet })
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$Full`, a movement packet
+// with position and rotation.
+pub struct FullC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> FullC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$Full";
+
+    pub fn new(env: &mut JNIEnv<'a>, pos: DVec3, yaw: f32, pitch: f32, on_ground: bool) -> Result<Self> {
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/PlayerMoveC2SPacket$Full",
+            "(DDDFFZ)V",
+            &[
+                JValue::Double(pos.x),
+                JValue::Double(pos.y),
+                JValue::Double(pos.z),
+                JValue::Float(yaw),
+                JValue::Float(pitch),
+                JValue::Bool(on_ground.into()),
+            ],
+        )?;
+        Ok(Self { inner: packet })
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/ChatMessageC2SPacket`, a chat message the player
+// sent. Besides the text it acknowledges the chat messages seen since the previous one.
+pub struct ChatMessageC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ChatMessageC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/ChatMessageC2SPacket";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_chat_message(&self, env: &mut JNIEnv) -> Result<String> {
+        let message = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "chatMessage", "()Ljava/lang/String;", &[])?.l()?);
+        let rust_message: String = env.get_string(&message)?.into();
+        env.delete_local_ref(message)?;
+        Ok(rust_message)
+    }
+
+    // Number of newly seen messages the packet acknowledges. The server expects every seen message
+    // acknowledged exactly once, so a dropped chat packet has to pass this on.
+    pub fn get_acknowledgment_offset(&self, env: &mut JNIEnv) -> Result<i32> {
+        let acknowledgment = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "acknowledgment",
+            "()Lnet/minecraft/network/message/LastSeenMessageList$Acknowledgment;",
+            &[],
+        )?.l()?;
+        let offset = remap::call_method(env, &acknowledgment, "net/minecraft/network/message/LastSeenMessageList$Acknowledgment", "offset", "()I", &[])?.i()?;
+        env.delete_local_ref(acknowledgment)?;
+        Ok(offset)
+    }
+}
+
+// Wrapper for `net/minecraft/network/packet/c2s/play/MessageAcknowledgmentC2SPacket`, which
+// acknowledges seen chat messages without sending one.
+pub struct MessageAcknowledgmentC2SPacket<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> MessageAcknowledgmentC2SPacket<'a> {
+    pub const CLASS: &'static str = "net/minecraft/network/packet/c2s/play/MessageAcknowledgmentC2SPacket";
+
+    pub fn new(env: &mut JNIEnv<'a>, offset: i32) -> Result<Self> {
+        let packet = remap::new_object(
+            env,
+            "net/minecraft/network/packet/c2s/play/MessageAcknowledgmentC2SPacket",
+            "(I)V",
+            &[JValue::Int(offset)],
+        )?;
+        Ok(Self { inner: packet })
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/network/packet/s2c/play/EntityVelocityUpdateS2CPacket`, which sets the
+    // velocity of an entity, e.g. knockback from a hit. The velocity is in 1/8000 blocks per tick.
+    pub struct EntityVelocityUpdateS2CPacket("net/minecraft/network/packet/s2c/play/EntityVelocityUpdateS2CPacket") {
+        method get_entity_id: "getId" -> i32;
+        getter get_velocity_x: "velocityX" -> i32;
+        setter set_velocity_x: "velocityX" -> i32;
+        getter get_velocity_y: "velocityY" -> i32;
+        setter set_velocity_y: "velocityY" -> i32;
+        getter get_velocity_z: "velocityZ" -> i32;
+        setter set_velocity_z: "velocityZ" -> i32;
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/network/packet/s2c/play/WorldTimeUpdateS2CPacket`, which the server
+    // sends every 20 of its ticks to correct the world time the client advances on its own.
+    pub struct WorldTimeUpdateS2CPacket("net/minecraft/network/packet/s2c/play/WorldTimeUpdateS2CPacket") {
+        method get_time: "getTime" -> i64;
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/network/packet/s2c/play/ExplosionS2CPacket`. Besides the explosion
+    // itself it carries the velocity the local player gets added, in blocks per tick.
+    pub struct ExplosionS2CPacket("net/minecraft/network/packet/s2c/play/ExplosionS2CPacket") {
+        getter get_player_velocity_x: "playerVelocityX" -> f32;
+        setter set_player_velocity_x: "playerVelocityX" -> f32;
+        getter get_player_velocity_y: "playerVelocityY" -> f32;
+        setter set_player_velocity_y: "playerVelocityY" -> f32;
+        getter get_player_velocity_z: "playerVelocityZ" -> f32;
+        setter set_player_velocity_z: "playerVelocityZ" -> f32;
+    }
+}
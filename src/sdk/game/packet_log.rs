@@ -0,0 +1,110 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use jni::objects::{JObject, JObjectArray, JString};
+use jni::JNIEnv;
+use tracing::{info, warn};
+
+use crate::sdk::error::take_java_exception;
+use crate::sdk::Result;
+
+// At most this many packets are logged per second, the rest are counted and reported once the
+// second is over.
+const MAX_LOGGED_PER_SECOND: u32 = 20;
+
+// `java.lang.reflect.Modifier.STATIC`.
+const MODIFIER_STATIC: i32 = 0x0008;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+// Start of the current one-second window, packets logged in it and packets suppressed in it.
+static RATE_LIMIT: Mutex<Option<(Instant, u32, u32)>> = Mutex::new(None);
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+// Logs a packet our code is about to send with its class name and public primitive fields. Never
+// fails: any error, including a Java exception from reflection, is logged and cleared so the packet
+// is still sent.
+pub fn log_outgoing(env: &mut JNIEnv, packet: &JObject) {
+    if !is_enabled() || !take_rate_limit_slot() {
+        return;
+    }
+
+    let summary = env.with_local_frame(32, |env| -> Result<String> { describe_packet(env, packet) });
+    match summary {
+        Ok(summary) => info!("Sending {}", summary),
+        Err(e) => {
+            let e = take_java_exception(env).unwrap_or(e);
+            warn!("Failed to describe an outgoing packet: {}", e);
+        }
+    }
+}
+
+fn take_rate_limit_slot() -> bool {
+    let mut rate_limit = RATE_LIMIT.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let now = Instant::now();
+
+    let (window_start, logged, suppressed) = match *rate_limit {
+        Some((window_start, logged, suppressed)) if now.duration_since(window_start) < Duration::from_secs(1) => {
+            (window_start, logged, suppressed)
+        }
+        Some((_, _, suppressed)) => {
+            if suppressed > 0 {
+                info!("{} more outgoing packets were not logged", suppressed);
+            }
+            (now, 0, 0)
+        }
+        None => (now, 0, 0),
+    };
+
+    let allowed = logged < MAX_LOGGED_PER_SECOND;
+    *rate_limit = if allowed {
+        Some((window_start, logged + 1, suppressed))
+    } else {
+        Some((window_start, logged, suppressed + 1))
+    };
+    allowed
+}
+
+// Formats a packet as `ClassName { field: value, ... }` from its public instance fields of
+// primitive type. Packets are mostly records with private fields, those show as `ClassName {}`.
+fn describe_packet(env: &mut JNIEnv, packet: &JObject) -> Result<String> {
+    let class = env.call_method(packet, "getClass", "()Ljava/lang/Class;", &[])?.l()?;
+    let class_name = read_string(env, &class, "getName")?;
+
+    let fields = JObjectArray::from(env.call_method(&class, "getFields", "()[Ljava/lang/reflect/Field;", &[])?.l()?);
+    let mut values = Vec::new();
+
+    for index in 0..env.get_array_length(&fields)? {
+        let field = env.get_object_array_element(&fields, index)?;
+
+        let modifiers = env.call_method(&field, "getModifiers", "()I", &[])?.i()?;
+        let field_type = env.call_method(&field, "getType", "()Ljava/lang/Class;", &[])?.l()?;
+        let primitive = env.call_method(&field_type, "isPrimitive", "()Z", &[])?.z()?;
+        env.delete_local_ref(field_type)?;
+
+        if modifiers & MODIFIER_STATIC == 0 && primitive {
+            let name = read_string(env, &field, "getName")?;
+            let value = env.call_method(&field, "get", "(Ljava/lang/Object;)Ljava/lang/Object;", &[(&packet).into()])?.l()?;
+            values.push(format!("{}: {}", name, read_string(env, &value, "toString")?));
+            env.delete_local_ref(value)?;
+        }
+        env.delete_local_ref(field)?;
+    }
+
+    Ok(format!("{} {{ {} }}", class_name, values.join(", ")))
+}
+
+fn read_string(env: &mut JNIEnv, object: &JObject, method: &str) -> Result<String> {
+    let string = JString::from(env.call_method(object, method, "()Ljava/lang/String;", &[])?.l()?);
+    let rust_string: String = env.get_string(&string)?.into();
+    env.delete_local_ref(string)?;
+    Ok(rust_string)
+}
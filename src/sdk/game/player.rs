@@ -0,0 +1,174 @@
+use std::ops::Deref;
+
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::entity::Entity;
+use super::game_profile::GameProfile;
+use super::hand::Hand;
+use super::interaction_manager::ClientPlayerInteractionManager;
+use super::inventory::PlayerInventory;
+use super::living_entity::LivingEntity;
+use super::macros::{java_class, set_primitive_field};
+use super::network::ClientPlayNetworkHandler;
+use super::screen_handler::ScreenHandler;
+use super::text::Text;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/client/network/ClientPlayerEntity`, the local player.
+// It dereferences to `LivingEntity` (and from there to `Entity`), so those methods stay reachable
+// without giving up the player wrapper.
+pub struct ClientPlayerEntity<'a> {
+    living_entity: LivingEntity<'a>,
+}
+
+impl<'a> ClientPlayerEntity<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/network/ClientPlayerEntity";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { living_entity: LivingEntity::new(inner) }
+    }
+
+    // Borrows the player as a living entity.
+    pub fn as_living_entity_ref(&self) -> &LivingEntity<'a> {
+        &self.living_entity
+    }
+
+    // Borrows the player as an entity.
+    pub fn as_entity_ref(&self) -> &Entity<'a> {
+        self.living_entity.as_entity_ref()
+    }
+
+    // Converts the player into an entity, giving up the player-specific methods.
+    pub fn as_entity(self) -> Entity<'a> {
+        self.living_entity.as_entity()
+    }
+
+    pub fn get_inventory<'local>(&self, env: &mut JNIEnv<'local>) -> Result<PlayerInventory<'local>> {
+        let inventory = remap::call_method(env, &self.inner, Self::CLASS, "getInventory", "()Lnet/minecraft/entity/player/PlayerInventory;", &[])?.l()?;
+        Ok(PlayerInventory::new(inventory))
+    }
+
+    pub fn get_abilities<'local>(&self, env: &mut JNIEnv<'local>) -> Result<PlayerAbilities<'local>> {
+        let abilities = remap::call_method(env, &self.inner, Self::CLASS, "getAbilities", "()Lnet/minecraft/entity/player/PlayerAbilities;", &[])?.l()?;
+        Ok(PlayerAbilities::new(abilities))
+    }
+
+    // The player has no getter for its network handler, so the public field is read instead.
+    pub fn get_network_handler<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ClientPlayNetworkHandler<'local>> {
+        let network_handler = remap::get_field(env, &self.inner, Self::CLASS, "networkHandler", "Lnet/minecraft/client/network/ClientPlayNetworkHandler;")?.l()?;
+        Ok(ClientPlayNetworkHandler::new(network_handler))
+    }
+
+    pub fn get_hunger_manager<'local>(&self, env: &mut JNIEnv<'local>) -> Result<HungerManager<'local>> {
+        let hunger_manager = remap::call_method(env, &self.inner, Self::CLASS, "getHungerManager", "()Lnet/minecraft/entity/player/HungerManager;", &[])?.l()?;
+        Ok(HungerManager::new(hunger_manager))
+    }
+
+    pub fn get_input<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Input<'local>> {
+        let input = remap::get_field(env, &self.inner, Self::CLASS, "input", "Lnet/minecraft/client/input/Input;")?.l()?;
+        Ok(Input::new(input))
+    }
+
+    pub fn get_game_profile<'local>(&self, env: &mut JNIEnv<'local>) -> Result<GameProfile<'local>> {
+        let profile = remap::call_method(env, &self.inner, Self::CLASS, "getGameProfile", "()Lcom/mojang/authlib/GameProfile;", &[])?.l()?;
+        Ok(GameProfile::new(profile))
+    }
+
+    // Uses the item in `hand` as if right-clicking into the air, returns whether the use was accepted.
+    pub fn use_held_item(&self, env: &mut JNIEnv, interaction_manager: &ClientPlayerInteractionManager, hand: Hand) -> Result<bool> {
+        interaction_manager.interact_item(env, self, hand)
+    }
+
+    // The handler of the open container, or of the player's own inventory while none is open.
+    pub fn get_current_screen_handler<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ScreenHandler<'local>> {
+        let screen_handler = remap::get_field(env, &self.inner, Self::CLASS, "currentScreenHandler", "Lnet/minecraft/screen/ScreenHandler;")?.l()?;
+        Ok(ScreenHandler::new(screen_handler))
+    }
+
+    // Closes the open container screen and tells the server, as pressing escape does.
+    pub fn close_handled_screen(&self, env: &mut JNIEnv) -> Result<()> {
+        remap::call_method(env, &self.inner, Self::CLASS, "closeHandledScreen", "()V", &[])?;
+        Ok(())
+    }
+
+    // Sets the rotation the game believes it last sent to the server. It sends a look packet in its
+    // next movement update whenever the player's rotation differs from this.
+    pub fn set_last_sent_rotation(&self, env: &mut JNIEnv, yaw: f32, pitch: f32) -> Result<()> {
+        set_primitive_field(env, &self.inner, Self::CLASS, "lastYaw", yaw)?;
+        set_primitive_field(env, &self.inner, Self::CLASS, "lastPitch", pitch)
+    }
+
+    // Sends a chat message to the server as if typed by the player.
+    pub fn send_chat_message(&self, env: &mut JNIEnv, message: &str) -> Result<()> {
+        let network_handler = self.get_network_handler(env)?;
+        network_handler.send_chat_message(env, message)?;
+        env.delete_local_ref(network_handler.inner)?;
+        Ok(())
+    }
+
+    // Runs a server command. A leading slash is accepted and stripped, as the game expects none.
+    pub fn send_command(&self, env: &mut JNIEnv, command: &str) -> Result<()> {
+        let network_handler = self.get_network_handler(env)?;
+        network_handler.send_chat_command(env, command.strip_prefix('/').unwrap_or(command))?;
+        env.delete_local_ref(network_handler.inner)?;
+        Ok(())
+    }
+
+    // Shows a message to the player only, either in chat or above the hotbar when `action_bar` is set.
+    pub fn send_message(&self, env: &mut JNIEnv, text: &Text, action_bar: bool) -> Result<()> {
+        remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "sendMessage",
+            "(Lnet/minecraft/text/Text;Z)V",
+            &[JValue::Object(&text.inner), JValue::Bool(action_bar.into())],
+        )?;
+        Ok(())
+    }
+}
+
+impl<'a> Deref for ClientPlayerEntity<'a> {
+    type Target = LivingEntity<'a>;
+
+    fn deref(&self) -> &LivingEntity<'a> {
+        &self.living_entity
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/entity/player/PlayerAbilities`.
+    pub struct PlayerAbilities("net/minecraft/entity/player/PlayerAbilities") {
+        getter is_flying: "flying" -> bool;
+        setter set_flying: "flying" -> bool;
+        getter allow_flying: "allowFlying" -> bool;
+        setter set_allow_flying: "allowFlying" -> bool;
+        getter is_creative_mode: "creativeMode" -> bool;
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/entity/player/HungerManager`.
+    pub struct HungerManager("net/minecraft/entity/player/HungerManager") {
+        method get_food_level: "getFoodLevel" -> i32;
+        method get_saturation_level: "getSaturationLevel" -> f32;
+    }
+}
+
+java_class! {
+    // Wrapper for `net/minecraft/client/input/Input`, the movement input of the local player. The
+    // game fills it from the keyboard at the start of every tick, so written values last for that
+    // tick.
+    pub struct Input("net/minecraft/client/input/Input") {
+        getter get_movement_forward: "movementForward" -> f32;
+        setter set_movement_forward: "movementForward" -> f32;
+        getter get_movement_sideways: "movementSideways" -> f32;
+        setter set_movement_sideways: "movementSideways" -> f32;
+        getter is_jumping: "jumping" -> bool;
+        setter set_jumping: "jumping" -> bool;
+        getter is_sneaking: "sneaking" -> bool;
+        setter set_sneaking: "sneaking" -> bool;
+    }
+}
@@ -0,0 +1,117 @@
+use jni::objects::JObject;
+use jni::JNIEnv;
+
+use super::game_profile::GameProfile;
+use super::identifier::Identifier;
+use super::network::ClientPlayNetworkHandler;
+use crate::sdk::jni::collections::JavaCollection;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Game modes as reported by `GameMode.getId()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl GameMode {
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(GameMode::Survival),
+            1 => Some(GameMode::Creative),
+            2 => Some(GameMode::Adventure),
+            3 => Some(GameMode::Spectator),
+            _ => None,
+        }
+    }
+}
+
+// Wrapper for `net/minecraft/client/network/PlayerListEntry`, a single entry of the tab list.
+pub struct PlayerListEntry<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> PlayerListEntry<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/network/PlayerListEntry";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Collects the tab list of the network handler. Entries without a game profile (NPC entries
+    // some servers inject) are skipped instead of failing the whole iteration.
+    pub fn collect_from<'local>(env: &mut JNIEnv<'local>, network_handler: &ClientPlayNetworkHandler) -> Result<Vec<PlayerListEntry<'local>>> {
+        let player_list = JavaCollection::new(
+            remap::call_method(env, &network_handler.inner, ClientPlayNetworkHandler::CLASS, "getPlayerList", "()Ljava/util/Collection;", &[])?.l()?,
+        );
+        let iterator = player_list.iter(env)?;
+        env.delete_local_ref(player_list.inner)?;
+
+        let mut entries = Vec::new();
+        while let Some(entry) = iterator.next(env)? {
+            let entry = PlayerListEntry::new(entry);
+
+            if entry.inner.is_null() || !entry.has_profile(env)? {
+                env.delete_local_ref(entry.inner)?;
+                continue;
+            }
+
+            entries.push(entry);
+        }
+        env.delete_local_ref(iterator.inner)?;
+
+        Ok(entries)
+    }
+
+    // Returns whether the entry carries a game profile, which some injected entries do not.
+    pub fn has_profile(&self, env: &mut JNIEnv) -> Result<bool> {
+        match self.get_profile(env)? {
+            Some(profile) => {
+                env.delete_local_ref(profile.inner)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    pub fn get_profile<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<GameProfile<'local>>> {
+        let profile = remap::call_method(env, &self.inner, Self::CLASS, "getProfile", "()Lcom/mojang/authlib/GameProfile;", &[])?.l()?;
+        if profile.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(GameProfile::new(profile)))
+    }
+
+    // Returns the game mode of the player, the tab list is the only client-side source for it on other players.
+    pub fn get_game_mode(&self, env: &mut JNIEnv) -> Result<Option<GameMode>> {
+        let game_mode = remap::call_method(env, &self.inner, Self::CLASS, "getGameMode", "()Lnet/minecraft/world/GameMode;", &[])?.l()?;
+        if game_mode.is_null() {
+            return Ok(None);
+        }
+
+        let id = remap::call_method(env, &game_mode, "net/minecraft/world/GameMode", "getId", "()I", &[])?.i()?;
+        env.delete_local_ref(game_mode)?;
+        Ok(GameMode::from_id(id))
+    }
+
+    // Returns the identifier of the skin texture (e.g. `minecraft:skins/<hash>`), which external UIs use to fetch skin heads.
+    pub fn get_skin_texture_id(&self, env: &mut JNIEnv) -> Result<Option<String>> {
+        let skin_textures = remap::call_method(env, &self.inner, Self::CLASS, "getSkinTextures", "()Lnet/minecraft/client/util/SkinTextures;", &[])?.l()?;
+        if skin_textures.is_null() {
+            return Ok(None);
+        }
+
+        let texture = Identifier::new(remap::call_method(env, &skin_textures, "net/minecraft/client/util/SkinTextures", "texture", "()Lnet/minecraft/util/Identifier;", &[])?.l()?);
+        env.delete_local_ref(skin_textures)?;
+        if texture.inner.is_null() {
+            return Ok(None);
+        }
+
+        let texture_id = texture.to_string(env)?;
+        env.delete_local_ref(texture.inner)?;
+        Ok(Some(texture_id))
+    }
+}
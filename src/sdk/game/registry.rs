@@ -0,0 +1,87 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use super::identifier::Identifier;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Where the registries live. The access path moved between game versions, so it is only spelled out here.
+const REGISTRIES_CLASS: &str = "net/minecraft/registry/Registries";
+const DEFAULTED_REGISTRY_SIGNATURE: &str = "Lnet/minecraft/registry/DefaultedRegistry;";
+
+// Access to the game registries.
+pub struct Registries;
+
+impl Registries {
+    pub fn item<'local>(env: &mut JNIEnv<'local>) -> Result<Registry<'local>> {
+        Self::get(env, "ITEM")
+    }
+
+    pub fn block<'local>(env: &mut JNIEnv<'local>) -> Result<Registry<'local>> {
+        Self::get(env, "BLOCK")
+    }
+
+    fn get<'local>(env: &mut JNIEnv<'local>, field: &str) -> Result<Registry<'local>> {
+        let registry = remap::get_static_field(env, REGISTRIES_CLASS, field, DEFAULTED_REGISTRY_SIGNATURE)?.l()?;
+        Ok(Registry::new(registry))
+    }
+}
+
+// Wrapper for `net/minecraft/registry/Registry`.
+pub struct Registry<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Registry<'a> {
+    pub const CLASS: &'static str = "net/minecraft/registry/Registry";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the identifier an entry is registered under, e.g. `minecraft:stone`.
+    pub fn get_id(&self, env: &mut JNIEnv, entry: &JObject) -> Result<String> {
+        let identifier = Identifier::new(remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "getId",
+            "(Ljava/lang/Object;)Lnet/minecraft/util/Identifier;",
+            &[JValue::Object(entry)],
+        )?.l()?);
+        let id = identifier.to_string(env)?;
+        env.delete_local_ref(identifier.inner)?;
+        Ok(id)
+    }
+
+    // Looks up an entry by its identifier. Defaulted registries return their default entry for unknown ids,
+    // so the id is checked first and `None` is returned for ids that are not registered.
+    pub fn get_by_id<'local>(&self, env: &mut JNIEnv<'local>, id: &str) -> Result<Option<JObject<'local>>> {
+        let identifier = Identifier::from_string(env, id)?;
+
+        let contains = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "containsId",
+            "(Lnet/minecraft/util/Identifier;)Z",
+            &[JValue::Object(&identifier.inner)],
+        )?.z()?;
+
+        let entry = if contains {
+            Some(remap::call_method(
+                env,
+                &self.inner,
+                Self::CLASS,
+                "get",
+                "(Lnet/minecraft/util/Identifier;)Ljava/lang/Object;",
+                &[JValue::Object(&identifier.inner)],
+            )?.l()?)
+        } else {
+            None
+        };
+        env.delete_local_ref(identifier.inner)?;
+
+        Ok(entry)
+    }
+}
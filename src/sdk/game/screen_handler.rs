@@ -0,0 +1,161 @@
+use std::ops::RangeInclusive;
+
+use jni::objects::{JObject, JString};
+use jni::JNIEnv;
+
+use super::item::ItemStack;
+use crate::sdk::jni::collections::JavaList;
+use crate::sdk::jni::remap;
+use crate::sdk::mappings;
+use crate::sdk::Result;
+
+// Slot ids of the main inventory and hotbar in the player's own inventory handler, which is the
+// current handler while no container is open. Ids 0-8 are crafting and armor, 45 is the offhand.
+pub const PLAYER_INVENTORY_SLOT_IDS: RangeInclusive<i32> = 9..=44;
+
+// Slots every container handler has after its own, the player's main inventory and hotbar.
+pub const PLAYER_SLOT_COUNT: usize = 36;
+
+// Handler of chests, barrels and ender chests, a grid of one to six rows of nine slots.
+pub const GENERIC_CONTAINER_CLASS: &str = "net/minecraft/screen/GenericContainerScreenHandler";
+
+// How a slot is clicked, mirroring `net/minecraft/screen/slot/SlotActionType`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotActionType {
+    Pickup,
+    QuickMove,
+    Swap,
+    Clone,
+    // Drops one item, or the whole stack with button 1, like pressing the drop key over the slot.
+    Throw,
+    QuickCraft,
+    PickupAll,
+}
+
+impl SlotActionType {
+    fn java_name(self) -> &'static str {
+        match self {
+            SlotActionType::Pickup => "PICKUP",
+            SlotActionType::QuickMove => "QUICK_MOVE",
+            SlotActionType::Swap => "SWAP",
+            SlotActionType::Clone => "CLONE",
+            SlotActionType::Throw => "THROW",
+            SlotActionType::QuickCraft => "QUICK_CRAFT",
+            SlotActionType::PickupAll => "PICKUP_ALL",
+        }
+    }
+
+    pub fn to_java<'local>(self, env: &mut JNIEnv<'local>) -> Result<JObject<'local>> {
+        Ok(remap::get_static_field(
+            env,
+            "net/minecraft/screen/slot/SlotActionType",
+            self.java_name(),
+            "Lnet/minecraft/screen/slot/SlotActionType;",
+        )?.l()?)
+    }
+}
+
+// Wrapper for `net/minecraft/screen/ScreenHandler`, the server-synchronized side of a container
+// screen. While no container is open, the player's own inventory handler is the current one.
+//
+// Slots are numbered twice. The slot id is the position in this handler's slot list and is what
+// click packets use. The slot index is the position in the inventory the slot belongs to. In a
+// single chest, ids 0-26 are chest indices 0-26, ids 27-53 are player inventory indices 9-35, and
+// ids 54-62 are the hotbar, player inventory indices 0-8.
+pub struct ScreenHandler<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ScreenHandler<'a> {
+    pub const CLASS: &'static str = "net/minecraft/screen/ScreenHandler";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // The id the server assigned to the open container, sent back with every click.
+    pub fn get_sync_id(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "syncId", "I")?.i()?)
+    }
+
+    // Yarn name of the handler's runtime class, e.g. `GENERIC_CONTAINER_CLASS`. The game reports the
+    // obfuscated name, which is translated back through the mappings.
+    pub fn get_class_name(&self, env: &mut JNIEnv) -> Result<String> {
+        let class = env.get_object_class(&self.inner)?;
+        let name = JString::from(env.call_method(&class, "getName", "()Ljava/lang/String;", &[])?.l()?);
+        let binary_name: String = env.get_string(&name)?.into();
+        env.delete_local_ref(name)?;
+        env.delete_local_ref(class)?;
+        let name = binary_name.replace('.', "/");
+        Ok(mappings::unmap_class(&name).unwrap_or(name))
+    }
+
+    pub fn get_slot_count(&self, env: &mut JNIEnv) -> Result<usize> {
+        let slots = self.get_slot_list(env)?;
+        let count = slots.len(env)?;
+        env.delete_local_ref(slots.inner)?;
+        Ok(count)
+    }
+
+    pub fn get_slot<'local>(&self, env: &mut JNIEnv<'local>, id: usize) -> Result<Slot<'local>> {
+        let slots = self.get_slot_list(env)?;
+        let slot = slots.get(env, id);
+        env.delete_local_ref(slots.inner)?;
+        Ok(Slot::new(slot?))
+    }
+
+    // Calls `f` for every slot in id order, each slot's local reference is deleted afterwards.
+    pub fn for_each_slot<F>(&self, env: &mut JNIEnv, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, &Slot) -> Result<()>,
+    {
+        let slots = self.get_slot_list(env)?;
+        for id in 0..slots.len(env)? {
+            let slot = Slot::new(slots.get(env, id)?);
+            let result = f(env, &slot);
+            env.delete_local_ref(slot.inner)?;
+            result?;
+        }
+        env.delete_local_ref(slots.inner)?;
+        Ok(())
+    }
+
+    // The `slots` field is a `DefaultedList`, which is a regular `java/util/List`.
+    fn get_slot_list<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JavaList<'local>> {
+        let slots = remap::get_field(env, &self.inner, Self::CLASS, "slots", "Lnet/minecraft/util/collection/DefaultedList;")?.l()?;
+        Ok(JavaList::new(slots))
+    }
+}
+
+// Wrapper for `net/minecraft/screen/slot/Slot`.
+pub struct Slot<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Slot<'a> {
+    pub const CLASS: &'static str = "net/minecraft/screen/slot/Slot";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Position in the screen handler, see `ScreenHandler`.
+    pub fn get_id(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::get_field(env, &self.inner, Self::CLASS, "id", "I")?.i()?)
+    }
+
+    // Position in the backing inventory, see `ScreenHandler`.
+    pub fn get_index(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getIndex", "()I", &[])?.i()?)
+    }
+
+    pub fn has_stack(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "hasStack", "()Z", &[])?.z()?)
+    }
+
+    // Returns the stack in the slot, an empty slot holds the empty stack rather than null.
+    pub fn get_stack<'local>(&self, env: &mut JNIEnv<'local>) -> Result<ItemStack<'local>> {
+        let stack = remap::call_method(env, &self.inner, Self::CLASS, "getStack", "()Lnet/minecraft/item/ItemStack;", &[])?.l()?;
+        Ok(ItemStack::new(stack))
+    }
+}
@@ -0,0 +1,25 @@
+use jni::objects::{JObject, JString};
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/client/session/Session`, the account the client is logged in with.
+pub struct Session<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Session<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/session/Session";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_username(&self, env: &mut JNIEnv) -> Result<String> {
+        let username = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "getUsername", "()Ljava/lang/String;", &[])?.l()?);
+        let username_string: String = env.get_string(&username)?.into();
+        env.delete_local_ref(username)?;
+        Ok(username_string)
+    }
+}
@@ -0,0 +1,88 @@
+use jni::objects::{JObject, JString};
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// A status effect on an entity, copied out of its `StatusEffectInstance`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveEffect {
+    // Registry id of the effect, e.g. `minecraft:speed`.
+    pub id: String,
+    // Level of the effect minus one, `Speed II` has amplifier 1.
+    pub amplifier: i32,
+    // Remaining ticks, -1 for infinite effects.
+    pub duration: i32,
+}
+
+impl ActiveEffect {
+    // The level as shown in game, 1 for `Speed I`.
+    pub fn level(&self) -> i32 {
+        self.amplifier + 1
+    }
+}
+
+// Turns a translation key such as `effect.minecraft.speed` into the registry id `minecraft:speed`.
+fn effect_id_from_translation_key(key: &str) -> String {
+    let path = key.strip_prefix("effect.").unwrap_or(key);
+    path.replacen('.', ":", 1)
+}
+
+// Wrapper for `net/minecraft/entity/effect/StatusEffectInstance`.
+pub struct StatusEffectInstance<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> StatusEffectInstance<'a> {
+    pub const CLASS: &'static str = "net/minecraft/entity/effect/StatusEffectInstance";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_amplifier(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getAmplifier", "()I", &[])?.i()?)
+    }
+
+    pub fn get_duration(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getDuration", "()I", &[])?.i()?)
+    }
+
+    // Registry id of the effect. It is derived from the translation key (`effect.minecraft.speed`),
+    // which unlike the effect type's signature is the same in every supported version.
+    pub fn get_effect_id(&self, env: &mut JNIEnv) -> Result<String> {
+        let key = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "getTranslationKey", "()Ljava/lang/String;", &[])?.l()?);
+        let key_string: String = env.get_string(&key)?.into();
+        env.delete_local_ref(key)?;
+        Ok(effect_id_from_translation_key(&key_string))
+    }
+
+    pub fn to_active_effect(&self, env: &mut JNIEnv) -> Result<ActiveEffect> {
+        Ok(ActiveEffect {
+            id: self.get_effect_id(env)?,
+            amplifier: self.get_amplifier(env)?,
+            duration: self.get_duration(env)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn effect_ids_from_translation_keys() {
+        assert_eq!(effect_id_from_translation_key("effect.minecraft.speed"), "minecraft:speed");
+        assert_eq!(effect_id_from_translation_key("effect.minecraft.jump_boost"), "minecraft:jump_boost");
+        // Only the namespace separator is replaced.
+        assert_eq!(effect_id_from_translation_key("effect.mymod.a.b"), "mymod:a.b");
+    }
+
+    #[test]
+    fn level_is_the_amplifier_plus_one() {
+        let effect = |amplifier| ActiveEffect { id: "minecraft:speed".to_string(), amplifier, duration: -1 };
+        assert_eq!(effect(0).level(), 1);
+        assert_eq!(effect(1).level(), 2);
+        assert_eq!(effect(254).level(), 255);
+    }
+}
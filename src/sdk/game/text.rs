@@ -0,0 +1,40 @@
+use jni::objects::{JObject, JString, JValue};
+use jni::JNIEnv;
+
+use crate::sdk::jni::string::ToJava;
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/text/Text`, formatted text shown in chat, titles and tooltips.
+pub struct Text<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Text<'a> {
+    pub const CLASS: &'static str = "net/minecraft/text/Text";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Creates a plain text. Legacy `§` color codes in the string are rendered by the game.
+    pub fn literal(env: &mut JNIEnv<'a>, string: &str) -> Result<Self> {
+        let java_string = string.to_java(env)?;
+        let text = remap::call_static_method(
+            env,
+            "net/minecraft/text/Text",
+            "literal",
+            "(Ljava/lang/String;)Lnet/minecraft/text/MutableText;",
+            &[JValue::Object(&java_string)],
+        )?.l()?;
+        Ok(Self::new(text))
+    }
+
+    // Returns the text without formatting.
+    pub fn get_string(&self, env: &mut JNIEnv) -> Result<String> {
+        let string = JString::from(remap::call_method(env, &self.inner, Self::CLASS, "getString", "()Ljava/lang/String;", &[])?.l()?);
+        let rust_string: String = env.get_string(&string)?.into();
+        env.delete_local_ref(string)?;
+        Ok(rust_string)
+    }
+}
@@ -0,0 +1,27 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+use crate::sdk::math::DVec3;
+
+// Conversions between `net/minecraft/util/math/Vec3d` objects and plain Rust vectors.
+
+// Reads the components of a `Vec3d` object.
+pub fn to_dvec3(env: &mut JNIEnv, vec3d: &JObject) -> Result<DVec3> {
+    let x = remap::get_field(env, vec3d, "net/minecraft/util/math/Vec3d", "x", "D")?.d()?;
+    let y = remap::get_field(env, vec3d, "net/minecraft/util/math/Vec3d", "y", "D")?.d()?;
+    let z = remap::get_field(env, vec3d, "net/minecraft/util/math/Vec3d", "z", "D")?.d()?;
+    Ok(DVec3::new(x, y, z))
+}
+
+// Creates a new `Vec3d` object from a Rust vector.
+pub fn from_dvec3<'local>(env: &mut JNIEnv<'local>, vec: DVec3) -> Result<JObject<'local>> {
+    let vec3d = remap::new_object(
+        env,
+        "net/minecraft/util/math/Vec3d",
+        "(DDD)V",
+        &[JValue::Double(vec.x), JValue::Double(vec.y), JValue::Double(vec.z)],
+    )?;
+    Ok(vec3d)
+}
@@ -0,0 +1,50 @@
+use jni::objects::{JObject, JValue};
+use jni::JNIEnv;
+use windows::Win32::Foundation::HWND;
+
+use crate::sdk::jni::remap;
+use crate::sdk::Result;
+
+// Wrapper for `net/minecraft/client/util/Window`, the game window.
+pub struct Window<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> Window<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/util/Window";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the GLFW window pointer.
+    pub fn get_handle(&self, env: &mut JNIEnv) -> Result<i64> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getHandle", "()J", &[])?.j()?)
+    }
+
+    // Resolves the native Win32 window handle through LWJGL's `glfwGetWin32Window`.
+    pub fn get_hwnd(&self, env: &mut JNIEnv) -> Result<HWND> {
+        let handle = self.get_handle(env)?;
+        let hwnd = env.call_static_method(
+            "org/lwjgl/glfw/GLFWNativeWin32",
+            "glfwGetWin32Window",
+            "(J)J",
+            &[JValue::Long(handle)],
+        )?.j()?;
+        Ok(HWND(hwnd as isize))
+    }
+
+    // The GUI scale, the factor between GUI coordinates and physical pixels.
+    pub fn get_scale_factor(&self, env: &mut JNIEnv) -> Result<f64> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getScaleFactor", "()D", &[])?.d()?)
+    }
+
+    // Size of the framebuffer the game renders into, in physical pixels.
+    pub fn get_framebuffer_width(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getFramebufferWidth", "()I", &[])?.i()?)
+    }
+
+    pub fn get_framebuffer_height(&self, env: &mut JNIEnv) -> Result<i32> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getFramebufferHeight", "()I", &[])?.i()?)
+    }
+}
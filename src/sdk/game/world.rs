@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use jni::objects::{JMethodID, JObject, JValue};
+use jni::signature::{Primitive, ReturnType};
+use jni::JNIEnv;
+
+use super::block::{BlockPos, BlockState, KnownBlock};
+use super::identifier::Identifier;
+use super::registry::Registries;
+use super::entity::Entity;
+use crate::sdk::jni::collections::{JavaIterable, JavaIterator};
+use crate::sdk::jni::{cache, remap};
+use crate::sdk::Result;
+use crate::sdk::math::coords::Dimension;
+use crate::sdk::math::DVec3;
+
+// Distance between two sampled points along a path.
+const PATH_SAMPLE_STEP: f64 = 0.25;
+
+// Limits of `find_blocks`. A radius of 8 is already about 4900 block state queries.
+pub const MAX_SEARCH_RADIUS: i32 = 8;
+pub const MAX_SEARCH_RESULTS: usize = 64;
+
+// Most block states `distance_to_ground` queries in one call.
+pub const MAX_GROUND_QUERIES: usize = 64;
+
+// Method IDs used by the block search, resolved through the mappings once and then kept outside the
+// cache's lock, since a search makes thousands of calls.
+struct BlockScanIds {
+    is_chunk_loaded: JMethodID,
+    get_block_state: JMethodID,
+    get_block: JMethodID,
+    mutable_set: JMethodID,
+}
+
+static BLOCK_SCAN_IDS: OnceLock<BlockScanIds> = OnceLock::new();
+
+fn block_scan_ids(env: &mut JNIEnv) -> Result<&'static BlockScanIds> {
+    if let Some(ids) = BLOCK_SCAN_IDS.get() {
+        return Ok(ids);
+    }
+
+    let ids = BlockScanIds {
+        is_chunk_loaded: cache::method_id(env, "net/minecraft/world/World", "isChunkLoaded", "(II)Z")?,
+        get_block_state: cache::method_id(
+            env,
+            "net/minecraft/world/World",
+            "getBlockState",
+            "(Lnet/minecraft/util/math/BlockPos;)Lnet/minecraft/block/BlockState;",
+        )?,
+        get_block: cache::method_id(env, "net/minecraft/block/BlockState", "getBlock", "()Lnet/minecraft/block/Block;")?,
+        mutable_set: cache::method_id(
+            env,
+            "net/minecraft/util/math/BlockPos$Mutable",
+            "set",
+            "(III)Lnet/minecraft/util/math/BlockPos$Mutable;",
+        )?,
+    };
+
+    // Another thread may have won the race, either way the stored IDs are equivalent.
+    let _ = BLOCK_SCAN_IDS.set(ids);
+    Ok(BLOCK_SCAN_IDS.get().expect("block scan IDs were just initialized"))
+}
+
+// Wrapper for `net/minecraft/client/world/ClientWorld`, the world the client currently plays in.
+pub struct ClientWorld<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> ClientWorld<'a> {
+    pub const CLASS: &'static str = "net/minecraft/client/world/ClientWorld";
+
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn get_dimension(&self, env: &mut JNIEnv) -> Result<Dimension> {
+        let registry_key = remap::call_method(env, &self.inner, Self::CLASS, "getRegistryKey", "()Lnet/minecraft/registry/RegistryKey;", &[])?.l()?;
+        let id = Identifier::new(remap::call_method(env, &registry_key, "net/minecraft/registry/RegistryKey", "getValue", "()Lnet/minecraft/util/Identifier;", &[])?.l()?);
+        env.delete_local_ref(registry_key)?;
+
+        let id_string = id.to_string(env)?;
+        env.delete_local_ref(id.inner)?;
+        Ok(Dimension::from_id(&id_string))
+    }
+
+    // Ticks the world has existed for, which keeps counting while the game is paused in multiplayer.
+    pub fn get_time(&self, env: &mut JNIEnv) -> Result<i64> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "getTime", "()J", &[])?.j()?)
+    }
+
+    pub fn is_raining(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isRaining", "()Z", &[])?.z()?)
+    }
+
+    // Every entity the client knows of, the local player included. Elements are `Entity` objects.
+    pub fn get_entities<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JavaIterable<'local>> {
+        Ok(JavaIterable::new(remap::call_method(env, &self.inner, Self::CLASS, "getEntities", "()Ljava/lang/Iterable;", &[])?.l()?))
+    }
+
+    // Returns a cursor over every entity the client knows of. The caller deletes the cursor's iterator
+    // and every entity it returns, `for_each_entity` does both. Only iterate on the client thread, the
+    // list changes while the world ticks.
+    pub fn entities<'local>(&self, env: &mut JNIEnv<'local>) -> Result<EntityIterator<'local>> {
+        let iterable = self.get_entities(env)?;
+        let iterator = iterable.iter(env);
+        env.delete_local_ref(iterable.inner)?;
+        Ok(EntityIterator { inner: iterator? })
+    }
+
+    // Calls `f` for every entity. Each call runs in its own local frame, so references created while
+    // handling an entity are freed before the next one, however many entities the world holds.
+    pub fn for_each_entity<F>(&self, env: &mut JNIEnv, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, &Entity) -> Result<()>,
+    {
+        let entities = self.entities(env)?;
+        let result = loop {
+            let step = env.with_local_frame(16, |env| -> Result<bool> {
+                let Some(entity) = entities.next(env)? else {
+                    return Ok(false);
+                };
+                f(env, &entity)?;
+                Ok(true)
+            });
+            match step {
+                Ok(true) => {}
+                Ok(false) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        env.delete_local_ref(entities.inner.inner)?;
+        result
+    }
+
+    // Whether the chunk containing `pos` is loaded on the client.
+    pub fn is_chunk_loaded(&self, env: &mut JNIEnv, pos: BlockPos) -> Result<bool> {
+        Ok(remap::call_method(env, &self.inner, Self::CLASS, "isChunkLoaded", "(II)Z", &[JValue::Int(pos.x >> 4), JValue::Int(pos.z >> 4)])?.z()?)
+    }
+
+    pub fn get_block_state<'local>(&self, env: &mut JNIEnv<'local>, pos: BlockPos) -> Result<BlockState<'local>> {
+        let block_pos = pos.to_java(env)?;
+        let block_state = remap::call_method(
+            env,
+            &self.inner,
+            Self::CLASS,
+            "getBlockState",
+            "(Lnet/minecraft/util/math/BlockPos;)Lnet/minecraft/block/BlockState;",
+            &[JValue::Object(&block_pos)],
+        )?.l()?;
+        env.delete_local_ref(block_pos)?;
+        Ok(BlockState::new(block_state))
+    }
+
+    // Distance from `from` down to the top of the first block below it that is neither air nor
+    // liquid, treating every such block as a full cube. `None` if there is none within `max` blocks
+    // or `MAX_GROUND_QUERIES` queries, or if the chunk is not loaded.
+    pub fn distance_to_ground(&self, env: &mut JNIEnv, from: DVec3, max: f64) -> Result<Option<f64>> {
+        let start = BlockPos::of_floored(from);
+        if !self.is_chunk_loaded(env, start)? {
+            return Ok(None);
+        }
+
+        for step in 0..MAX_GROUND_QUERIES as i32 {
+            let pos = start.offset(0, -step, 0);
+            let distance = from.y - (pos.y + 1) as f64;
+            if distance > max {
+                return Ok(None);
+            }
+
+            let solid = env.with_local_frame(4, |env| -> Result<bool> {
+                let state = self.get_block_state(env, pos)?;
+                Ok(!state.is_air(env)? && !state.is_liquid(env)?)
+            })?;
+            if solid {
+                return Ok(Some(distance.max(0.0)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Finds blocks of the given kind in the cube of `radius` blocks around `center`, nearest first.
+    // The radius is capped at `MAX_SEARCH_RADIUS` and the result at `MAX_SEARCH_RESULTS`, and
+    // positions in chunks that are not loaded are skipped.
+    pub fn find_blocks(&self, env: &mut JNIEnv, center: BlockPos, radius: i32, block: KnownBlock) -> Result<Vec<BlockPos>> {
+        let radius = radius.clamp(0, MAX_SEARCH_RADIUS);
+        let ids = block_scan_ids(env)?;
+
+        let registry = Registries::block(env)?;
+        let target = registry.get_by_id(env, block.id())?;
+        env.delete_local_ref(registry.inner)?;
+        let Some(target) = target else {
+            return Ok(Vec::new());
+        };
+
+        // A single mutable position is reused for every query instead of allocating thousands.
+        let mutable_pos = remap::new_object(env, "net/minecraft/util/math/BlockPos$Mutable", "()V", &[])?;
+        let mut loaded_chunks = HashMap::new();
+        let mut found = Vec::new();
+
+        for x in -radius..=radius {
+            for z in -radius..=radius {
+                let column = center.offset(x, 0, z);
+                let chunk = (column.x >> 4, column.z >> 4);
+                let loaded = match loaded_chunks.get(&chunk) {
+                    Some(&loaded) => loaded,
+                    None => {
+                        // SAFETY: `isChunkLoaded(II)Z` takes the two chunk coordinates passed.
+                        let loaded = unsafe {
+                            env.call_method_unchecked(
+                                &self.inner,
+                                ids.is_chunk_loaded,
+                                ReturnType::Primitive(Primitive::Boolean),
+                                &[JValue::Int(chunk.0).as_jni(), JValue::Int(chunk.1).as_jni()],
+                            )
+                        }?.z()?;
+                        loaded_chunks.insert(chunk, loaded);
+                        loaded
+                    }
+                };
+                if !loaded {
+                    continue;
+                }
+
+                for y in -radius..=radius {
+                    let pos = column.offset(0, y, 0);
+                    if self.is_block_at(env, ids, &mutable_pos, pos, &target)? {
+                        found.push(pos);
+                    }
+                }
+            }
+        }
+
+        env.delete_local_ref(mutable_pos)?;
+        env.delete_local_ref(target)?;
+
+        found.sort_by_key(|pos| pos.distance_squared(center));
+        found.truncate(MAX_SEARCH_RESULTS);
+        Ok(found)
+    }
+
+    fn is_block_at(&self, env: &mut JNIEnv, ids: &BlockScanIds, mutable_pos: &JObject, pos: BlockPos, target: &JObject) -> Result<bool> {
+        // SAFETY: every method ID matches the receiver's class and the arguments passed, see `BlockScanIds`.
+        let (returned_pos, state) = unsafe {
+            let returned_pos = env.call_method_unchecked(
+                mutable_pos,
+                ids.mutable_set,
+                ReturnType::Object,
+                &[JValue::Int(pos.x).as_jni(), JValue::Int(pos.y).as_jni(), JValue::Int(pos.z).as_jni()],
+            )?.l()?;
+            let state = env.call_method_unchecked(&self.inner, ids.get_block_state, ReturnType::Object, &[JValue::Object(mutable_pos).as_jni()])?.l()?;
+            (returned_pos, state)
+        };
+        // SAFETY: `getBlock()` takes no arguments and returns an object.
+        let block = unsafe { env.call_method_unchecked(&state, ids.get_block, ReturnType::Object, &[]) }?.l()?;
+        let matches = env.is_same_object(&block, target)?;
+
+        env.delete_local_ref(block)?;
+        env.delete_local_ref(state)?;
+        env.delete_local_ref(returned_pos)?;
+        Ok(matches)
+    }
+
+    // Checks whether a point could move from `from` along `direction` for `distance` blocks without
+    // running into a block with a collision shape. The path is sampled every quarter block.
+    pub fn is_path_clear(&self, env: &mut JNIEnv, from: DVec3, direction: DVec3, distance: f64) -> Result<bool> {
+        let direction = direction.normalize();
+        let samples = (distance / PATH_SAMPLE_STEP).ceil() as usize;
+        let mut last_block = None;
+
+        for i in 1..=samples {
+            let point = from + direction * (i as f64 * PATH_SAMPLE_STEP).min(distance);
+
+            // Neighbouring samples mostly hit the same block, which only needs to be checked once.
+            let block = (point.x.floor() as i32, point.y.floor() as i32, point.z.floor() as i32);
+            if last_block == Some(block) {
+                continue;
+            }
+            last_block = Some(block);
+
+            let blocked = env.with_local_frame(8, |env| -> Result<bool> {
+                let block_pos = remap::call_static_method(
+                    env,
+                    "net/minecraft/util/math/BlockPos",
+                    "ofFloored",
+                    "(DDD)Lnet/minecraft/util/math/BlockPos;",
+                    &[JValue::Double(point.x), JValue::Double(point.y), JValue::Double(point.z)],
+                )?.l()?;
+
+                let block_state = remap::call_method(
+                    env,
+                    &self.inner,
+                    Self::CLASS,
+                    "getBlockState",
+                    "(Lnet/minecraft/util/math/BlockPos;)Lnet/minecraft/block/BlockState;",
+                    &[JValue::Object(&block_pos)],
+                )?.l()?;
+
+                let collision_shape = remap::call_method(
+                    env,
+                    &block_state,
+                    "net/minecraft/block/BlockState",
+                    "getCollisionShape",
+                    "(Lnet/minecraft/world/BlockView;Lnet/minecraft/util/math/BlockPos;)Lnet/minecraft/util/shape/VoxelShape;",
+                    &[JValue::Object(&self.inner), JValue::Object(&block_pos)],
+                )?.l()?;
+
+                Ok(!remap::call_method(env, &collision_shape, "net/minecraft/util/shape/VoxelShape", "isEmpty", "()Z", &[])?.z()?)
+            })?;
+
+            if blocked {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+// Cursor over the entities of a world, see `ClientWorld::entities`. Like `JavaIterator` it is not a Rust
+// `Iterator`, every step needs the JNI environment.
+pub struct EntityIterator<'a> {
+    pub inner: JavaIterator<'a>,
+}
+
+impl<'a> EntityIterator<'a> {
+    // Returns the next entity as a new local reference, or `None` once every entity was returned.
+    pub fn next<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<Entity<'local>>> {
+        Ok(self.inner.next(env)?.map(Entity::new))
+    }
+}
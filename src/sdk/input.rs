@@ -0,0 +1,72 @@
+use anyhow::{bail, Result};
+use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::System::SystemServices::{MK_LBUTTON, MK_MBUTTON, MK_RBUTTON};
+use windows::Win32::UI::Input::KeyboardAndMouse::{MapVirtualKeyA, MAPVK_VK_TO_VSC};
+use windows::Win32::UI::WindowsAndMessaging::{
+    IsWindow, PostMessageA, WM_KEYDOWN, WM_KEYUP, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN,
+    WM_MBUTTONUP, WM_MOUSEMOVE, WM_RBUTTONDOWN, WM_RBUTTONUP,
+};
+
+// Injects input as window messages into the Minecraft window, for interactions that cannot be
+// expressed through key bindings (e.g. clicking a button in a server GUI).
+//
+// Messages are posted directly to the window, so the game does not need to be focused and the user's
+// real cursor and keyboard are left alone. GLFW handles posted messages like real input: key messages
+// reach the focused screen (e.g. typing into the chat screen works while tabbed out), and mouse clicks
+// land at the last position reported through `WM_MOUSEMOVE`, which is why clicks move the cursor first.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+// Packs two 16-bit coordinates into an `LPARAM` like the `MAKELPARAM` macro.
+fn make_lparam(low: i32, high: i32) -> LPARAM {
+    LPARAM((((high & 0xFFFF) << 16) | (low & 0xFFFF)) as isize)
+}
+
+fn ensure_window(window_handle: HWND) -> Result<()> {
+    if !unsafe { IsWindow(window_handle) }.as_bool() {
+        bail!("The game window {:?} does not exist", window_handle);
+    }
+    Ok(())
+}
+
+// Sends a key press or release of the virtual key code `vk`. GLFW reads the scan code from the
+// message, so it is filled in along with the transition flags.
+pub fn send_key(window_handle: HWND, vk: u32, down: bool) -> Result<()> {
+    ensure_window(window_handle)?;
+
+    let scan_code = unsafe { MapVirtualKeyA(vk, MAPVK_VK_TO_VSC) } as isize;
+    let mut lparam = 1 | (scan_code << 16);
+    if !down {
+        // Previous key state and transition state are both set for key releases.
+        lparam |= (1 << 30) | (1 << 31);
+    }
+
+    let message = if down { WM_KEYDOWN } else { WM_KEYUP };
+    unsafe { PostMessageA(window_handle, message, WPARAM(vk as usize), LPARAM(lparam)) }?;
+    Ok(())
+}
+
+// Clicks at the GUI coordinates `x`, `y`. GUI coordinates are scaled by the GUI scale, so they are
+// multiplied by `scale_factor` (see `Window::get_scale_factor`) to get physical window pixels.
+pub fn send_mouse_click(window_handle: HWND, x: f64, y: f64, button: MouseButton, scale_factor: f64) -> Result<()> {
+    ensure_window(window_handle)?;
+
+    let position = make_lparam((x * scale_factor) as i32, (y * scale_factor) as i32);
+    let (down_message, up_message, modifier) = match button {
+        MouseButton::Left => (WM_LBUTTONDOWN, WM_LBUTTONUP, MK_LBUTTON),
+        MouseButton::Right => (WM_RBUTTONDOWN, WM_RBUTTONUP, MK_RBUTTON),
+        MouseButton::Middle => (WM_MBUTTONDOWN, WM_MBUTTONUP, MK_MBUTTON),
+    };
+
+    unsafe {
+        PostMessageA(window_handle, WM_MOUSEMOVE, WPARAM(0), position)?;
+        PostMessageA(window_handle, down_message, WPARAM(modifier.0 as usize), position)?;
+        PostMessageA(window_handle, up_message, WPARAM(0), position)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::sync::OnceLock;
+
+use jni::objects::{GlobalRef, JClass, JMethodID, JObject, JStaticMethodID, JValue};
+use jni::signature::{Primitive, ReturnType};
+use jni::JNIEnv;
+
+use crate::sdk::{Error, Result};
+
+// Method IDs of the boxing and unboxing methods, resolved once. The classes are kept as global
+// references because static methods are called on them.
+struct BoxingIds {
+    integer_class: GlobalRef,
+    integer_value_of: JStaticMethodID,
+    double_class: GlobalRef,
+    double_value_of: JStaticMethodID,
+    boolean_class: GlobalRef,
+    boolean_value_of: JStaticMethodID,
+    int_value: JMethodID,
+    double_value: JMethodID,
+    boolean_value: JMethodID,
+}
+
+static BOXING_IDS: OnceLock<BoxingIds> = OnceLock::new();
+
+fn boxing_ids(env: &mut JNIEnv) -> Result<&'static BoxingIds> {
+    if let Some(ids) = BOXING_IDS.get() {
+        return Ok(ids);
+    }
+
+    let integer_class = env.find_class("java/lang/Integer")?;
+    let double_class = env.find_class("java/lang/Double")?;
+    let boolean_class = env.find_class("java/lang/Boolean")?;
+
+    // `intValue` and `doubleValue` are resolved on `Number`, so they also unbox other numeric types.
+    let ids = BoxingIds {
+        integer_value_of: env.get_static_method_id(&integer_class, "valueOf", "(I)Ljava/lang/Integer;")?,
+        double_value_of: env.get_static_method_id(&double_class, "valueOf", "(D)Ljava/lang/Double;")?,
+        boolean_value_of: env.get_static_method_id(&boolean_class, "valueOf", "(Z)Ljava/lang/Boolean;")?,
+        int_value: env.get_method_id("java/lang/Number", "intValue", "()I")?,
+        double_value: env.get_method_id("java/lang/Number", "doubleValue", "()D")?,
+        boolean_value: env.get_method_id(&boolean_class, "booleanValue", "()Z")?,
+        integer_class: env.new_global_ref(&integer_class)?,
+        double_class: env.new_global_ref(&double_class)?,
+        boolean_class: env.new_global_ref(&boolean_class)?,
+    };
+    env.delete_local_ref(integer_class)?;
+    env.delete_local_ref(double_class)?;
+    env.delete_local_ref(boolean_class)?;
+
+    // Another thread may have won the race, either way the stored IDs are equivalent.
+    let _ = BOXING_IDS.set(ids);
+    Ok(BOXING_IDS.get().expect("boxing IDs were just initialized"))
+}
+
+fn ensure_not_null(object: &JObject, context: &str) -> Result<()> {
+    if object.is_null() {
+        return Err(Error::null_object(context));
+    }
+    Ok(())
+}
+
+fn box_value<'local>(
+    env: &mut JNIEnv<'local>,
+    class: &GlobalRef,
+    value_of: JStaticMethodID,
+    value: JValue,
+) -> Result<JObject<'local>> {
+    let class: &JClass = class.as_obj().into();
+    // SAFETY: the method ID belongs to `class` and takes exactly the single primitive passed.
+    let boxed = unsafe { env.call_static_method_unchecked(class, value_of, ReturnType::Object, &[value.as_jni()]) }?.l()?;
+    Ok(boxed)
+}
+
+// Unboxes a `java/lang/Integer` (or any other `java/lang/Number`).
+pub fn unbox_int(env: &mut JNIEnv, object: &JObject) -> Result<i32> {
+    ensure_not_null(object, "unbox_int")?;
+    let ids = boxing_ids(env)?;
+    // SAFETY: `intValue()I` takes no arguments and returns an int.
+    let value = unsafe { env.call_method_unchecked(object, ids.int_value, ReturnType::Primitive(Primitive::Int), &[]) }?.i()?;
+    Ok(value)
+}
+
+// Unboxes a `java/lang/Double` (or any other `java/lang/Number`).
+pub fn unbox_double(env: &mut JNIEnv, object: &JObject) -> Result<f64> {
+    ensure_not_null(object, "unbox_double")?;
+    let ids = boxing_ids(env)?;
+    // SAFETY: `doubleValue()D` takes no arguments and returns a double.
+    let value = unsafe { env.call_method_unchecked(object, ids.double_value, ReturnType::Primitive(Primitive::Double), &[]) }?.d()?;
+    Ok(value)
+}
+
+// Unboxes a `java/lang/Boolean`.
+pub fn unbox_bool(env: &mut JNIEnv, object: &JObject) -> Result<bool> {
+    ensure_not_null(object, "unbox_bool")?;
+    let ids = boxing_ids(env)?;
+    // SAFETY: `booleanValue()Z` takes no arguments and returns a boolean.
+    let value = unsafe { env.call_method_unchecked(object, ids.boolean_value, ReturnType::Primitive(Primitive::Boolean), &[]) }?.z()?;
+    Ok(value)
+}
+
+// Boxes an int into a `java/lang/Integer`.
+pub fn box_int<'local>(env: &mut JNIEnv<'local>, value: i32) -> Result<JObject<'local>> {
+    let ids = boxing_ids(env)?;
+    box_value(env, &ids.integer_class, ids.integer_value_of, JValue::Int(value))
+}
+
+// Boxes a double into a `java/lang/Double`.
+pub fn box_double<'local>(env: &mut JNIEnv<'local>, value: f64) -> Result<JObject<'local>> {
+    let ids = boxing_ids(env)?;
+    box_value(env, &ids.double_class, ids.double_value_of, JValue::Double(value))
+}
+
+// Boxes a bool into a `java/lang/Boolean`.
+pub fn box_bool<'local>(env: &mut JNIEnv<'local>, value: bool) -> Result<JObject<'local>> {
+    let ids = boxing_ids(env)?;
+    box_value(env, &ids.boolean_class, ids.boolean_value_of, JValue::Bool(value.into()))
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+use jni::objects::{GlobalRef, JClass, JFieldID, JMethodID, JStaticFieldID, JStaticMethodID};
+use jni::JNIEnv;
+
+use crate::sdk::mappings;
+use crate::sdk::{Error, Result};
+
+// Classes, method IDs and field IDs resolved once and reused for the rest of the session. Minecraft's
+// classes are never unloaded while the game runs, so the IDs stay valid. Lookups by name walk the
+// class hierarchy on every call, which adds up in code that runs every tick.
+//
+// Everything is keyed by yarn names and translated through the installed mappings on the first lookup.
+// Method and field IDs are keyed by the class they are looked up on, so IDs resolved on a subclass
+// are cached separately from those of its superclass, which JNI allows using interchangeably.
+type MemberKey = (String, String, String);
+
+static CLASSES: Mutex<BTreeMap<String, GlobalRef>> = Mutex::new(BTreeMap::new());
+static METHODS: Mutex<BTreeMap<MemberKey, JMethodID>> = Mutex::new(BTreeMap::new());
+static STATIC_METHODS: Mutex<BTreeMap<MemberKey, JStaticMethodID>> = Mutex::new(BTreeMap::new());
+static FIELDS: Mutex<BTreeMap<MemberKey, JFieldID>> = Mutex::new(BTreeMap::new());
+static STATIC_FIELDS: Mutex<BTreeMap<MemberKey, JStaticFieldID>> = Mutex::new(BTreeMap::new());
+
+fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn key(class: &str, name: &str, signature: &str) -> MemberKey {
+    (class.to_string(), name.to_string(), signature.to_string())
+}
+
+// Returns a global reference to the class, looking it up the first time.
+pub fn class(env: &mut JNIEnv, name: &str) -> Result<GlobalRef> {
+    if let Some(class) = lock(&CLASSES).get(name) {
+        return Ok(class.clone());
+    }
+
+    let local = env.find_class(mappings::class(name)).map_err(|_| Error::class_not_found(env, name))?;
+    let global = env.new_global_ref(&local)?;
+    env.delete_local_ref(local)?;
+
+    // The lock is not held across the lookup, another thread may have cached the class meanwhile.
+    Ok(lock(&CLASSES).entry(name.to_string()).or_insert(global).clone())
+}
+
+pub fn method_id(env: &mut JNIEnv, class_name: &str, name: &str, signature: &str) -> Result<JMethodID> {
+    let key = key(class_name, name, signature);
+    if let Some(&id) = lock(&METHODS).get(&key) {
+        return Ok(id);
+    }
+
+    let class = class(env, class_name)?;
+    let (name, signature) = remap_method(class_name, name, signature);
+    let id = env.get_method_id(<&JClass>::from(class.as_obj()), name, signature).map_err(|e| member_error(env, e, class_name))?;
+    lock(&METHODS).insert(key, id);
+    Ok(id)
+}
+
+pub fn static_method_id(env: &mut JNIEnv, class_name: &str, name: &str, signature: &str) -> Result<JStaticMethodID> {
+    let key = key(class_name, name, signature);
+    if let Some(&id) = lock(&STATIC_METHODS).get(&key) {
+        return Ok(id);
+    }
+
+    let class = class(env, class_name)?;
+    let (name, signature) = remap_method(class_name, name, signature);
+    let id = env.get_static_method_id(<&JClass>::from(class.as_obj()), name, signature).map_err(|e| member_error(env, e, class_name))?;
+    lock(&STATIC_METHODS).insert(key, id);
+    Ok(id)
+}
+
+pub fn field_id(env: &mut JNIEnv, class_name: &str, name: &str, signature: &str) -> Result<JFieldID> {
+    let key = key(class_name, name, signature);
+    if let Some(&id) = lock(&FIELDS).get(&key) {
+        return Ok(id);
+    }
+
+    let class = class(env, class_name)?;
+    let (name, signature) = remap_field(class_name, name, signature);
+    let id = env.get_field_id(<&JClass>::from(class.as_obj()), name, signature).map_err(|e| member_error(env, e, class_name))?;
+    lock(&FIELDS).insert(key, id);
+    Ok(id)
+}
+
+pub fn static_field_id(env: &mut JNIEnv, class_name: &str, name: &str, signature: &str) -> Result<JStaticFieldID> {
+    let key = key(class_name, name, signature);
+    if let Some(&id) = lock(&STATIC_FIELDS).get(&key) {
+        return Ok(id);
+    }
+
+    let class = class(env, class_name)?;
+    let (name, signature) = remap_field(class_name, name, signature);
+    let id = env.get_static_field_id(<&JClass>::from(class.as_obj()), name, signature).map_err(|e| member_error(env, e, class_name))?;
+    lock(&STATIC_FIELDS).insert(key, id);
+    Ok(id)
+}
+
+fn remap_method(class_name: &str, name: &str, signature: &str) -> (String, String) {
+    match mappings::installed() {
+        Some(mappings) => mappings.method(class_name, name, signature),
+        None => (name.to_string(), signature.to_string()),
+    }
+}
+
+fn remap_field(class_name: &str, name: &str, signature: &str) -> (String, String) {
+    match mappings::installed() {
+        Some(mappings) => mappings.field(class_name, name, signature),
+        None => (name.to_string(), signature.to_string()),
+    }
+}
+
+// Number of cached classes, methods and fields (instance and static), for diagnostics.
+pub fn sizes() -> (usize, usize, usize) {
+    (
+        lock(&CLASSES).len(),
+        lock(&METHODS).len() + lock(&STATIC_METHODS).len(),
+        lock(&FIELDS).len() + lock(&STATIC_FIELDS).len(),
+    )
+}
+
+// Fills in the class a failed member lookup was made on, which the JNI error does not know.
+fn member_error(env: &mut JNIEnv, error: jni::errors::Error, class_name: &str) -> Error {
+    match Error::from(error).with_exception(env) {
+        Error::MethodNotFound { method, sig, .. } => Error::MethodNotFound { class: class_name.to_string(), method, sig },
+        Error::FieldNotFound { field, sig, .. } => Error::FieldNotFound { class: class_name.to_string(), field, sig },
+        error => error,
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+
+// Writer for the few Java classes the client defines at runtime, e.g. the packet interceptor. The
+// classes are small enough to assemble by hand, which avoids shipping compiled class files or needing
+// a Java compiler for the build.
+//
+// Classes are written as version 49 (Java 5). Those are checked by the type-inferring verifier and
+// need no `StackMapTable`, so branching bytecode can be written without computing stack maps.
+pub const CLASS_VERSION: u16 = 49;
+
+pub const ACC_PUBLIC: u16 = 0x0001;
+pub const ACC_PRIVATE: u16 = 0x0002;
+pub const ACC_STATIC: u16 = 0x0008;
+pub const ACC_SUPER: u16 = 0x0020;
+pub const ACC_NATIVE: u16 = 0x0100;
+
+// Opcodes used by the generated classes.
+pub mod op {
+    pub const ALOAD_0: u8 = 0x2a;
+    pub const ALOAD_1: u8 = 0x2b;
+    pub const ALOAD_2: u8 = 0x2c;
+    pub const ALOAD_3: u8 = 0x2d;
+    pub const POP: u8 = 0x57;
+    pub const IFNE: u8 = 0x9a;
+    pub const RETURN: u8 = 0xb1;
+    pub const INVOKESPECIAL: u8 = 0xb7;
+    pub const INVOKESTATIC: u8 = 0xb8;
+    pub const INVOKEINTERFACE: u8 = 0xb9;
+}
+
+const TAG_UTF8: u8 = 1;
+const TAG_CLASS: u8 = 7;
+const TAG_METHOD_REF: u8 = 10;
+const TAG_INTERFACE_METHOD_REF: u8 = 11;
+const TAG_NAME_AND_TYPE: u8 = 12;
+
+struct Method {
+    access: u16,
+    name: u16,
+    descriptor: u16,
+    // Max stack, max locals and bytecode, `None` for native methods.
+    code: Option<(u16, u16, Vec<u8>)>,
+}
+
+pub struct ClassWriter {
+    constants: Vec<u8>,
+    constant_count: u16,
+    indices: HashMap<(u8, Vec<u8>), u16>,
+    this_class: u16,
+    super_class: u16,
+    methods: Vec<Method>,
+}
+
+impl ClassWriter {
+    // Starts a public class `name` extending `super_name`, both in internal form (`java/lang/Object`).
+    pub fn new(name: &str, super_name: &str) -> Self {
+        let mut writer = Self {
+            constants: Vec::new(),
+            constant_count: 1,
+            indices: HashMap::new(),
+            this_class: 0,
+            super_class: 0,
+            methods: Vec::new(),
+        };
+        writer.this_class = writer.class(name);
+        writer.super_class = writer.class(super_name);
+        writer
+    }
+
+    pub fn utf8(&mut self, value: &str) -> u16 {
+        let mut data = (value.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(value.as_bytes());
+        self.constant(TAG_UTF8, data)
+    }
+
+    pub fn class(&mut self, name: &str) -> u16 {
+        let name = self.utf8(name);
+        self.constant(TAG_CLASS, name.to_be_bytes().to_vec())
+    }
+
+    pub fn method_ref(&mut self, owner: &str, name: &str, descriptor: &str) -> u16 {
+        self.member_ref(TAG_METHOD_REF, owner, name, descriptor)
+    }
+
+    pub fn interface_method_ref(&mut self, owner: &str, name: &str, descriptor: &str) -> u16 {
+        self.member_ref(TAG_INTERFACE_METHOD_REF, owner, name, descriptor)
+    }
+
+    // Adds a method with the given bytecode. The code must not throw or catch, there is no exception table.
+    pub fn method(&mut self, access: u16, name: &str, descriptor: &str, max_stack: u16, max_locals: u16, code: Vec<u8>) {
+        let (name, descriptor) = (self.utf8(name), self.utf8(descriptor));
+        self.methods.push(Method { access, name, descriptor, code: Some((max_stack, max_locals, code)) });
+    }
+
+    pub fn native_method(&mut self, access: u16, name: &str, descriptor: &str) {
+        let (name, descriptor) = (self.utf8(name), self.utf8(descriptor));
+        self.methods.push(Method { access: access | ACC_NATIVE, name, descriptor, code: None });
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        let code_name = self.utf8("Code");
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&0xCAFEBABE_u32.to_be_bytes());
+        out.extend_from_slice(&0_u16.to_be_bytes());
+        out.extend_from_slice(&CLASS_VERSION.to_be_bytes());
+        out.extend_from_slice(&self.constant_count.to_be_bytes());
+        out.extend_from_slice(&self.constants);
+        out.extend_from_slice(&(ACC_PUBLIC | ACC_SUPER).to_be_bytes());
+        out.extend_from_slice(&self.this_class.to_be_bytes());
+        out.extend_from_slice(&self.super_class.to_be_bytes());
+        // No interfaces and no fields.
+        out.extend_from_slice(&0_u16.to_be_bytes());
+        out.extend_from_slice(&0_u16.to_be_bytes());
+
+        out.extend_from_slice(&(self.methods.len() as u16).to_be_bytes());
+        for method in &self.methods {
+            out.extend_from_slice(&method.access.to_be_bytes());
+            out.extend_from_slice(&method.name.to_be_bytes());
+            out.extend_from_slice(&method.descriptor.to_be_bytes());
+            let Some((max_stack, max_locals, code)) = &method.code else {
+                out.extend_from_slice(&0_u16.to_be_bytes());
+                continue;
+            };
+
+            out.extend_from_slice(&1_u16.to_be_bytes());
+            out.extend_from_slice(&code_name.to_be_bytes());
+            // Stack and locals, code length, the code, and empty exception table and attributes.
+            out.extend_from_slice(&(12 + code.len() as u32).to_be_bytes());
+            out.extend_from_slice(&max_stack.to_be_bytes());
+            out.extend_from_slice(&max_locals.to_be_bytes());
+            out.extend_from_slice(&(code.len() as u32).to_be_bytes());
+            out.extend_from_slice(code);
+            out.extend_from_slice(&0_u16.to_be_bytes());
+            out.extend_from_slice(&0_u16.to_be_bytes());
+        }
+
+        // No class attributes.
+        out.extend_from_slice(&0_u16.to_be_bytes());
+        out
+    }
+
+    fn member_ref(&mut self, tag: u8, owner: &str, name: &str, descriptor: &str) -> u16 {
+        let owner = self.class(owner);
+        let (name, descriptor) = (self.utf8(name), self.utf8(descriptor));
+        let name_and_type = self.constant(TAG_NAME_AND_TYPE, [name.to_be_bytes(), descriptor.to_be_bytes()].concat());
+        self.constant(tag, [owner.to_be_bytes(), name_and_type.to_be_bytes()].concat())
+    }
+
+    // Adds a constant pool entry, reusing an equal one.
+    fn constant(&mut self, tag: u8, data: Vec<u8>) -> u16 {
+        if let Some(&index) = self.indices.get(&(tag, data.clone())) {
+            return index;
+        }
+
+        let index = self.constant_count;
+        self.constants.push(tag);
+        self.constants.extend_from_slice(&data);
+        self.constant_count += 1;
+        self.indices.insert((tag, data), index);
+        index
+    }
+}
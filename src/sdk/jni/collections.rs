@@ -0,0 +1,245 @@
+use std::sync::OnceLock;
+
+use jni::objects::{JMethodID, JObject, JValue};
+use jni::signature::{Primitive, ReturnType};
+use jni::JNIEnv;
+
+use crate::sdk::Result;
+
+// Method IDs of the collection interfaces, resolved once. Interface method IDs dispatch to
+// whatever implementation the object has, so they work for every list, set and map.
+struct CollectionIds {
+    size: JMethodID,
+    iterator: JMethodID,
+    iterable_iterator: JMethodID,
+    list_get: JMethodID,
+    has_next: JMethodID,
+    next: JMethodID,
+    entry_set: JMethodID,
+    get_key: JMethodID,
+    get_value: JMethodID,
+}
+
+static COLLECTION_IDS: OnceLock<CollectionIds> = OnceLock::new();
+
+fn collection_ids(env: &mut JNIEnv) -> Result<&'static CollectionIds> {
+    if let Some(ids) = COLLECTION_IDS.get() {
+        return Ok(ids);
+    }
+
+    let ids = CollectionIds {
+        size: env.get_method_id("java/util/Collection", "size", "()I")?,
+        iterator: env.get_method_id("java/util/Collection", "iterator", "()Ljava/util/Iterator;")?,
+        iterable_iterator: env.get_method_id("java/lang/Iterable", "iterator", "()Ljava/util/Iterator;")?,
+        list_get: env.get_method_id("java/util/List", "get", "(I)Ljava/lang/Object;")?,
+        has_next: env.get_method_id("java/util/Iterator", "hasNext", "()Z")?,
+        next: env.get_method_id("java/util/Iterator", "next", "()Ljava/lang/Object;")?,
+        entry_set: env.get_method_id("java/util/Map", "entrySet", "()Ljava/util/Set;")?,
+        get_key: env.get_method_id("java/util/Map$Entry", "getKey", "()Ljava/lang/Object;")?,
+        get_value: env.get_method_id("java/util/Map$Entry", "getValue", "()Ljava/lang/Object;")?,
+    };
+
+    let _ = COLLECTION_IDS.set(ids);
+    Ok(COLLECTION_IDS.get().expect("collection IDs were just initialized"))
+}
+
+fn call_object<'local>(env: &mut JNIEnv<'local>, object: &JObject, method: JMethodID, args: &[JValue]) -> Result<JObject<'local>> {
+    let args: Vec<_> = args.iter().map(|arg| arg.as_jni()).collect();
+    // SAFETY: every cached method ID returning an object is called with the arguments of its signature.
+    Ok(unsafe { env.call_method_unchecked(object, method, ReturnType::Object, &args) }?.l()?)
+}
+
+fn collection_size(env: &mut JNIEnv, collection: &JObject) -> Result<usize> {
+    let ids = collection_ids(env)?;
+    // SAFETY: `size()I` takes no arguments and returns an int.
+    let size = unsafe { env.call_method_unchecked(collection, ids.size, ReturnType::Primitive(Primitive::Int), &[]) }?.i()?;
+    Ok(size.max(0) as usize)
+}
+
+// Cursor over a `java/util/Iterator`. It is not a Rust `Iterator` because every step needs the JNI
+// environment, which the caller also needs while handling an element. Elements are local references
+// owned by the caller, who has to delete them (or use `for_each`, which does it).
+pub struct JavaIterator<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> JavaIterator<'a> {
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns the next element, or `None` once the iterator is exhausted.
+    pub fn next<'local>(&self, env: &mut JNIEnv<'local>) -> Result<Option<JObject<'local>>> {
+        let ids = collection_ids(env)?;
+        // SAFETY: `hasNext()Z` takes no arguments and returns a boolean.
+        let has_next = unsafe { env.call_method_unchecked(&self.inner, ids.has_next, ReturnType::Primitive(Primitive::Boolean), &[]) }?.z()?;
+        if !has_next {
+            return Ok(None);
+        }
+        Ok(Some(call_object(env, &self.inner, ids.next, &[])?))
+    }
+
+    // Calls `f` for every remaining element and deletes each element's local reference afterwards.
+    pub fn for_each<F>(&self, env: &mut JNIEnv, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, &JObject) -> Result<()>,
+    {
+        while let Some(element) = self.next(env)? {
+            let result = f(env, &element);
+            env.delete_local_ref(element)?;
+            result?;
+        }
+        Ok(())
+    }
+}
+
+// Wrapper for a `java/lang/Iterable` that is not necessarily a collection, so it has no size.
+pub struct JavaIterable<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> JavaIterable<'a> {
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn iter<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JavaIterator<'local>> {
+        let ids = collection_ids(env)?;
+        Ok(JavaIterator::new(call_object(env, &self.inner, ids.iterable_iterator, &[])?))
+    }
+
+    // Calls `f` for every element and cleans up each element's local reference afterwards.
+    pub fn for_each<F>(&self, env: &mut JNIEnv, f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, &JObject) -> Result<()>,
+    {
+        let iterator = self.iter(env)?;
+        let result = iterator.for_each(env, f);
+        env.delete_local_ref(iterator.inner)?;
+        result
+    }
+}
+
+// Wrapper for any `java/util/Collection`.
+pub struct JavaCollection<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> JavaCollection<'a> {
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn len(&self, env: &mut JNIEnv) -> Result<usize> {
+        collection_size(env, &self.inner)
+    }
+
+    pub fn is_empty(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(self.len(env)? == 0)
+    }
+
+    pub fn iter<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JavaIterator<'local>> {
+        let ids = collection_ids(env)?;
+        Ok(JavaIterator::new(call_object(env, &self.inner, ids.iterator, &[])?))
+    }
+
+    // Calls `f` for every element and cleans up each element's local reference afterwards.
+    pub fn for_each<F>(&self, env: &mut JNIEnv, f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, &JObject) -> Result<()>,
+    {
+        let iterator = self.iter(env)?;
+        let result = iterator.for_each(env, f);
+        env.delete_local_ref(iterator.inner)?;
+        result
+    }
+}
+
+// Wrapper for a `java/util/List`, which also allows indexed access.
+pub struct JavaList<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> JavaList<'a> {
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    pub fn len(&self, env: &mut JNIEnv) -> Result<usize> {
+        collection_size(env, &self.inner)
+    }
+
+    pub fn is_empty(&self, env: &mut JNIEnv) -> Result<bool> {
+        Ok(self.len(env)? == 0)
+    }
+
+    // Returns the element at `index` as a new local reference.
+    pub fn get<'local>(&self, env: &mut JNIEnv<'local>, index: usize) -> Result<JObject<'local>> {
+        let ids = collection_ids(env)?;
+        call_object(env, &self.inner, ids.list_get, &[JValue::Int(index as i32)])
+    }
+
+    pub fn iter<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JavaIterator<'local>> {
+        let ids = collection_ids(env)?;
+        Ok(JavaIterator::new(call_object(env, &self.inner, ids.iterator, &[])?))
+    }
+
+    // Calls `f` with the index and element of every entry and cleans up each element's local reference afterwards.
+    pub fn for_each<F>(&self, env: &mut JNIEnv, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, usize, &JObject) -> Result<()>,
+    {
+        for index in 0..self.len(env)? {
+            let element = self.get(env, index)?;
+            let result = f(env, index, &element);
+            env.delete_local_ref(element)?;
+            result?;
+        }
+        Ok(())
+    }
+}
+
+// Wrapper for a `java/util/Map`.
+pub struct JavaMap<'a> {
+    pub inner: JObject<'a>,
+}
+
+impl<'a> JavaMap<'a> {
+    pub fn new(inner: JObject<'a>) -> Self {
+        Self { inner }
+    }
+
+    // Returns a cursor over the `Map.Entry` objects of the map, see `entry_key_value` to split them.
+    pub fn entries<'local>(&self, env: &mut JNIEnv<'local>) -> Result<JavaIterator<'local>> {
+        let ids = collection_ids(env)?;
+        let entry_set = call_object(env, &self.inner, ids.entry_set, &[])?;
+        let iterator = call_object(env, &entry_set, ids.iterator, &[])?;
+        env.delete_local_ref(entry_set)?;
+        Ok(JavaIterator::new(iterator))
+    }
+
+    // Calls `f` with the key and value of every entry and cleans up all local references of the entry afterwards.
+    pub fn for_each_entry<F>(&self, env: &mut JNIEnv, mut f: F) -> Result<()>
+    where
+        F: FnMut(&mut JNIEnv, &JObject, &JObject) -> Result<()>,
+    {
+        let entries = self.entries(env)?;
+        let result = entries.for_each(env, |env, entry| {
+            let (key, value) = entry_key_value(env, entry)?;
+            let result = f(env, &key, &value);
+            env.delete_local_ref(key)?;
+            env.delete_local_ref(value)?;
+            result
+        });
+        env.delete_local_ref(entries.inner)?;
+        result
+    }
+}
+
+// Splits a `Map.Entry` into its key and value.
+pub fn entry_key_value<'local>(env: &mut JNIEnv<'local>, entry: &JObject) -> Result<(JObject<'local>, JObject<'local>)> {
+    let ids = collection_ids(env)?;
+    let key = call_object(env, entry, ids.get_key, &[])?;
+    let value = call_object(env, entry, ids.get_value, &[])?;
+    Ok((key, value))
+}
@@ -0,0 +1,8 @@
+// Helpers for working with plain Java types (boxed primitives, collections, strings) that Minecraft
+// methods take and return, independent of any Minecraft class.
+pub mod boxing;
+pub mod cache;
+pub mod class_file;
+pub mod collections;
+pub mod remap;
+pub mod string;
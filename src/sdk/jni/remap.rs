@@ -0,0 +1,117 @@
+use std::str::FromStr;
+
+use jni::objects::{JClass, JObject, JValue, JValueOwned};
+use jni::signature::{JavaType, ReturnType, TypeSignature};
+use jni::JNIEnv;
+
+use crate::sdk::jni::cache;
+use crate::sdk::{Error, Result};
+
+// By-name JNI calls on Minecraft classes, the counterparts of the `JNIEnv` methods of the same name.
+// Names are written in yarn and go through the installed mappings and the cache, where the `JNIEnv`
+// methods would pass them to the JVM as they are and fail on an obfuscated client. `class` is the
+// class the member is looked up on: the wrapper's own class, which the mappings fall back from to
+// the declaring class when the member is inherited.
+//
+// Library classes (`java/...`, Netty, JOML, LWJGL) are never obfuscated and keep using `JNIEnv`.
+
+pub fn call_method<'local>(
+    env: &mut JNIEnv<'local>,
+    object: &JObject,
+    class: &str,
+    name: &str,
+    signature: &str,
+    args: &[JValue],
+) -> Result<JValueOwned<'local>> {
+    let ret = checked_return_type(signature, args)?;
+    let method_id = cache::method_id(env, class, name, signature)?;
+    let args: Vec<_> = args.iter().map(|arg| arg.as_jni()).collect();
+    // SAFETY: the number and types of the arguments were checked against the signature the method was
+    // resolved with.
+    Ok(unsafe { env.call_method_unchecked(object, method_id, ret, &args) }?)
+}
+
+pub fn call_static_method<'local>(env: &mut JNIEnv<'local>, class: &str, name: &str, signature: &str, args: &[JValue]) -> Result<JValueOwned<'local>> {
+    let ret = checked_return_type(signature, args)?;
+    let method_id = cache::static_method_id(env, class, name, signature)?;
+    let class_ref = cache::class(env, class)?;
+    let args: Vec<_> = args.iter().map(|arg| arg.as_jni()).collect();
+    // SAFETY: as in `call_method`.
+    Ok(unsafe { env.call_static_method_unchecked(<&JClass>::from(class_ref.as_obj()), method_id, ret, &args) }?)
+}
+
+pub fn new_object<'local>(env: &mut JNIEnv<'local>, class: &str, signature: &str, args: &[JValue]) -> Result<JObject<'local>> {
+    checked_return_type(signature, args)?;
+    let constructor_id = cache::method_id(env, class, "<init>", signature)?;
+    let class_ref = cache::class(env, class)?;
+    let args: Vec<_> = args.iter().map(|arg| arg.as_jni()).collect();
+    // SAFETY: as in `call_method`.
+    Ok(unsafe { env.new_object_unchecked(<&JClass>::from(class_ref.as_obj()), constructor_id, &args) }?)
+}
+
+pub fn get_field<'local>(env: &mut JNIEnv<'local>, object: &JObject, class: &str, name: &str, signature: &str) -> Result<JValueOwned<'local>> {
+    let field_id = cache::field_id(env, class, name, signature)?;
+    Ok(env.get_field_unchecked(object, field_id, field_type(signature)?)?)
+}
+
+pub fn set_field(env: &mut JNIEnv, object: &JObject, class: &str, name: &str, signature: &str, value: JValue) -> Result<()> {
+    let field_id = cache::field_id(env, class, name, signature)?;
+    Ok(env.set_field_unchecked(object, field_id, value)?)
+}
+
+pub fn get_static_field<'local>(env: &mut JNIEnv<'local>, class: &str, name: &str, signature: &str) -> Result<JValueOwned<'local>> {
+    let field_id = cache::static_field_id(env, class, name, signature)?;
+    let class_ref = cache::class(env, class)?;
+    let ty = JavaType::from_str(signature)?;
+    Ok(env.get_static_field_unchecked(<&JClass>::from(class_ref.as_obj()), field_id, ty)?)
+}
+
+pub fn is_instance_of(env: &mut JNIEnv, object: &JObject, class: &str) -> Result<bool> {
+    let class_ref = cache::class(env, class)?;
+    Ok(env.is_instance_of(object, <&JClass>::from(class_ref.as_obj()))?)
+}
+
+// The return type of a method signature, after checking that the arguments match its parameters in
+// number and type, as the by-name calls do. Calls through IDs skip those checks, and a wrong count or
+// a primitive passed for an object would corrupt the stack or be dereferenced as a pointer.
+fn checked_return_type(signature: &str, args: &[JValue]) -> Result<ReturnType> {
+    let parsed = TypeSignature::from_str(signature)?;
+    let types_match = parsed.args.len() == args.len()
+        && parsed.args.iter().zip(args).all(|(expected, arg)| match expected {
+            JavaType::Primitive(primitive) => arg.primitive_type() == Some(*primitive),
+            JavaType::Object(_) | JavaType::Array(_) => arg.primitive_type().is_none(),
+            JavaType::Method(_) => false,
+        });
+    if !types_match {
+        return Err(Error::from(jni::errors::Error::InvalidArgList(parsed)));
+    }
+    Ok(parsed.ret)
+}
+
+fn field_type(signature: &str) -> Result<ReturnType> {
+    Ok(ReturnType::from_str(signature)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jni::signature::Primitive;
+
+    #[test]
+    fn arguments_matching_the_signature_are_accepted() {
+        let ret = checked_return_type("(IZLjava/lang/String;[D)V", &[JValue::Int(1), JValue::Bool(1), JValue::Object(&JObject::null()), JValue::Object(&JObject::null())]);
+        assert!(matches!(ret, Ok(ReturnType::Primitive(Primitive::Void))));
+    }
+
+    #[test]
+    fn arguments_of_the_wrong_type_are_rejected() {
+        assert!(checked_return_type("(I)V", &[JValue::Long(1)]).is_err());
+        assert!(checked_return_type("(Ljava/lang/Object;)V", &[JValue::Int(1)]).is_err());
+        assert!(checked_return_type("(D)V", &[JValue::Object(&JObject::null())]).is_err());
+    }
+
+    #[test]
+    fn wrong_argument_count_is_rejected() {
+        assert!(checked_return_type("(II)V", &[JValue::Int(1)]).is_err());
+    }
+}
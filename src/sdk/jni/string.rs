@@ -0,0 +1,26 @@
+use jni::objects::{AutoLocal, JObject};
+use jni::JNIEnv;
+
+use crate::sdk::Result;
+
+// Converts a Rust value into a Java object for use as a method argument. The returned reference
+// deletes itself when dropped, so callers cannot leak it into the local reference table:
+//
+//     let message = "hello".to_java(env)?;
+//     env.call_method(&handler, "sendChatMessage", "(Ljava/lang/String;)V", &[JValue::Object(&message)])?;
+pub trait ToJava {
+    fn to_java<'local>(&self, env: &mut JNIEnv<'local>) -> Result<AutoLocal<'local, JObject<'local>>>;
+}
+
+impl ToJava for str {
+    fn to_java<'local>(&self, env: &mut JNIEnv<'local>) -> Result<AutoLocal<'local, JObject<'local>>> {
+        let string = JObject::from(env.new_string(self)?);
+        Ok(env.auto_local(string))
+    }
+}
+
+impl ToJava for String {
+    fn to_java<'local>(&self, env: &mut JNIEnv<'local>) -> Result<AutoLocal<'local, JObject<'local>>> {
+        self.as_str().to_java(env)
+    }
+}
@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+
+use jni::objects::{JByteArray, JObject, JString, JValue};
+use jni::JNIEnv;
+use tracing::info;
+
+use crate::sdk::jni::string::ToJava;
+use crate::sdk::mappings::{Mappings, MappingsError, MappingsSource, RUNTIME_NAMESPACE};
+use crate::sdk::version::json_string;
+use crate::sdk::Result;
+
+// Downloads the Yarn mappings matching the running game from Fabric's maven, so they no longer have
+// to be shipped next to the game. The HTTP requests and the jar are handled by the game's own JVM,
+// which already has everything needed for it. Downloads are cached per game version.
+
+const META_URL: &str = "https://meta.fabricmc.net/v2/versions/yarn";
+const MAVEN_URL: &str = "https://maven.fabricmc.net/net/fabricmc/yarn";
+// Suffix of the jar with the merged tiny v2 file. Only the merged one has the `official` namespace a
+// vanilla client runs with, the plain `-v2` jar maps between intermediary and named.
+const JAR_SUFFIX: &str = "mergedv2";
+// Path of the tiny v2 file inside the jar.
+const JAR_ENTRY: &str = "mappings/mappings.tiny";
+
+// Loads the mappings for the game version, e.g. `1.21.1`, downloading them on first use.
+pub fn load_for(env: &mut JNIEnv, game_version: &str) -> std::result::Result<Mappings, MappingsError> {
+    let path = cached_path(game_version);
+    if path.is_file() {
+        return Mappings::load_from(&path);
+    }
+
+    let yarn_version = latest_yarn_version(env, game_version)?;
+    let url = format!("{}/{}/yarn-{}-{}.jar", MAVEN_URL, yarn_version, yarn_version, JAR_SUFFIX);
+    info!("Downloading Yarn {} from {}", yarn_version, url);
+
+    let jar = fetch(env, &url).map_err(|e| download_error(env, &url, e))?;
+    let tiny = extract_entry(env, &jar, JAR_ENTRY)
+        .map_err(|e| download_error(env, &url, e))?
+        .ok_or_else(|| MappingsError::Download { url: url.clone(), reason: format!("{} is missing from the jar", JAR_ENTRY) })?;
+
+    // Checked before caching, a file without the runtime namespace would fail every later start too.
+    let mappings = parse_downloaded(&tiny, &path)?;
+    let io_error = |source| MappingsError::Io { path: path.clone(), source };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    std::fs::write(&path, tiny).map_err(io_error)?;
+    info!("Cached Yarn {} at {}", yarn_version, path.display());
+
+    Ok(mappings)
+}
+
+// Parses a downloaded tiny file into the mappings it will be loaded as from `path`.
+fn parse_downloaded(tiny: &[u8], path: &Path) -> std::result::Result<Mappings, MappingsError> {
+    let text = std::str::from_utf8(tiny).map_err(|_| MappingsError::NotUtf8)?;
+    Mappings::parse(text, RUNTIME_NAMESPACE, MappingsSource::File(path.to_path_buf()))
+}
+
+// Directory downloaded mappings are kept in, `%APPDATA%\LiquidBounceLite\mappings` on Windows.
+pub fn cache_dir() -> PathBuf {
+    std::env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("LiquidBounceLite")
+        .join("mappings")
+}
+
+pub fn cached_path(game_version: &str) -> PathBuf {
+    cache_dir().join(format!("yarn-{}.tiny", game_version))
+}
+
+// Newest Yarn build for the game version, e.g. `1.21.1+build.3`. Fabric's meta lists builds newest first.
+fn latest_yarn_version(env: &mut JNIEnv, game_version: &str) -> std::result::Result<String, MappingsError> {
+    let url = format!("{}/{}", META_URL, game_version);
+    let json = fetch(env, &url).map_err(|e| download_error(env, &url, e))?;
+    let json = String::from_utf8(json).map_err(|_| MappingsError::NotUtf8)?;
+    json_string(&json, "version").ok_or_else(|| MappingsError::UnknownGameVersion(format!("no Yarn build for {}", game_version)))
+}
+
+fn download_error(env: &mut JNIEnv, url: &str, error: crate::sdk::Error) -> MappingsError {
+    MappingsError::Download { url: url.to_string(), reason: error.with_exception(env).to_string() }
+}
+
+// Reads the whole response of a GET request to `url`.
+fn fetch(env: &mut JNIEnv, url: &str) -> Result<Vec<u8>> {
+    env.with_local_frame(8, |env| -> Result<Vec<u8>> {
+        let url = url.to_java(env)?;
+        let uri = env
+            .call_static_method("java/net/URI", "create", "(Ljava/lang/String;)Ljava/net/URI;", &[JValue::Object(&url)])?
+            .l()?;
+        let url = env.call_method(&uri, "toURL", "()Ljava/net/URL;", &[])?.l()?;
+        let stream = env.call_method(&url, "openStream", "()Ljava/io/InputStream;", &[])?.l()?;
+        read_stream(env, &stream)
+    })
+}
+
+// Returns the contents of the zip entry `name`, or `None` if the archive has no such entry.
+fn extract_entry(env: &mut JNIEnv, archive: &[u8], name: &str) -> Result<Option<Vec<u8>>> {
+    env.with_local_frame(16, |env| -> Result<Option<Vec<u8>>> {
+        let bytes = env.byte_array_from_slice(archive)?;
+        let input = env.new_object("java/io/ByteArrayInputStream", "([B)V", &[JValue::Object(&bytes)])?;
+        let zip = env.new_object("java/util/zip/ZipInputStream", "(Ljava/io/InputStream;)V", &[JValue::Object(&input)])?;
+
+        let mut contents = None;
+        loop {
+            let entry = env.call_method(&zip, "getNextEntry", "()Ljava/util/zip/ZipEntry;", &[])?.l()?;
+            if entry.is_null() {
+                break;
+            }
+
+            let java_name = JString::from(env.call_method(&entry, "getName", "()Ljava/lang/String;", &[])?.l()?);
+            let entry_name: String = env.get_string(&java_name)?.into();
+            env.delete_local_ref(java_name)?;
+            env.delete_local_ref(entry)?;
+            if entry_name == name {
+                let data = env.call_method(&zip, "readAllBytes", "()[B", &[])?.l()?;
+                contents = Some(env.convert_byte_array(JByteArray::from(data))?);
+                break;
+            }
+        }
+
+        env.call_method(&zip, "close", "()V", &[])?;
+        Ok(contents)
+    })
+}
+
+// Reads the stream to its end and closes it.
+fn read_stream(env: &mut JNIEnv, stream: &JObject) -> Result<Vec<u8>> {
+    let data = env.call_method(stream, "readAllBytes", "()[B", &[])?.l()?;
+    let bytes = env.convert_byte_array(JByteArray::from(data))?;
+    env.call_method(stream, "close", "()V", &[])?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERGED_V2: &str = "tiny\t2\t0\tofficial\tintermediary\tnamed\n\
+                             c\tdwv\tnet/minecraft/class_310\tnet/minecraft/client/MinecraftClient\n";
+    const V2: &str = "tiny\t2\t0\tintermediary\tnamed\n\
+                      c\tnet/minecraft/class_310\tnet/minecraft/client/MinecraftClient\n";
+
+    #[test]
+    fn merged_jar_maps_to_the_runtime_namespace() {
+        let mappings = parse_downloaded(MERGED_V2.as_bytes(), Path::new("yarn.tiny")).unwrap();
+        assert_eq!(mappings.namespace, RUNTIME_NAMESPACE);
+        assert_eq!(mappings.class("net/minecraft/client/MinecraftClient"), "dwv");
+    }
+
+    #[test]
+    fn plain_jar_is_rejected() {
+        let error = parse_downloaded(V2.as_bytes(), Path::new("yarn.tiny")).unwrap_err();
+        assert!(matches!(error, MappingsError::MissingNamespace(namespace) if namespace == RUNTIME_NAMESPACE));
+    }
+}
@@ -0,0 +1,546 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use jni::JNIEnv;
+
+// Yarn mappings translating the names the SDK is written against into the names the running game
+// uses. Without them names are looked up as they are, which only works in a development environment.
+pub mod download;
+pub mod tiny;
+
+// Environment variable overriding where the mappings file is read from.
+pub const PATH_VARIABLE: &str = "LIQUIDBOUNCE_MAPPINGS";
+// File read next to the game executable when nothing else is configured and there is no
+// `mappings-<version>.tiny` for the running version.
+pub const DEFAULT_FILE_NAME: &str = "mappings.tiny";
+// Namespace the SDK names are written in.
+pub const SOURCE_NAMESPACE: &str = "named";
+// Namespace of a vanilla client, which is what the DLL gets injected into.
+pub const RUNTIME_NAMESPACE: &str = "official";
+
+// Tiny files embedded at compile time with the `embed-mappings` feature by game version, see
+// `build.rs`. An empty version is used for any game version without its own set.
+#[cfg(feature = "embed-mappings")]
+const EMBEDDED: &[(&str, &[u8])] = include!(concat!(env!("OUT_DIR"), "/embedded_mappings.rs"));
+#[cfg(not(feature = "embed-mappings"))]
+const EMBEDDED: &[(&str, &[u8])] = &[];
+
+// Every Minecraft class and member the sources look up with literal names, collected by `build.rs`.
+pub const REQUIRED: &[Requirement] = include!(concat!(env!("OUT_DIR"), "/required_members.rs"));
+
+static INSTALLED: OnceLock<Mappings> = OnceLock::new();
+
+#[derive(Debug, thiserror::Error)]
+pub enum MappingsError {
+    #[error("No mappings embedded and none found at {}", path.display())]
+    NotFound { path: PathBuf },
+    #[error("Failed to read mappings from {}: {source}", path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("Mappings are not valid UTF-8")]
+    NotUtf8,
+    #[error("Invalid tiny mappings at line {line}: {reason}")]
+    Parse { line: usize, reason: String },
+    #[error("Mappings have no {0} namespace")]
+    MissingNamespace(String),
+    #[error("Cannot determine the mappings for the running game: {0}")]
+    UnknownGameVersion(String),
+    #[error("Failed to download mappings from {url}: {reason}")]
+    Download { url: String, reason: String },
+    #[error(transparent)]
+    Jvm(#[from] crate::sdk::Error),
+}
+
+// Where the installed mappings came from, for the startup banner and diagnostics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MappingsSource {
+    Embedded(String),
+    File(PathBuf),
+}
+
+impl std::fmt::Display for MappingsSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MappingsSource::Embedded(version) if version.is_empty() => write!(f, "embedded"),
+            MappingsSource::Embedded(version) => write!(f, "embedded {}", version),
+            MappingsSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+// A class or member the SDK looks up by its source name. Descriptors are left out where the sources
+// do not spell them out, any member of that name then counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Requirement {
+    Class(&'static str),
+    Method { class: &'static str, name: &'static str, descriptor: Option<&'static str> },
+    Field { class: &'static str, name: &'static str, descriptor: Option<&'static str> },
+}
+
+impl Requirement {
+    pub fn class(&self) -> &'static str {
+        match *self {
+            Requirement::Class(class) | Requirement::Method { class, .. } | Requirement::Field { class, .. } => class,
+        }
+    }
+}
+
+impl std::fmt::Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Requirement::Class(class) => write!(f, "class {}", class),
+            Requirement::Method { class, name, descriptor } => write!(f, "method {}.{}{}", class, name, descriptor.unwrap_or("")),
+            Requirement::Field { class, name, descriptor } => write!(f, "field {}.{}:{}", class, name, descriptor.unwrap_or("?")),
+        }
+    }
+}
+
+// Which of the required classes and members the mappings have no entry for.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Coverage {
+    pub classes: usize,
+    pub methods: usize,
+    pub fields: usize,
+    // Missing classes first, members of missing classes are not listed again.
+    pub missing: Vec<Requirement>,
+}
+
+impl Coverage {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+
+    fn missing_count(&self, kind: fn(&Requirement) -> bool) -> usize {
+        self.missing.iter().filter(|requirement| kind(requirement)).count()
+    }
+}
+
+impl std::fmt::Display for Coverage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} of {} required classes, {} of {} methods and {} of {} fields missing",
+            self.missing_count(|requirement| matches!(requirement, Requirement::Class(_))),
+            self.classes,
+            self.missing_count(|requirement| matches!(requirement, Requirement::Method { .. })),
+            self.methods,
+            self.missing_count(|requirement| matches!(requirement, Requirement::Field { .. })),
+            self.fields
+        )
+    }
+}
+
+// Class, method and field names from `SOURCE_NAMESPACE` to the target namespace. Member keys use the
+// owner, name and descriptor in source names, as the SDK passes them to JNI.
+//
+// The inverted tables go the other way, from names the game reports at runtime (class names,
+// exception stack traces) back to yarn. Their keys are all in target names.
+#[derive(Debug)]
+pub struct Mappings {
+    pub source: MappingsSource,
+    pub namespace: String,
+    classes: HashMap<String, String>,
+    methods: HashMap<(String, String, String), String>,
+    fields: HashMap<(String, String, String), String>,
+    // Target names of members by name and descriptor alone, `None` where the owners disagree.
+    methods_by_name: HashMap<(String, String), Option<String>>,
+    fields_by_name: HashMap<(String, String), Option<String>>,
+    unmapped_classes: HashMap<String, String>,
+    unmapped_methods: HashMap<(String, String, String), String>,
+    unmapped_fields: HashMap<(String, String, String), String>,
+    // Source names of methods by owner and name alone, for stack traces which have no descriptors.
+    // `None` where overloads have different source names.
+    unmapped_method_names: HashMap<(String, String), Option<String>>,
+}
+
+impl Mappings {
+    // Loads the mappings for the game version, e.g. `1.21.1`. Embedded mappings for that version are
+    // preferred, then unversioned embedded ones, then the first file of `configured_paths` that exists.
+    pub fn load(game_version: &str) -> Result<Self, MappingsError> {
+        let embedded = EMBEDDED
+            .iter()
+            .find(|(version, _)| *version == game_version)
+            .or_else(|| EMBEDDED.iter().find(|(version, _)| version.is_empty()));
+        if let Some((version, bytes)) = embedded {
+            let text = std::str::from_utf8(bytes).map_err(|_| MappingsError::NotUtf8)?;
+            return Self::parse(text, RUNTIME_NAMESPACE, MappingsSource::Embedded(version.to_string()));
+        }
+
+        let paths = configured_paths(game_version);
+        let path = paths.iter().find(|path| path.is_file()).unwrap_or(&paths[paths.len() - 1]);
+        Self::load_from(path)
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, MappingsError> {
+        let text = std::fs::read_to_string(path).map_err(|source| match source.kind() {
+            std::io::ErrorKind::NotFound => MappingsError::NotFound { path: path.to_path_buf() },
+            std::io::ErrorKind::InvalidData => MappingsError::NotUtf8,
+            _ => MappingsError::Io { path: path.to_path_buf(), source },
+        })?;
+        Self::parse(&text, RUNTIME_NAMESPACE, MappingsSource::File(path.to_path_buf()))
+    }
+
+    // Parses a tiny v2 file, mapping from `SOURCE_NAMESPACE` to `namespace`.
+    pub fn parse(text: &str, namespace: &str, source: MappingsSource) -> Result<Self, MappingsError> {
+        let file = tiny::parse(text)?;
+        let from = file.namespace_index(SOURCE_NAMESPACE)?;
+        let to = file.namespace_index(namespace)?;
+
+        // Member descriptors are written in the first namespace and have to be translated first.
+        let first_to_source: HashMap<&str, &str> = file.classes.iter().map(|class| (class.name(0), class.name(from))).collect();
+        let first_to_target: HashMap<&str, &str> = file.classes.iter().map(|class| (class.name(0), class.name(to))).collect();
+
+        let mut mappings = Self {
+            source,
+            namespace: namespace.to_string(),
+            classes: HashMap::new(),
+            methods: HashMap::new(),
+            fields: HashMap::new(),
+            methods_by_name: HashMap::new(),
+            fields_by_name: HashMap::new(),
+            unmapped_classes: HashMap::new(),
+            unmapped_methods: HashMap::new(),
+            unmapped_fields: HashMap::new(),
+            unmapped_method_names: HashMap::new(),
+        };
+        for class in &file.classes {
+            let owner = class.name(from).to_string();
+            let target_owner = class.name(to).to_string();
+            mappings.classes.insert(owner.clone(), target_owner.clone());
+            mappings.unmapped_classes.insert(target_owner.clone(), owner.clone());
+
+            for member in &class.members {
+                let descriptor = remap_descriptor(&member.descriptor, |name| first_to_source.get(name).copied());
+                let target_descriptor = remap_descriptor(&member.descriptor, |name| first_to_target.get(name).copied());
+                let key = (owner.clone(), member.name(from).to_string(), descriptor);
+                let target = member.name(to).to_string();
+                let target_key = (target_owner.clone(), target.clone(), target_descriptor);
+                let (by_owner, by_name, unmapped) = match member.kind {
+                    tiny::MemberKind::Method => (&mut mappings.methods, &mut mappings.methods_by_name, &mut mappings.unmapped_methods),
+                    tiny::MemberKind::Field => (&mut mappings.fields, &mut mappings.fields_by_name, &mut mappings.unmapped_fields),
+                };
+
+                if member.kind == tiny::MemberKind::Method {
+                    mappings
+                        .unmapped_method_names
+                        .entry((target_owner.clone(), target.clone()))
+                        .and_modify(|existing| {
+                            if existing.as_deref() != Some(key.1.as_str()) {
+                                *existing = None;
+                            }
+                        })
+                        .or_insert_with(|| Some(key.1.clone()));
+                }
+                unmapped.insert(target_key, key.1.clone());
+
+                by_name
+                    .entry((key.1.clone(), key.2.clone()))
+                    .and_modify(|existing| {
+                        if existing.as_deref() != Some(target.as_str()) {
+                            *existing = None;
+                        }
+                    })
+                    .or_insert_with(|| Some(target.clone()));
+                by_owner.insert(key, target);
+            }
+        }
+        Ok(mappings)
+    }
+
+    pub fn class_count(&self) -> usize {
+        self.classes.len()
+    }
+
+    pub fn method_count(&self) -> usize {
+        self.methods.len()
+    }
+
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    // Resolves every requirement against the mappings. A member counts as mapped when a lookup on
+    // its class would find it, including the fallback to the name and descriptor alone.
+    pub fn coverage(&self, required: &[Requirement]) -> Coverage {
+        let classes: BTreeSet<&str> = required.iter().map(Requirement::class).collect();
+        let members: BTreeSet<&Requirement> = required.iter().filter(|requirement| !matches!(requirement, Requirement::Class(_))).collect();
+
+        let mut coverage = Coverage { classes: classes.len(), ..Coverage::default() };
+        coverage.missing.extend(classes.iter().filter(|class| !self.classes.contains_key(**class)).map(|class| Requirement::Class(class)));
+        for &&requirement in &members {
+            let mapped = match requirement {
+                Requirement::Class(_) => continue,
+                Requirement::Method { class, name, descriptor } => {
+                    coverage.methods += 1;
+                    Self::has_member(&self.methods, &self.methods_by_name, class, name, descriptor)
+                }
+                Requirement::Field { class, name, descriptor } => {
+                    coverage.fields += 1;
+                    Self::has_member(&self.fields, &self.fields_by_name, class, name, descriptor)
+                }
+            };
+            if !mapped && self.classes.contains_key(requirement.class()) {
+                coverage.missing.push(requirement);
+            }
+        }
+        coverage
+    }
+
+    // Target name of the class, or the name itself for classes outside the mappings (the JDK).
+    pub fn class<'a>(&'a self, name: &'a str) -> &'a str {
+        self.classes.get(name).map_or(name, String::as_str)
+    }
+
+    // Target name and descriptor of a method. Members are mapped on their declaring class, so a method
+    // looked up on a subclass falls back to the name and descriptor alone when that is unambiguous.
+    pub fn method(&self, owner: &str, name: &str, descriptor: &str) -> (String, String) {
+        let target = Self::member(&self.methods, &self.methods_by_name, owner, name, descriptor);
+        (target.unwrap_or(name).to_string(), self.descriptor(descriptor))
+    }
+
+    pub fn field(&self, owner: &str, name: &str, descriptor: &str) -> (String, String) {
+        let target = Self::member(&self.fields, &self.fields_by_name, owner, name, descriptor);
+        (target.unwrap_or(name).to_string(), self.descriptor(descriptor))
+    }
+
+    // Translates every class name in a method or field descriptor.
+    pub fn descriptor(&self, descriptor: &str) -> String {
+        remap_descriptor(descriptor, |name| self.classes.get(name).map(String::as_str))
+    }
+
+    // Source name of a class given its target name, e.g. `net/minecraft/client/MinecraftClient` for
+    // `fgo`. `None` for classes outside the mappings.
+    pub fn unmap_class(&self, name: &str) -> Option<&str> {
+        self.unmapped_classes.get(name).map(String::as_str)
+    }
+
+    // Source name of a method given its declaring class, name and descriptor in target names.
+    pub fn unmap_method(&self, owner: &str, name: &str, descriptor: &str) -> Option<&str> {
+        self.unmapped_methods.get(&(owner.to_string(), name.to_string(), descriptor.to_string())).map(String::as_str)
+    }
+
+    pub fn unmap_field(&self, owner: &str, name: &str, descriptor: &str) -> Option<&str> {
+        self.unmapped_fields.get(&(owner.to_string(), name.to_string(), descriptor.to_string())).map(String::as_str)
+    }
+
+    // Rewrites the obfuscated class and method names in a Java stack trace to source names: the
+    // exception classes of the header and `Caused by:` lines and the `at` frames. Methods are only
+    // renamed where their name alone is unambiguous on the class.
+    pub fn unmap_stacktrace(&self, stacktrace: &str) -> String {
+        let mut result = String::with_capacity(stacktrace.len());
+        for line in stacktrace.lines() {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let line = line.trim_start();
+            result.push_str(indent);
+            if let Some(frame) = line.strip_prefix("at ") {
+                result.push_str("at ");
+                result.push_str(&self.unmap_frame(frame));
+            } else {
+                let (prefix, rest) = line.strip_prefix("Caused by: ").map_or(("", line), |rest| ("Caused by: ", rest));
+                let end = rest.find(':').unwrap_or(rest.len());
+                result.push_str(prefix);
+                result.push_str(&self.unmap_binary_name(&rest[..end]));
+                result.push_str(&rest[end..]);
+            }
+            result.push('\n');
+        }
+        result
+    }
+
+    // Source name of a class given as `Class.getName` reports it, with dots, or the name unchanged.
+    pub fn unmap_binary_name(&self, name: &str) -> String {
+        match self.unmap_class(&name.replace('.', "/")) {
+            Some(source) => source.replace('/', "."),
+            None => name.to_string(),
+        }
+    }
+
+    // A frame like `fgo.a(SourceFile:123)`. Frames of JDK modules (`java.base/...`) are never mapped.
+    fn unmap_frame(&self, frame: &str) -> String {
+        let (method, location) = frame.split_at(frame.find('(').unwrap_or(frame.len()));
+        let Some((owner, name)) = method.rsplit_once('.') else {
+            return frame.to_string();
+        };
+        if owner.contains('/') {
+            return frame.to_string();
+        }
+
+        let target_owner = owner.replace('.', "/");
+        let Some(source_owner) = self.unmap_class(&target_owner) else {
+            return frame.to_string();
+        };
+        let source_name = match self.unmapped_method_names.get(&(target_owner, name.to_string())) {
+            Some(Some(source_name)) => source_name.as_str(),
+            _ => name,
+        };
+        format!("{}.{}{}", source_owner.replace('/', "."), source_name, location)
+    }
+
+    fn has_member(
+        by_owner: &HashMap<(String, String, String), String>,
+        by_name: &HashMap<(String, String), Option<String>>,
+        owner: &str,
+        name: &str,
+        descriptor: Option<&str>,
+    ) -> bool {
+        match descriptor {
+            Some(descriptor) => Self::member(by_owner, by_name, owner, name, descriptor).is_some(),
+            None => by_owner.keys().any(|(member_owner, member_name, _)| member_owner == owner && member_name == name),
+        }
+    }
+
+    fn member<'a>(
+        by_owner: &'a HashMap<(String, String, String), String>,
+        by_name: &'a HashMap<(String, String), Option<String>>,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Option<&'a str> {
+        by_owner
+            .get(&(owner.to_string(), name.to_string(), descriptor.to_string()))
+            .or_else(|| by_name.get(&(name.to_string(), descriptor.to_string()))?.as_ref())
+            .map(String::as_str)
+    }
+}
+
+// Paths the mappings file is looked for at when none are embedded, in order: the path in
+// `LIQUIDBOUNCE_MAPPINGS` alone if set, otherwise `mappings-<version>.tiny` and `mappings.tiny` next
+// to the game executable.
+pub fn configured_paths(game_version: &str) -> Vec<PathBuf> {
+    if let Some(path) = std::env::var_os(PATH_VARIABLE) {
+        return vec![PathBuf::from(path)];
+    }
+
+    let dir = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf)).unwrap_or_default();
+    vec![dir.join(format!("mappings-{}.tiny", game_version)), dir.join(DEFAULT_FILE_NAME)]
+}
+
+// Makes the mappings available to the SDK for the rest of the session. Returns false if some were
+// installed already, those stay in use.
+pub fn install(mappings: Mappings) -> bool {
+    INSTALLED.set(mappings).is_ok()
+}
+
+pub fn installed() -> Option<&'static Mappings> {
+    INSTALLED.get()
+}
+
+// Checks the installed mappings against the classes and members the SDK needs, `REQUIRED` normally.
+// `None` without mappings, where names are looked up as they are.
+pub fn check_coverage(required: &[Requirement]) -> Option<Coverage> {
+    Some(installed()?.coverage(required))
+}
+
+// Whether the game's classes carry yarn names, as in a development environment, so no mappings are
+// needed. Leaves no exception pending.
+pub fn uses_yarn_names(env: &mut JNIEnv) -> bool {
+    match env.find_class("net/minecraft/client/MinecraftClient") {
+        Ok(class) => {
+            let _ = env.delete_local_ref(class);
+            true
+        }
+        Err(_) => {
+            let _ = env.exception_clear();
+            false
+        }
+    }
+}
+
+// Translates a class name through the installed mappings, leaving it as it is without any.
+pub fn class(name: &str) -> String {
+    installed().map_or(name, |mappings| mappings.class(name)).to_string()
+}
+
+// Source name of a class the game reported, the reverse of `class`. `None` without mappings or for
+// classes outside them.
+pub fn unmap_class(obfuscated: &str) -> Option<String> {
+    installed()?.unmap_class(obfuscated).map(str::to_string)
+}
+
+pub fn unmap_method(owner: &str, name: &str, descriptor: &str) -> Option<String> {
+    installed()?.unmap_method(owner, name, descriptor).map(str::to_string)
+}
+
+pub fn unmap_field(owner: &str, name: &str, descriptor: &str) -> Option<String> {
+    installed()?.unmap_field(owner, name, descriptor).map(str::to_string)
+}
+
+// Replaces the class names in the `L...;` parts of a descriptor that `map` knows.
+fn remap_descriptor<'a>(descriptor: &str, map: impl Fn(&str) -> Option<&'a str>) -> String {
+    let mut result = String::with_capacity(descriptor.len());
+    let mut rest = descriptor;
+    while let Some(start) = rest.find('L') {
+        let Some(end) = rest[start..].find(';') else {
+            break;
+        };
+        let name = &rest[start + 1..start + end];
+        result.push_str(&rest[..=start]);
+        result.push_str(map(name).unwrap_or(name));
+        result.push(';');
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TINY: &str = "tiny\t2\t0\tofficial\tintermediary\tnamed
+c\tfgo\tnet/minecraft/class_310\tnet/minecraft/client/MinecraftClient
+\tf\tLfgo;\tE\tfield_1700\tinstance
+\tm\t()Lfgo;\ta\tmethod_1551\tgetInstance
+\tm\t()Z\tb\tmethod_1493\tisPaused
+\tm\t(Z)V\tb\tmethod_1507\tsetPaused
+c\tfgo$a\tnet/minecraft/class_310$class_1\tnet/minecraft/client/MinecraftClient$Inner
+";
+
+    fn mappings() -> Mappings {
+        Mappings::parse(TINY, RUNTIME_NAMESPACE, MappingsSource::Embedded(String::new())).unwrap()
+    }
+
+    #[test]
+    fn unmaps_classes_and_members() {
+        let mappings = mappings();
+        assert_eq!(mappings.unmap_class("fgo"), Some("net/minecraft/client/MinecraftClient"));
+        assert_eq!(mappings.unmap_class("java/lang/String"), None);
+        assert_eq!(mappings.unmap_method("fgo", "a", "()Lfgo;"), Some("getInstance"));
+        assert_eq!(mappings.unmap_method("fgo", "b", "(Z)V"), Some("setPaused"));
+        assert_eq!(mappings.unmap_field("fgo", "E", "Lfgo;"), Some("instance"));
+        assert_eq!(mappings.unmap_field("fgo", "E", "I"), None);
+    }
+
+    #[test]
+    fn reverse_lookups_invert_forward_lookups() {
+        let mappings = mappings();
+        let class = mappings.class("net/minecraft/client/MinecraftClient");
+        let (name, descriptor) = mappings.method("net/minecraft/client/MinecraftClient", "getInstance", "()Lnet/minecraft/client/MinecraftClient;");
+        assert_eq!(mappings.unmap_method(class, &name, &descriptor), Some("getInstance"));
+    }
+
+    #[test]
+    fn coverage_reports_missing_classes_and_members() {
+        let required = [
+            Requirement::Method { class: "net/minecraft/client/MinecraftClient", name: "getInstance", descriptor: Some("()Lnet/minecraft/client/MinecraftClient;") },
+            Requirement::Method { class: "net/minecraft/client/MinecraftClient", name: "isPaused", descriptor: None },
+            Requirement::Method { class: "net/minecraft/client/MinecraftClient", name: "getWindow", descriptor: Some("()Lnet/minecraft/client/util/Window;") },
+            Requirement::Field { class: "net/minecraft/client/MinecraftClient", name: "instance", descriptor: Some("Lnet/minecraft/client/MinecraftClient;") },
+            Requirement::Field { class: "net/minecraft/client/util/Window", name: "handle", descriptor: Some("J") },
+            Requirement::Class("net/minecraft/client/MinecraftClient$Inner"),
+        ];
+        let coverage = mappings().coverage(&required);
+        assert_eq!(
+            coverage.missing,
+            vec![Requirement::Class("net/minecraft/client/util/Window"), required[2]]
+        );
+        assert_eq!(coverage.to_string(), "1 of 3 required classes, 1 of 3 methods and 0 of 2 fields missing");
+    }
+
+    #[test]
+    fn unmaps_stacktraces() {
+        let stacktrace = "fgo$a: boom\n\tat fgo.a(SourceFile:12)\n\tat fgo.b(SourceFile:34)\n\tat java.base/java.lang.Thread.run(Thread.java:1583)\nCaused by: fgo$a\n";
+        let expected = "net.minecraft.client.MinecraftClient$Inner: boom\n\tat net.minecraft.client.MinecraftClient.getInstance(SourceFile:12)\n\tat net.minecraft.client.MinecraftClient.b(SourceFile:34)\n\tat java.base/java.lang.Thread.run(Thread.java:1583)\nCaused by: net.minecraft.client.MinecraftClient$Inner\n";
+        assert_eq!(mappings().unmap_stacktrace(stacktrace), expected);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::sdk::mappings::MappingsError;
+
+// Reader for the tiny v2 format Yarn ships its mappings in:
+//
+//   tiny	2	0	official	intermediary	named
+//   c	dwv	net/minecraft/class_310	net/minecraft/client/MinecraftClient
+//   	f	Ldwv;	E	field_1700	instance
+//   	m	()Ljava/lang/String;	d	method_1515	getGameVersion
+//
+// Classes are unindented, their fields and methods indented once with the descriptor in the first
+// namespace. Parameters, locals and comments are indented further and skipped, as are properties.
+
+#[derive(Debug)]
+pub struct TinyFile {
+    pub namespaces: Vec<String>,
+    pub classes: Vec<TinyClass>,
+}
+
+#[derive(Debug)]
+pub struct TinyClass {
+    names: Vec<String>,
+    pub members: Vec<TinyMember>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberKind {
+    Method,
+    Field,
+}
+
+#[derive(Debug)]
+pub struct TinyMember {
+    pub kind: MemberKind,
+    pub descriptor: String,
+    names: Vec<String>,
+}
+
+impl TinyFile {
+    pub fn namespace_index(&self, namespace: &str) -> Result<usize, MappingsError> {
+        self.namespaces
+            .iter()
+            .position(|name| name == namespace)
+            .ok_or_else(|| MappingsError::MissingNamespace(namespace.to_string()))
+    }
+}
+
+impl TinyClass {
+    pub fn name(&self, namespace: usize) -> &str {
+        name_in(&self.names, namespace)
+    }
+}
+
+impl TinyMember {
+    pub fn name(&self, namespace: usize) -> &str {
+        name_in(&self.names, namespace)
+    }
+}
+
+// An empty name means the entry keeps its name from the first namespace.
+fn name_in(names: &[String], namespace: usize) -> &str {
+    match names.get(namespace).map(String::as_str) {
+        Some(name) if !name.is_empty() => name,
+        _ => &names[0],
+    }
+}
+
+pub fn parse(text: &str) -> Result<TinyFile, MappingsError> {
+    let mut lines = text.lines().enumerate().map(|(index, line)| (index + 1, line));
+    let error = |line: usize, reason: &str| MappingsError::Parse { line, reason: reason.to_string() };
+
+    let (_, header) = lines.next().ok_or_else(|| error(1, "empty file"))?;
+    let header: Vec<&str> = header.split('\t').collect();
+    if header.len() < 5 || header[0] != "tiny" || header[1] != "2" {
+        return Err(error(1, "not a tiny v2 header"));
+    }
+    let namespaces: Vec<String> = header[3..].iter().map(|namespace| namespace.to_string()).collect();
+
+    let mut classes: Vec<TinyClass> = Vec::new();
+    for (number, line) in lines {
+        let depth = line.len() - line.trim_start_matches('\t').len();
+        let columns: Vec<&str> = line[depth..].split('\t').collect();
+        let names = |from: usize| -> Result<Vec<String>, MappingsError> {
+            let names = columns.get(from..from + namespaces.len()).ok_or_else(|| error(number, "missing names"))?;
+            Ok(names.iter().map(|name| name.to_string()).collect())
+        };
+
+        match (depth, columns[0]) {
+            (0, "c") => classes.push(TinyClass { names: names(1)?, members: Vec::new() }),
+            (1, kind @ ("m" | "f")) => {
+                let class = classes.last_mut().ok_or_else(|| error(number, "member outside of a class"))?;
+                class.members.push(TinyMember {
+                    kind: if kind == "m" { MemberKind::Method } else { MemberKind::Field },
+                    descriptor: columns.get(1).ok_or_else(|| error(number, "missing descriptor"))?.to_string(),
+                    names: names(2)?,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(TinyFile { namespaces, classes })
+}
@@ -0,0 +1,116 @@
+use super::DVec3;
+
+// Reach of a survival player in vanilla, measured from the eyes to the target's bounding box.
+pub const VANILLA_REACH: f64 = 3.0;
+
+// An axis-aligned bounding box mirroring Minecraft's `Box`, which is named so it doesn't collide
+// with `std::boxed::Box`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RustBox {
+    pub min: DVec3,
+    pub max: DVec3,
+}
+
+impl RustBox {
+    // Builds a box from two opposite corners in any order.
+    pub fn new(a: DVec3, b: DVec3) -> Self {
+        Self {
+            min: DVec3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: DVec3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    // Grows the box by `margin` on every side, a negative margin shrinks it.
+    pub fn expand(self, margin: f64) -> Self {
+        let margin = DVec3::new(margin, margin, margin);
+        Self::new(self.min - margin, self.max + margin)
+    }
+
+    pub fn center(self) -> DVec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn contains(self, point: DVec3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+}
+
+// Point of `aabb` nearest to `from`, which is `from` itself when it lies inside the box.
+pub fn closest_point_on_aabb(aabb: RustBox, from: DVec3) -> DVec3 {
+    DVec3::new(
+        from.x.clamp(aabb.min.x, aabb.max.x),
+        from.y.clamp(aabb.min.y, aabb.max.y),
+        from.z.clamp(aabb.min.z, aabb.max.z),
+    )
+}
+
+// Distance from `from` to the nearest point of `aabb`, which is how the game measures attack reach.
+pub fn distance_to_aabb(aabb: RustBox, from: DVec3) -> f64 {
+    from.distance(closest_point_on_aabb(aabb, from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A player-sized hitbox standing at the origin.
+    fn player_box() -> RustBox {
+        RustBox::new(DVec3::new(-0.3, 0.0, -0.3), DVec3::new(0.3, 1.8, 0.3))
+    }
+
+    #[test]
+    fn corners_are_sorted() {
+        let aabb = RustBox::new(DVec3::new(1.0, -2.0, 3.0), DVec3::new(-1.0, 2.0, -3.0));
+        assert_eq!(aabb.min, DVec3::new(-1.0, -2.0, -3.0));
+        assert_eq!(aabb.max, DVec3::new(1.0, 2.0, 3.0));
+        assert_eq!(aabb.center(), DVec3::ZERO);
+    }
+
+    #[test]
+    fn closest_point_from_outside_each_side() {
+        let aabb = player_box();
+        assert_eq!(closest_point_on_aabb(aabb, DVec3::new(3.0, 1.62, 0.0)), DVec3::new(0.3, 1.62, 0.0));
+        assert_eq!(closest_point_on_aabb(aabb, DVec3::new(0.0, 5.0, 0.0)), DVec3::new(0.0, 1.8, 0.0));
+        assert_eq!(closest_point_on_aabb(aabb, DVec3::new(-2.0, -1.0, 2.0)), DVec3::new(-0.3, 0.0, 0.3));
+    }
+
+    #[test]
+    fn point_inside_is_its_own_closest_point() {
+        let inside = DVec3::new(0.1, 1.0, -0.2);
+        assert!(player_box().contains(inside));
+        assert_eq!(closest_point_on_aabb(player_box(), inside), inside);
+        assert_eq!(distance_to_aabb(player_box(), inside), 0.0);
+    }
+
+    #[test]
+    fn reach_is_measured_to_the_hitbox_not_the_center() {
+        // Eyes 3.3 blocks from the target's center are exactly at vanilla reach of its side.
+        let eyes = DVec3::new(3.3, 1.0, 0.0);
+        assert!((distance_to_aabb(player_box(), eyes) - VANILLA_REACH).abs() < 1e-9);
+        assert!(eyes.distance(player_box().center()) > VANILLA_REACH);
+        // Diagonally off a corner, a 3-4-5 triangle.
+        let eyes = DVec3::new(0.3 + 3.0, 1.0, 0.3 + 4.0);
+        assert!((distance_to_aabb(player_box(), eyes) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn expanding_grows_every_side() {
+        let aabb = player_box().expand(0.1);
+        assert!(aabb.contains(DVec3::new(0.4, 1.9, -0.4)));
+        assert!(!aabb.contains(DVec3::new(0.41, 1.0, 0.0)));
+        assert!((distance_to_aabb(aabb, DVec3::new(3.4, 1.0, 0.0)) - 3.0).abs() < 1e-9);
+        // A negative margin shrinks it.
+        assert!(!player_box().expand(-0.1).contains(DVec3::new(0.25, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn boxes_touching_at_a_face_contain_the_shared_face() {
+        let a = RustBox::new(DVec3::ZERO, DVec3::new(1.0, 1.0, 1.0));
+        let b = RustBox::new(DVec3::new(1.0, 0.0, 0.0), DVec3::new(2.0, 1.0, 1.0));
+        let shared = DVec3::new(1.0, 0.5, 0.5);
+        assert!(a.contains(shared) && b.contains(shared));
+        assert_eq!(distance_to_aabb(a, b.center()), 0.5);
+    }
+}
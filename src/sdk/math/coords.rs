@@ -0,0 +1,78 @@
+use super::DVec3;
+
+// Horizontal distance in the overworld covered by one block in the nether.
+pub const NETHER_SCALE: f64 = 8.0;
+
+// The dimension a position belongs to. Positions are only comparable within the same dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    Overworld,
+    Nether,
+    End,
+    // A dimension added by a mod or datapack.
+    Other,
+}
+
+impl Dimension {
+    // Maps a dimension identifier such as `minecraft:the_nether`.
+    pub fn from_id(id: &str) -> Self {
+        match id {
+            "minecraft:overworld" => Dimension::Overworld,
+            "minecraft:the_nether" => Dimension::Nether,
+            "minecraft:the_end" => Dimension::End,
+            _ => Dimension::Other,
+        }
+    }
+}
+
+// The nether position a portal at `pos` leads to. The height is kept as is.
+pub fn overworld_to_nether(pos: DVec3) -> DVec3 {
+    DVec3::new(pos.x / NETHER_SCALE, pos.y, pos.z / NETHER_SCALE)
+}
+
+// The overworld position a portal at `pos` leads to. The height is kept as is.
+pub fn nether_to_overworld(pos: DVec3) -> DVec3 {
+    DVec3::new(pos.x * NETHER_SCALE, pos.y, pos.z * NETHER_SCALE)
+}
+
+// Distance between two positions, `None` if they are in different dimensions. Unknown dimensions
+// are never considered the same, since two modded dimensions cannot be told apart.
+pub fn same_dimension_distance(a_dimension: Dimension, a: DVec3, b_dimension: Dimension, b: DVec3) -> Option<f64> {
+    if a_dimension != b_dimension || a_dimension == Dimension::Other {
+        return None;
+    }
+    Some(a.distance(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nether_coordinates_are_scaled_horizontally() {
+        assert_eq!(overworld_to_nether(DVec3::new(800.0, 70.0, -160.0)), DVec3::new(100.0, 70.0, -20.0));
+        assert_eq!(nether_to_overworld(DVec3::new(100.0, 70.0, -20.0)), DVec3::new(800.0, 70.0, -160.0));
+    }
+
+    #[test]
+    fn conversions_round_trip() {
+        let pos = DVec3::new(-1234.5, 12.0, 98.25);
+        assert_eq!(nether_to_overworld(overworld_to_nether(pos)), pos);
+    }
+
+    #[test]
+    fn dimension_ids() {
+        assert_eq!(Dimension::from_id("minecraft:overworld"), Dimension::Overworld);
+        assert_eq!(Dimension::from_id("minecraft:the_nether"), Dimension::Nether);
+        assert_eq!(Dimension::from_id("minecraft:the_end"), Dimension::End);
+        assert_eq!(Dimension::from_id("mymod:moon"), Dimension::Other);
+    }
+
+    #[test]
+    fn distance_only_within_the_same_known_dimension() {
+        let (a, b) = (DVec3::ZERO, DVec3::new(3.0, 0.0, 4.0));
+        assert_eq!(same_dimension_distance(Dimension::Nether, a, Dimension::Nether, b), Some(5.0));
+        assert_eq!(same_dimension_distance(Dimension::Overworld, a, Dimension::Nether, b), None);
+        assert_eq!(same_dimension_distance(Dimension::Other, a, Dimension::Other, b), None);
+    }
+}
@@ -0,0 +1,91 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub mod aabb;
+pub mod coords;
+pub mod movement;
+pub mod projection;
+pub mod rotations;
+
+pub use aabb::{closest_point_on_aabb, RustBox};
+pub use projection::{Projection, ScreenRect};
+pub use rotations::fov_check;
+
+// A plain double precision vector mirroring Minecraft's `Vec3d`, used by all pure Rust helpers
+// so they never have to touch the JVM.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DVec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl DVec3 {
+    pub const ZERO: DVec3 = DVec3::new(0.0, 0.0, 0.0);
+
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn length_squared(self) -> f64 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    pub fn length(self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    // Length of the vector projected onto the XZ plane, ignoring the vertical component.
+    pub fn horizontal_length(self) -> f64 {
+        (self.x * self.x + self.z * self.z).sqrt()
+    }
+
+    pub fn dot(self, other: DVec3) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn distance(self, other: DVec3) -> f64 {
+        (other - self).length()
+    }
+
+    // Returns the unit vector in the same direction, or zero if the vector has no length.
+    pub fn normalize(self) -> DVec3 {
+        let length = self.length();
+        if length < 1.0e-4 {
+            DVec3::ZERO
+        } else {
+            self * (1.0 / length)
+        }
+    }
+}
+
+impl Add for DVec3 {
+    type Output = DVec3;
+
+    fn add(self, rhs: DVec3) -> DVec3 {
+        DVec3::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for DVec3 {
+    type Output = DVec3;
+
+    fn sub(self, rhs: DVec3) -> DVec3 {
+        DVec3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f64> for DVec3 {
+    type Output = DVec3;
+
+    fn mul(self, rhs: f64) -> DVec3 {
+        DVec3::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Neg for DVec3 {
+    type Output = DVec3;
+
+    fn neg(self) -> DVec3 {
+        DVec3::new(-self.x, -self.y, -self.z)
+    }
+}
@@ -0,0 +1,130 @@
+use super::DVec3;
+
+// Slipperiness of regular blocks, ice and similar blocks override this.
+pub const DEFAULT_SLIPPERINESS: f32 = 0.6;
+
+// Horizontal drag applied every tick while in the air, and as a factor of the block slipperiness on ground.
+pub const AIR_DRAG: f64 = 0.91;
+
+// Vertical drag applied every tick.
+pub const VERTICAL_DRAG: f64 = 0.98;
+
+// Converts movement input into world-space motion the same way `Entity.movementInputToVelocity` does.
+// `forward` and `sideways` are the input axes in [-1, 1] (positive sideways is left), diagonal input
+// longer than 1 gets normalized so strafing is not faster than walking straight.
+pub fn input_to_motion(yaw: f32, forward: f32, sideways: f32, speed: f64) -> DVec3 {
+    let input = DVec3::new(sideways as f64, 0.0, forward as f64);
+    let length_squared = input.length_squared();
+    if length_squared < 1.0e-7 {
+        return DVec3::ZERO;
+    }
+
+    let input = if length_squared > 1.0 { input.normalize() } else { input };
+    let input = input * speed;
+
+    // The game computes these with single precision, so do the same to match its results.
+    let yaw_radians = yaw * 0.017_453_292;
+    let sin = yaw_radians.sin() as f64;
+    let cos = yaw_radians.cos() as f64;
+
+    DVec3::new(
+        input.x * cos - input.z * sin,
+        input.y,
+        input.z * cos + input.x * sin,
+    )
+}
+
+// Speed on the XZ plane in blocks per tick.
+pub fn horizontal_speed(velocity: DVec3) -> f64 {
+    velocity.horizontal_length()
+}
+
+// Applies one tick of vanilla friction: horizontal motion is scaled by the block slipperiness
+// when on ground (or plain air drag otherwise) and vertical motion by the vertical drag.
+pub fn apply_friction(velocity: DVec3, slipperiness: f32, on_ground: bool) -> DVec3 {
+    let horizontal_factor = if on_ground {
+        slipperiness as f64 * AIR_DRAG
+    } else {
+        AIR_DRAG
+    };
+
+    DVec3::new(
+        velocity.x * horizontal_factor,
+        velocity.y * VERTICAL_DRAG,
+        velocity.z * horizontal_factor,
+    )
+}
+
+// Horizontal speed in blocks per tick the player settles at when walking on regular blocks with the
+// given movement speed attribute value. Ground acceleration scales with `0.216 / slipperiness³`,
+// which is 1 on regular blocks, and each tick keeps `slipperiness * AIR_DRAG` of the previous speed,
+// so the speed converges to `movement_speed / (1 - slipperiness * AIR_DRAG)`.
+pub fn expected_ground_speed(movement_speed: f64) -> f64 {
+    movement_speed / (1.0 - DEFAULT_SLIPPERINESS as f64 * AIR_DRAG)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_motion(actual: DVec3, x: f64, z: f64) {
+        assert!((actual.x - x).abs() < 1e-6 && (actual.z - z).abs() < 1e-6, "expected ({}, {}), got {:?}", x, z, actual);
+        assert_eq!(actual.y, 0.0);
+    }
+
+    #[test]
+    fn input_to_motion_in_all_eight_directions() {
+        let d = std::f64::consts::FRAC_1_SQRT_2;
+        // Facing south (+Z), left is east (+X).
+        assert_motion(input_to_motion(0.0, 1.0, 0.0, 1.0), 0.0, 1.0);
+        assert_motion(input_to_motion(0.0, -1.0, 0.0, 1.0), 0.0, -1.0);
+        assert_motion(input_to_motion(0.0, 0.0, 1.0, 1.0), 1.0, 0.0);
+        assert_motion(input_to_motion(0.0, 0.0, -1.0, 1.0), -1.0, 0.0);
+        assert_motion(input_to_motion(0.0, 1.0, 1.0, 1.0), d, d);
+        assert_motion(input_to_motion(0.0, 1.0, -1.0, 1.0), -d, d);
+        assert_motion(input_to_motion(0.0, -1.0, 1.0, 1.0), d, -d);
+        assert_motion(input_to_motion(0.0, -1.0, -1.0, 1.0), -d, -d);
+    }
+
+    #[test]
+    fn input_to_motion_follows_the_yaw() {
+        // Facing west (-X) and north (-Z).
+        assert_motion(input_to_motion(90.0, 1.0, 0.0, 0.5), -0.5, 0.0);
+        assert_motion(input_to_motion(180.0, 1.0, 0.0, 0.5), 0.0, -0.5);
+        assert_motion(input_to_motion(-90.0, 0.0, 1.0, 0.5), 0.0, -0.5);
+    }
+
+    #[test]
+    fn diagonal_input_is_not_faster() {
+        let motion = input_to_motion(45.0, 1.0, 1.0, 0.2);
+        assert!((horizontal_speed(motion) - 0.2).abs() < 1e-6);
+        // Partial input below a length of 1 is kept as it is.
+        let motion = input_to_motion(0.0, 0.3, 0.3, 1.0);
+        assert!((horizontal_speed(motion) - (0.18f64).sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn no_input_gives_no_motion() {
+        assert_eq!(input_to_motion(30.0, 0.0, 0.0, 1.0), DVec3::ZERO);
+    }
+
+    #[test]
+    fn expected_ground_speed_for_each_effect_level() {
+        // Movement speed attribute values: walking is 0.1, each speed level adds 20% and each slowness
+        // level takes 15%, sprinting multiplies by 1.3.
+        let cases = [
+            (0.1, 0.22026),
+            (0.12, 0.26432),
+            (0.14, 0.30837),
+            (0.16, 0.35242),
+            (0.085, 0.18722),
+            (0.07, 0.15419),
+            (0.13, 0.28634),
+            (0.13 * 1.4, 0.40088),
+        ];
+        for (movement_speed, expected) in cases {
+            let speed = expected_ground_speed(movement_speed);
+            assert!((speed - expected).abs() < 1e-5, "{} gave {}, expected {}", movement_speed, speed, expected);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use super::{DVec3, RustBox};
+
+// Everything needed to find where a point in the world appears on screen, captured from the game's
+// camera once per frame. Projecting is plain math afterwards, so drawing many entities costs no JNI
+// calls. View bobbing and the hurt tilt are not included, they shift the picture by a few pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    pub camera_pos: DVec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    // The game's projection matrix in column-major order, the layout JOML stores it in.
+    pub matrix: [f32; 16],
+    // Size of the framebuffer in pixels.
+    pub width: f64,
+    pub height: f64,
+}
+
+// A rectangle on screen in framebuffer pixels, with the origin in the top left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScreenRect {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl Projection {
+    // Screen position of a point in framebuffer pixels, with the origin in the top left corner.
+    // `None` for points behind the camera. Points outside the view still get a position, off screen.
+    pub fn world_to_screen(&self, point: DVec3) -> Option<(f64, f64)> {
+        let (x, y, z) = self.view_offset(point - self.camera_pos);
+        let m = self.matrix.map(f64::from);
+        let clip_x = m[0] * x + m[4] * y + m[8] * z + m[12];
+        let clip_y = m[1] * x + m[5] * y + m[9] * z + m[13];
+        let clip_w = m[3] * x + m[7] * y + m[11] * z + m[15];
+        if clip_w <= f64::EPSILON {
+            return None;
+        }
+
+        let ndc_x = clip_x / clip_w;
+        let ndc_y = clip_y / clip_w;
+        Some(((ndc_x + 1.0) * 0.5 * self.width, (1.0 - ndc_y) * 0.5 * self.height))
+    }
+
+    // Smallest screen rectangle around all corners of a box, the outline an ESP draws. `None` if any
+    // corner is behind the camera, as the rectangle would be wrong then.
+    pub fn project_box(&self, aabb: RustBox) -> Option<ScreenRect> {
+        let mut rect = ScreenRect { min_x: f64::MAX, min_y: f64::MAX, max_x: f64::MIN, max_y: f64::MIN };
+        for corner in 0..8 {
+            let point = DVec3::new(
+                if corner & 1 == 0 { aabb.min.x } else { aabb.max.x },
+                if corner & 2 == 0 { aabb.min.y } else { aabb.max.y },
+                if corner & 4 == 0 { aabb.min.z } else { aabb.max.z },
+            );
+            let (x, y) = self.world_to_screen(point)?;
+            rect.min_x = rect.min_x.min(x);
+            rect.min_y = rect.min_y.min(y);
+            rect.max_x = rect.max_x.max(x);
+            rect.max_y = rect.max_y.max(y);
+        }
+        Some(rect)
+    }
+
+    // Rotates an offset from the camera into view space, where the camera looks down -Z with +Y up,
+    // the same rotations the game applies before rendering the world: pitch around X after yaw + 180
+    // around Y.
+    fn view_offset(&self, offset: DVec3) -> (f64, f64, f64) {
+        let (yaw_sin, yaw_cos) = (self.yaw as f64 + 180.0).to_radians().sin_cos();
+        let x = offset.x * yaw_cos + offset.z * yaw_sin;
+        let z = offset.z * yaw_cos - offset.x * yaw_sin;
+
+        let (pitch_sin, pitch_cos) = (self.pitch as f64).to_radians().sin_cos();
+        let y = offset.y * pitch_cos - z * pitch_sin;
+        let z = offset.y * pitch_sin + z * pitch_cos;
+        (x, y, z)
+    }
+}
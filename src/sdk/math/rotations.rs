@@ -0,0 +1,185 @@
+use super::DVec3;
+
+// Minecraft's rotation conventions: yaw 0 faces +Z (south) and grows clockwise when seen
+// from above, so yaw 90 faces -X (west). Pitch is negative when looking up and is clamped
+// to [-90, 90].
+
+// Wraps an angle into the range [-180, 180), the same way `MathHelper.wrapDegrees` does.
+pub fn wrap_degrees(angle: f32) -> f32 {
+    let mut wrapped = angle % 360.0;
+    if wrapped >= 180.0 {
+        wrapped -= 360.0;
+    }
+    if wrapped < -180.0 {
+        wrapped += 360.0;
+    }
+    wrapped
+}
+
+// Computes the yaw and pitch needed to look from `from` to `to`.
+pub fn rotation_to(from: DVec3, to: DVec3) -> (f32, f32) {
+    let diff = to - from;
+    let horizontal_distance = diff.horizontal_length();
+
+    let yaw = diff.z.atan2(diff.x).to_degrees() - 90.0;
+    let pitch = -diff.y.atan2(horizontal_distance).to_degrees();
+
+    (wrap_degrees(yaw as f32), pitch as f32)
+}
+
+// Returns the signed shortest angle to turn from `b` to `a`, handling the wraparound at ±180°.
+pub fn angle_difference(a: f32, b: f32) -> f32 {
+    wrap_degrees(a - b)
+}
+
+// Turns from `current` towards `target` (both as yaw, pitch) by at most `max_step` degrees on
+// each axis, which gives a smooth rotation when applied once per tick.
+pub fn limit_rotation_change(current: (f32, f32), target: (f32, f32), max_step: f32) -> (f32, f32) {
+    let max_step = max_step.abs();
+    let yaw_change = angle_difference(target.0, current.0).clamp(-max_step, max_step);
+    let pitch_change = (target.1 - current.1).clamp(-max_step, max_step);
+
+    (current.0 + yaw_change, (current.1 + pitch_change).clamp(-90.0, 90.0))
+}
+
+// Converts a rotation into the unit look vector the game would use for it.
+pub fn direction_from_rotation(yaw: f32, pitch: f32) -> DVec3 {
+    let yaw = (yaw as f64).to_radians();
+    let pitch = (pitch as f64).to_radians();
+
+    DVec3::new(
+        -yaw.sin() * pitch.cos(),
+        -pitch.sin(),
+        yaw.cos() * pitch.cos(),
+    )
+}
+
+// Angle in degrees between the look direction of a camera at `camera_pos` and the direction from
+// the camera to `target`, from 0 (straight ahead) to 180 (directly behind). A target at the camera
+// position counts as straight ahead.
+pub fn angle_to_target(camera_yaw: f32, camera_pitch: f32, camera_pos: DVec3, target: DVec3) -> f32 {
+    let to_target = (target - camera_pos).normalize();
+    if to_target == DVec3::ZERO {
+        return 0.0;
+    }
+
+    let look = direction_from_rotation(camera_yaw, camera_pitch);
+    look.dot(to_target).clamp(-1.0, 1.0).acos().to_degrees() as f32
+}
+
+// Checks whether `target` lies within a cone of `max_angle_deg` degrees around the camera's look
+// direction. The angle is measured from the center of the view, so 45 allows targets up to 45
+// degrees off in any direction.
+pub fn fov_check(camera_yaw: f32, camera_pitch: f32, camera_pos: DVec3, target: DVec3, max_angle_deg: f32) -> bool {
+    angle_to_target(camera_yaw, camera_pitch, camera_pos, target) <= max_angle_deg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-3, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn wrap_degrees_keeps_the_half_open_range() {
+        assert_close(wrap_degrees(0.0), 0.0);
+        assert_close(wrap_degrees(179.0), 179.0);
+        assert_close(wrap_degrees(180.0), -180.0);
+        assert_close(wrap_degrees(-180.0), -180.0);
+        assert_close(wrap_degrees(190.0), -170.0);
+        assert_close(wrap_degrees(-190.0), 170.0);
+        assert_close(wrap_degrees(720.0 + 45.0), 45.0);
+        assert_close(wrap_degrees(-720.0 - 45.0), -45.0);
+    }
+
+    #[test]
+    fn rotation_to_covers_every_quadrant() {
+        let origin = DVec3::ZERO;
+        // South is yaw 0, then clockwise: west 90, north -180, east -90.
+        assert_close(rotation_to(origin, DVec3::new(0.0, 0.0, 1.0)).0, 0.0);
+        assert_close(rotation_to(origin, DVec3::new(-1.0, 0.0, 0.0)).0, 90.0);
+        assert_close(rotation_to(origin, DVec3::new(0.0, 0.0, -1.0)).0, -180.0);
+        assert_close(rotation_to(origin, DVec3::new(1.0, 0.0, 0.0)).0, -90.0);
+        // Diagonals.
+        assert_close(rotation_to(origin, DVec3::new(-1.0, 0.0, 1.0)).0, 45.0);
+        assert_close(rotation_to(origin, DVec3::new(-1.0, 0.0, -1.0)).0, 135.0);
+        assert_close(rotation_to(origin, DVec3::new(1.0, 0.0, -1.0)).0, -135.0);
+        assert_close(rotation_to(origin, DVec3::new(1.0, 0.0, 1.0)).0, -45.0);
+    }
+
+    #[test]
+    fn rotation_to_pitch_is_negative_looking_up() {
+        let origin = DVec3::ZERO;
+        assert_close(rotation_to(origin, DVec3::new(0.0, 1.0, 1.0)).1, -45.0);
+        assert_close(rotation_to(origin, DVec3::new(0.0, -1.0, 1.0)).1, 45.0);
+        assert_close(rotation_to(origin, DVec3::new(0.0, 1.0, 0.0)).1, -90.0);
+        assert_close(rotation_to(origin, DVec3::new(0.0, 0.0, 1.0)).1, 0.0);
+    }
+
+    #[test]
+    fn angle_difference_takes_the_short_way_across_180() {
+        assert_close(angle_difference(10.0, -10.0), 20.0);
+        assert_close(angle_difference(-10.0, 10.0), -20.0);
+        assert_close(angle_difference(-170.0, 170.0), 20.0);
+        assert_close(angle_difference(170.0, -170.0), -20.0);
+        assert_close(angle_difference(90.0, 90.0 + 360.0), 0.0);
+    }
+
+    #[test]
+    fn limit_rotation_change_clamps_each_axis() {
+        assert_eq!(limit_rotation_change((0.0, 0.0), (5.0, -5.0), 10.0), (5.0, -5.0));
+        assert_eq!(limit_rotation_change((0.0, 0.0), (90.0, 60.0), 10.0), (10.0, 10.0));
+        assert_eq!(limit_rotation_change((0.0, 0.0), (-90.0, -60.0), -10.0), (-10.0, -10.0));
+    }
+
+    #[test]
+    fn limit_rotation_change_turns_across_180() {
+        let (yaw, pitch) = limit_rotation_change((170.0, 0.0), (-170.0, 0.0), 5.0);
+        assert_close(yaw, 175.0);
+        assert_close(pitch, 0.0);
+        let (yaw, _) = limit_rotation_change((-175.0, 0.0), (175.0, 0.0), 30.0);
+        assert_close(yaw, -185.0);
+    }
+
+    #[test]
+    fn limit_rotation_change_keeps_pitch_in_range() {
+        assert_eq!(limit_rotation_change((0.0, 88.0), (0.0, 120.0), 10.0).1, 90.0);
+        assert_eq!(limit_rotation_change((0.0, -88.0), (0.0, -120.0), 10.0).1, -90.0);
+    }
+
+    #[test]
+    fn fov_check_rejects_targets_behind_the_camera() {
+        let camera = DVec3::new(0.0, 64.0, 0.0);
+        let behind = DVec3::new(0.0, 64.0, -5.0);
+        assert_close(angle_to_target(0.0, 0.0, camera, behind), 180.0);
+        assert!(!fov_check(0.0, 0.0, camera, behind, 179.0));
+        assert!(fov_check(0.0, 0.0, camera, DVec3::new(0.0, 64.0, 5.0), 1.0));
+    }
+
+    #[test]
+    fn fov_check_directly_above() {
+        let camera = DVec3::new(0.0, 64.0, 0.0);
+        let above = DVec3::new(0.0, 70.0, 0.0);
+        assert_close(angle_to_target(0.0, 0.0, camera, above), 90.0);
+        assert!(!fov_check(0.0, 0.0, camera, above, 60.0));
+        // Looking straight up, whatever the yaw.
+        assert!(fov_check(123.0, -90.0, camera, above, 1.0));
+    }
+
+    #[test]
+    fn fov_check_at_the_cone_boundary() {
+        let camera = DVec3::ZERO;
+        let target = DVec3::new(1.0, 0.0, 1.0);
+        assert_close(angle_to_target(0.0, 0.0, camera, target), 45.0);
+        assert!(fov_check(0.0, 0.0, camera, target, 45.01));
+        assert!(!fov_check(0.0, 0.0, camera, target, 44.99));
+    }
+
+    #[test]
+    fn fov_check_target_at_the_camera_counts_as_ahead() {
+        let camera = DVec3::new(1.0, 2.0, 3.0);
+        assert!(fov_check(0.0, 0.0, camera, camera, 0.0));
+    }
+}
@@ -0,0 +1,14 @@
+// The SDK groups everything needed to talk to the game: plain Rust math helpers
+// as well as the wrappers around Minecraft classes.
+pub mod error;
+pub mod game;
+pub mod input;
+pub mod jni;
+pub mod mappings;
+pub mod math;
+pub mod retry;
+pub mod version;
+
+pub use error::{Error, Result};
+pub use retry::TransientRetry;
+pub use version::GameVersion;
@@ -0,0 +1,143 @@
+use jni::JNIEnv;
+use tracing::debug;
+
+use super::{Error, Result};
+
+// Retries a call that fails transiently, see `Error::is_transient`, on the following ticks instead of
+// reporting every failure. Retrying within the same tick does not help: the world is swapped by the
+// game's own tick, which cannot run while ours holds on to it.
+//
+// Other errors are returned right away. A transient failure is returned once it has lasted
+// `give_up_after` ticks in a row, and every tick after that until the call succeeds again. A single
+// debug line is logged when the failure starts and when it is resolved.
+#[derive(Debug)]
+pub struct TransientRetry {
+    give_up_after: u64,
+    failed_ticks: u64,
+}
+
+impl TransientRetry {
+    pub const fn new(give_up_after: u64) -> Self {
+        Self { give_up_after, failed_ticks: 0 }
+    }
+
+    // Calls `f` for this tick. `None` if it failed transiently and is retried on the next one. Any
+    // pending Java exception is folded into the error, so the next call starts with a clean environment.
+    pub fn attempt<T>(&mut self, env: &mut JNIEnv, f: impl FnOnce(&mut JNIEnv) -> Result<T>) -> Result<Option<T>> {
+        let result = f(env).map_err(|e| e.with_exception(env));
+        self.record(result)
+    }
+
+    // Ticks in a row the call has failed transiently.
+    pub fn failed_ticks(&self) -> u64 {
+        self.failed_ticks
+    }
+
+    fn record<T>(&mut self, result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => {
+                if self.failed_ticks > 0 {
+                    debug!("Transient failure resolved after {} ticks", self.failed_ticks);
+                }
+                self.failed_ticks = 0;
+                Ok(Some(value))
+            }
+            Err(error) if error.is_transient() => {
+                self.failed_ticks += 1;
+                if self.failed_ticks == 1 {
+                    debug!("Transient failure, retrying on the next tick: {}", error);
+                }
+                if self.failed_ticks >= self.give_up_after.max(1) {
+                    return Err(error);
+                }
+                Ok(None)
+            }
+            Err(error) => {
+                self.failed_ticks = 0;
+                Err(error)
+            }
+        }
+    }
+}
+
+impl Error {
+    // Whether the error is expected to go away on its own, as happens for a moment while the world
+    // is being swapped: the player or world is briefly null, or a call hits a null reference.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Error::NullObject { .. }) || self.is_java_exception("java.lang.NullPointerException")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn null_object() -> Result<i32> {
+        Err(Error::NullObject { context: "player".to_string() })
+    }
+
+    fn null_pointer() -> Result<i32> {
+        Err(Error::JavaException {
+            class: "java.lang.NullPointerException".to_string(),
+            message: String::new(),
+            stacktrace: String::new(),
+        })
+    }
+
+    fn invalid_value() -> Result<i32> {
+        Err(Error::InvalidValue { context: "ordinal 9".to_string() })
+    }
+
+    #[test]
+    fn success_passes_through() {
+        let mut retry = TransientRetry::new(3);
+        assert_eq!(retry.record(Ok(1)).unwrap(), Some(1));
+        assert_eq!(retry.failed_ticks(), 0);
+    }
+
+    #[test]
+    fn transient_failures_are_deferred_until_giving_up() {
+        let mut retry = TransientRetry::new(3);
+        assert_eq!(retry.record(null_object()).unwrap(), None);
+        assert_eq!(retry.record(null_pointer()).unwrap(), None);
+        assert!(retry.record(null_object()).is_err());
+        // Still failing after giving up.
+        assert!(retry.record(null_object()).is_err());
+        assert_eq!(retry.failed_ticks(), 4);
+    }
+
+    #[test]
+    fn success_resets_the_count() {
+        let mut retry = TransientRetry::new(3);
+        retry.record(null_object()).unwrap();
+        retry.record(null_object()).unwrap();
+        assert_eq!(retry.record(Ok(2)).unwrap(), Some(2));
+        assert_eq!(retry.record(null_object()).unwrap(), None);
+        assert_eq!(retry.failed_ticks(), 1);
+    }
+
+    #[test]
+    fn other_errors_are_returned_right_away() {
+        let mut retry = TransientRetry::new(3);
+        retry.record(null_object()).unwrap();
+        assert!(matches!(retry.record(invalid_value()), Err(Error::InvalidValue { .. })));
+        assert_eq!(retry.failed_ticks(), 0);
+    }
+
+    #[test]
+    fn other_java_exceptions_are_not_transient() {
+        let error = Error::JavaException {
+            class: "java.lang.IllegalStateException".to_string(),
+            message: String::new(),
+            stacktrace: String::new(),
+        };
+        assert!(!error.is_transient());
+        assert!(null_pointer().unwrap_err().is_transient());
+    }
+
+    #[test]
+    fn zero_ticks_gives_up_on_the_first_failure() {
+        let mut retry = TransientRetry::new(0);
+        assert!(retry.record(null_object()).is_err());
+    }
+}
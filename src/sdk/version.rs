@@ -0,0 +1,123 @@
+use std::sync::OnceLock;
+
+use jni::objects::{JByteArray, JValue};
+use jni::JNIEnv;
+
+use crate::sdk::jni::string::ToJava;
+use crate::sdk::{Error, Result};
+
+// Entry point of the client, which keeps its name in obfuscated builds.
+const MAIN_CLASS: &str = "net/minecraft/client/main/Main";
+
+static DETECTED: OnceLock<(String, Option<GameVersion>)> = OnceLock::new();
+
+// Ranges of game versions whose classes the SDK has to call differently. Wrappers branch on these
+// where a signature changed, ordered from oldest to newest so ranges can be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GameVersion {
+    // 1.20 to 1.20.4.
+    V1_20,
+    // 1.20.5 and 1.20.6, where item components replaced NBT and attributes became registry entries.
+    V1_20_5,
+    // 1.21 and 1.21.1.
+    V1_21,
+}
+
+impl GameVersion {
+    pub const ALL: [GameVersion; 3] = [GameVersion::V1_20, GameVersion::V1_20_5, GameVersion::V1_21];
+    // Assumed for versions the SDK does not know, which are most likely newer than all of these.
+    pub const LATEST: GameVersion = GameVersion::V1_21;
+
+    // Maps a release name like `1.20.6` to its range, `None` for unsupported versions and snapshots.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mut parts = name.split('.').map(str::parse::<u32>);
+        let (Some(Ok(1)), Some(Ok(minor))) = (parts.next(), parts.next()) else {
+            return None;
+        };
+        let patch = match parts.next() {
+            Some(Ok(patch)) => patch,
+            Some(Err(_)) => return None,
+            None => 0,
+        };
+
+        match (minor, patch) {
+            (20, 0..=4) => Some(GameVersion::V1_20),
+            (20, 5..=6) => Some(GameVersion::V1_20_5),
+            (21, 0..=1) => Some(GameVersion::V1_21),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameVersion::V1_20 => "1.20-1.20.4",
+            GameVersion::V1_20_5 => "1.20.5-1.20.6",
+            GameVersion::V1_21 => "1.21-1.21.1",
+        }
+    }
+}
+
+impl std::fmt::Display for GameVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Reads the game version once attached and remembers it for the rest of the session. Returns the
+// version name and its range, `None` if the SDK does not know the version.
+pub fn detect(env: &mut JNIEnv) -> Result<(&'static str, Option<GameVersion>)> {
+    if let Some((name, version)) = DETECTED.get() {
+        return Ok((name.as_str(), *version));
+    }
+
+    let name = read_version_name(env).map_err(|e| e.with_exception(env))?;
+    let version = GameVersion::from_name(&name);
+    let (name, version) = DETECTED.get_or_init(|| (name, version));
+    Ok((name.as_str(), *version))
+}
+
+// Name of the running game version, e.g. `1.21.1`, once detected.
+pub fn name() -> Option<&'static str> {
+    DETECTED.get().map(|(name, _)| name.as_str())
+}
+
+// Range of the running game version. Unknown or not yet detected versions count as the latest.
+pub fn current() -> GameVersion {
+    DETECTED.get().and_then(|(_, version)| *version).unwrap_or(GameVersion::LATEST)
+}
+
+pub fn at_least(version: GameVersion) -> bool {
+    current() >= version
+}
+
+// Reads the `id` of the `version.json` every client jar carries, which works before any mappings are
+// known.
+fn read_version_name(env: &mut JNIEnv) -> Result<String> {
+    let json = env.with_local_frame(8, |env| -> Result<Vec<u8>> {
+        let main = env.find_class(MAIN_CLASS).map_err(|_| Error::class_not_found(env, MAIN_CLASS))?;
+        let name = "/version.json".to_java(env)?;
+        let stream = env
+            .call_method(&main, "getResourceAsStream", "(Ljava/lang/String;)Ljava/io/InputStream;", &[JValue::Object(&name)])?
+            .l()?;
+        if stream.is_null() {
+            return Err(Error::null_object("version.json resource"));
+        }
+
+        let data = env.call_method(&stream, "readAllBytes", "()[B", &[])?.l()?;
+        let bytes = env.convert_byte_array(JByteArray::from(data))?;
+        env.call_method(&stream, "close", "()V", &[])?;
+        Ok(bytes)
+    })?;
+
+    let json = String::from_utf8_lossy(&json);
+    json_string(&json, "id").ok_or_else(|| Error::InvalidValue { context: String::from("version.json has no id") })
+}
+
+// Value of the first string property `key` in a JSON document. Enough for the flat documents the
+// client reads.
+pub fn json_string(json: &str, key: &str) -> Option<String> {
+    let pattern = format!("\"{}\"", key);
+    let after_key = &json[json.find(&pattern)? + pattern.len()..];
+    let value = after_key.trim_start().strip_prefix(':')?.trim_start().strip_prefix('"')?;
+    Some(value[..value.find('"')?].to_string())
+}
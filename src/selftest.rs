@@ -0,0 +1,186 @@
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+use jni::objects::JValue;
+use jni::JNIEnv;
+
+use crate::sdk::error::take_java_exception;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::direction::Direction;
+use crate::sdk::game::vec3d;
+use crate::sdk::math::DVec3;
+use crate::sdk::{Error, Result};
+
+// Outcome of a single self-test step. Failures carry the SDK error message, which names the
+// missing class, method or field when a lookup failed.
+#[derive(Debug, Clone)]
+pub struct SelftestStep {
+    pub name: &'static str,
+    pub duration: Duration,
+    pub result: std::result::Result<String, String>,
+}
+
+impl SelftestStep {
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+// Runs a fixed sequence of SDK calls against the live game and reports each step, so a report of
+// "nothing works on version X" can be narrowed down to mappings, JNI plumbing or a single wrapper.
+// Steps that depend on a failed one are reported as skipped. Never leaves a Java exception pending.
+pub fn run(env: &mut JNIEnv) -> Vec<SelftestStep> {
+    let mut steps = Vec::new();
+
+    let client = step(env, &mut steps, "fetch client", |env| {
+        let client = MinecraftClient::get_instance(env)?;
+        Ok((client, String::from("MinecraftClient.instance")))
+    });
+    let player = client.as_ref().and_then(|client| {
+        step(env, &mut steps, "fetch player", |env| match client.get_player(env)? {
+            Some(player) => Ok((player, String::from("present"))),
+            None => Err(Error::null_object("MinecraftClient.player (no world loaded)")),
+        })
+    });
+    if player.is_none() {
+        skip(&mut steps, "fetch player");
+    }
+
+    match &client {
+        Some(client) => {
+            step(env, &mut steps, "fetch world", |env| match client.get_world(env)? {
+                Some(world) => {
+                    let dimension = world.get_dimension(env)?;
+                    env.delete_local_ref(world.inner)?;
+                    Ok(((), format!("{:?}", dimension)))
+                }
+                None => Err(Error::null_object("MinecraftClient.world (no world loaded)")),
+            });
+        }
+        None => skip(&mut steps, "fetch world"),
+    }
+
+    match &player {
+        Some(player) => {
+            let entity = player.as_entity_ref();
+            step(env, &mut steps, "read position", |env| {
+                let pos = entity.get_pos(env)?;
+                Ok(((), format!("{:.2}, {:.2}, {:.2}", pos.x, pos.y, pos.z)))
+            });
+            step(env, &mut steps, "read rotation", |env| {
+                let (yaw, pitch) = (entity.get_yaw(env)?, entity.get_pitch(env)?);
+                Ok(((), format!("yaw {:.1}, pitch {:.1}", yaw, pitch)))
+            });
+            step(env, &mut steps, "read health", |env| {
+                let health = player.as_living_entity_ref().get_health(env)?;
+                Ok(((), format!("{:.1}", health)))
+            });
+        }
+        None => {
+            skip(&mut steps, "read position");
+            skip(&mut steps, "read rotation");
+            skip(&mut steps, "read health");
+        }
+    }
+
+    step(env, &mut steps, "Vec3d round trip", |env| {
+        let expected = DVec3::new(1.5, -2.25, 1024.125);
+        let object = vec3d::from_dvec3(env, expected)?;
+        let actual = vec3d::to_dvec3(env, &object)?;
+        env.delete_local_ref(object)?;
+        if actual != expected {
+            return Err(Error::InvalidValue { context: format!("Vec3d round trip returned {:?}", actual) });
+        }
+        Ok(((), String::from("components match")))
+    });
+
+    step(env, &mut steps, "resolve enum constant", |env| {
+        let object = Direction::Up.to_java(env)?;
+        let direction = Direction::from_java(env, &object)?;
+        env.delete_local_ref(object)?;
+        if direction != Direction::Up {
+            return Err(Error::InvalidValue { context: format!("Direction.UP resolved to {:?}", direction) });
+        }
+        Ok(((), String::from("Direction.UP")))
+    });
+
+    step(env, &mut steps, "recover from exception", |env| {
+        let argument = env.new_string("selftest")?;
+        let call = env.call_static_method("java/lang/Integer", "parseInt", "(Ljava/lang/String;)I", &[JValue::Object(&argument)]);
+        env.delete_local_ref(argument)?;
+        if call.is_ok() {
+            return Err(Error::InvalidValue { context: String::from("Integer.parseInt(\"selftest\") did not throw") });
+        }
+
+        match take_java_exception(env) {
+            Some(exception) if exception.is_java_exception("java.lang.NumberFormatException") => {}
+            Some(exception) => return Err(exception),
+            None => return Err(Error::InvalidValue { context: String::from("no Java exception was pending") }),
+        }
+        if env.exception_check()? {
+            return Err(Error::InvalidValue { context: String::from("exception still pending after clearing") });
+        }
+        Ok(((), String::from("NumberFormatException caught and cleared")))
+    });
+
+    if let Some(player) = player {
+        let _ = env.delete_local_ref(player.as_entity().inner);
+    }
+    if let Some(client) = client {
+        let _ = env.delete_local_ref(client.inner);
+    }
+
+    steps
+}
+
+// Formats the steps one per line with their timing and a summary line at the end.
+pub fn format_report(steps: &[SelftestStep]) -> String {
+    let mut report = String::new();
+    for step in steps {
+        let (status, detail) = match &step.result {
+            Ok(detail) => ("PASS", detail),
+            Err(error) => ("FAIL", error),
+        };
+        let _ = writeln!(report, "[{}] {} ({:.2} ms): {}", status, step.name, step.duration.as_secs_f64() * 1000.0, detail);
+    }
+
+    let passed = steps.iter().filter(|step| step.passed()).count();
+    let _ = writeln!(report, "{}/{} steps passed", passed, steps.len());
+    report
+}
+
+// Runs one step, records its outcome and returns its value if it passed.
+fn step<'local, T>(
+    env: &mut JNIEnv<'local>,
+    steps: &mut Vec<SelftestStep>,
+    name: &'static str,
+    f: impl FnOnce(&mut JNIEnv<'local>) -> Result<(T, String)>,
+) -> Option<T> {
+    let start = Instant::now();
+    let result = f(env).map_err(|e| e.with_exception(env));
+    let duration = start.elapsed();
+
+    match result {
+        Ok((value, detail)) => {
+            steps.push(SelftestStep { name, duration, result: Ok(detail) });
+            Some(value)
+        }
+        Err(e) => {
+            steps.push(SelftestStep { name, duration, result: Err(e.to_string()) });
+            None
+        }
+    }
+}
+
+// Records a step that could not run because one it depends on failed. A step that already ran
+// and failed under the same name is not recorded twice.
+fn skip(steps: &mut Vec<SelftestStep>, name: &'static str) {
+    if steps.iter().any(|step| step.name == name) {
+        return;
+    }
+    steps.push(SelftestStep {
+        name,
+        duration: Duration::ZERO,
+        result: Err(String::from("skipped, a previous step failed")),
+    });
+}
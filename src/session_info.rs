@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::{info, warn};
+
+use crate::config;
+use crate::sdk::game::client::MinecraftClient;
+use crate::world_change::WorldChange;
+
+// Server name used for singleplayer worlds, which have no address.
+pub const SINGLEPLAYER: &str = "singleplayer";
+
+pub const FILE_NAME: &str = "sessions.tsv";
+
+static SESSIONS: Mutex<SessionTracker> = Mutex::new(SessionTracker::new());
+
+pub fn sessions() -> MutexGuard<'static, SessionTracker> {
+    SESSIONS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// File the per-server play time totals are kept in, see `SessionTracker::save`.
+pub fn path() -> PathBuf {
+    config::client_dir().join(FILE_NAME)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    pub username: String,
+    pub server: String,
+    pub login_time: SystemTime,
+    started: Instant,
+}
+
+impl SessionInfo {
+    pub fn duration(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+// Tracks the current play session and how long was played on each server. Durations that have not
+// been saved yet are kept apart, so saving adds them to the totals in the file instead of replacing
+// what earlier injections recorded.
+pub struct SessionTracker {
+    current: Option<SessionInfo>,
+    unsaved: Vec<(String, Duration)>,
+}
+
+impl Default for SessionTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionTracker {
+    pub const fn new() -> Self {
+        Self { current: None, unsaved: Vec::new() }
+    }
+
+    pub fn current(&self) -> Option<&SessionInfo> {
+        self.current.as_ref()
+    }
+
+    // Starts or ends sessions on world changes. A switch to another world on the same server, e.g. a
+    // dimension change, continues the session.
+    pub fn on_world_change(&mut self, env: &mut JNIEnv, client: &MinecraftClient, change: WorldChange) -> Result<()> {
+        match change {
+            WorldChange::Left => self.end(),
+            WorldChange::Joined | WorldChange::Switched => {
+                let server = client.get_server_address(env)?.unwrap_or_else(|| SINGLEPLAYER.to_string());
+                if self.current.as_ref().is_some_and(|session| session.server == server) {
+                    return Ok(());
+                }
+                self.end();
+
+                let session = client.get_session(env)?;
+                let username = session.get_username(env)?;
+                env.delete_local_ref(session.inner)?;
+
+                info!("Session started as {} on {}", username, server);
+                self.current = Some(SessionInfo {
+                    username,
+                    server,
+                    login_time: SystemTime::now(),
+                    started: Instant::now(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Ends the current session and keeps its duration for the next save.
+    pub fn end(&mut self) {
+        if let Some(session) = self.current.take() {
+            let duration = session.duration();
+            info!("Session on {} ended after {:?}", session.server, duration);
+            self.unsaved.push((session.server, duration));
+        }
+    }
+
+    // Adds the durations of ended sessions to the per-server totals in `path`. The file holds one
+    // `server<TAB>seconds` line per server. Unreadable lines are skipped with a warning.
+    pub fn save(&mut self, path: &Path) -> Result<()> {
+        if self.unsaved.is_empty() {
+            return Ok(());
+        }
+
+        let mut totals = load_totals(path)?;
+        for (server, duration) in self.unsaved.drain(..) {
+            *totals.entry(server).or_default() += duration.as_secs();
+        }
+
+        let mut servers: Vec<_> = totals.into_iter().collect();
+        servers.sort();
+        let contents: String = servers.iter().map(|(server, seconds)| format!("{}\t{}\n", server, seconds)).collect();
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+// Reads the per-server totals in seconds, a missing file means nothing was recorded yet.
+pub fn load_totals(path: &Path) -> Result<HashMap<String, u64>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut totals = HashMap::new();
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        match line.rsplit_once('\t').map(|(server, seconds)| (server, seconds.trim().parse::<u64>())) {
+            Some((server, Ok(seconds))) => *totals.entry(server.to_string()).or_default() += seconds,
+            _ => warn!("Skipping malformed session line '{}'", line),
+        }
+    }
+    Ok(totals)
+}
@@ -0,0 +1,68 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::sdk::game::packet::UpdateSelectedSlotC2SPacket;
+use crate::sdk::game::player::ClientPlayerEntity;
+use crate::timing;
+
+// Switches the held hotbar slot. Writing `selectedSlot` alone does not reach the server on every
+// version, which causes ghost items, so every switch is also sent as a packet.
+#[derive(Debug, Default)]
+pub struct SlotSwitcher {
+    // The tick and slot of the last packet sent, used to drop duplicates within a tick.
+    last_sent: Option<(u64, i32)>,
+}
+
+impl SlotSwitcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Selects the slot on the client and tells the server about it.
+    pub fn select_slot(&mut self, env: &mut JNIEnv, player: &ClientPlayerEntity, slot: i32) -> Result<()> {
+        ensure_hotbar_slot(slot)?;
+
+        let inventory = player.get_inventory(env)?;
+        inventory.set_selected_slot(env, slot)?;
+        env.delete_local_ref(inventory.inner)?;
+
+        self.send_slot(env, player, slot)
+    }
+
+    // Tells the server about the slot without changing the client's selected slot, e.g. to hit with a
+    // weapon without visibly switching to it. Returns the slot the client still has selected, which
+    // has to be switched back to silently afterwards.
+    pub fn silent_switch(&mut self, env: &mut JNIEnv, player: &ClientPlayerEntity, slot: i32) -> Result<i32> {
+        ensure_hotbar_slot(slot)?;
+
+        let inventory = player.get_inventory(env)?;
+        let client_slot = inventory.get_selected_slot(env)?;
+        env.delete_local_ref(inventory.inner)?;
+
+        self.send_slot(env, player, slot)?;
+        Ok(client_slot)
+    }
+
+    fn send_slot(&mut self, env: &mut JNIEnv, player: &ClientPlayerEntity, slot: i32) -> Result<()> {
+        let tick = timing::current_tick();
+        if self.last_sent == Some((tick, slot)) {
+            return Ok(());
+        }
+
+        let network_handler = player.get_network_handler(env)?;
+        let packet = UpdateSelectedSlotC2SPacket::new(env, slot)?;
+        network_handler.send_packet(env, &packet.inner)?;
+        env.delete_local_ref(packet.inner)?;
+        env.delete_local_ref(network_handler.inner)?;
+
+        self.last_sent = Some((tick, slot));
+        Ok(())
+    }
+}
+
+fn ensure_hotbar_slot(slot: i32) -> Result<()> {
+    if !(0..9).contains(&slot) {
+        bail!("Slot {} is not a hotbar slot", slot);
+    }
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::Result;
+use jni::JNIEnv;
+
+use crate::sdk;
+use crate::sdk::game::attribute::KnownAttribute;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::entity::Entity;
+use crate::sdk::math::coords::Dimension;
+use crate::sdk::math::{aabb, movement, rotations, DVec3};
+use crate::timing;
+
+// Global default for whether automation pauses while the game is paused or the window is unfocused.
+static SUSPEND_WHEN_INACTIVE: AtomicBool = AtomicBool::new(true);
+
+pub fn suspend_when_inactive() -> bool {
+    SUSPEND_WHEN_INACTIVE.load(Ordering::Relaxed)
+}
+
+pub fn set_suspend_when_inactive(suspend: bool) {
+    SUSPEND_WHEN_INACTIVE.store(suspend, Ordering::Relaxed);
+}
+
+// State of the local player at the start of a tick, as plain Rust values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerSnapshot {
+    pub position: DVec3,
+    pub eye_position: DVec3,
+    pub velocity: DVec3,
+    pub on_ground: bool,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub health: f32,
+    pub food_level: i32,
+    // Movement speed attribute with sprinting and status effects already applied.
+    pub movement_speed: f64,
+}
+
+// Facts most modules need every tick, gathered once so each module does not repeat the same JNI calls.
+// Rarer data should still be read through the live wrappers.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TickSnapshot {
+    pub tick: u64,
+    // Present while the player is in a world.
+    pub player: Option<PlayerSnapshot>,
+    pub dimension: Option<Dimension>,
+    pub window_focused: bool,
+    // Set when the integrated server is paused or the escape menu is open in multiplayer.
+    pub paused: bool,
+    pub screen_open: bool,
+    // Set in singleplayer worlds, where the integrated server is far more lenient than real servers.
+    pub singleplayer: bool,
+}
+
+impl TickSnapshot {
+    // Gathers the snapshot, the player and world parts each inside a local frame so every temporary
+    // reference is freed at once. Those parts fail transiently for a moment while the world is being
+    // swapped, the client loop retries the capture on the next tick then, see `sdk::TransientRetry`.
+    pub fn capture(env: &mut JNIEnv, client: &MinecraftClient) -> sdk::Result<Self> {
+        let player = env.with_local_frame(32, |env| -> sdk::Result<Option<PlayerSnapshot>> {
+            let Some(player) = client.get_player(env)? else {
+                return Ok(None);
+            };
+
+            let hunger_manager = player.get_hunger_manager(env)?;
+
+            Ok(Some(PlayerSnapshot {
+                position: player.get_pos(env)?,
+                eye_position: player.get_eye_pos(env)?,
+                velocity: player.get_velocity(env)?,
+                on_ground: player.is_on_ground(env)?,
+                yaw: player.get_yaw(env)?,
+                pitch: player.get_pitch(env)?,
+                health: player.get_health(env)?,
+                food_level: hunger_manager.get_food_level(env)?,
+                movement_speed: player.get_attribute_value(env, KnownAttribute::MovementSpeed)?,
+            }))
+        })?;
+
+        let dimension = env.with_local_frame(4, |env| -> sdk::Result<Option<Dimension>> {
+            match client.get_world(env)? {
+                Some(world) => Ok(Some(world.get_dimension(env)?)),
+                None => Ok(None),
+            }
+        })?;
+        let paused = client.is_paused(env)? || client.is_game_menu_open(env)?;
+
+        Ok(Self {
+            tick: timing::current_tick(),
+            player,
+            dimension,
+            window_focused: client.is_window_focused(env)?,
+            paused,
+            screen_open: client.is_screen_open(env)?,
+            singleplayer: client.is_in_singleplayer(env)?,
+        })
+    }
+
+    pub fn in_world(&self) -> bool {
+        self.player.is_some()
+    }
+
+    // Angle in degrees between where the player looks and `target`, `None` outside a world.
+    pub fn angle_to(&self, target: DVec3) -> Option<f32> {
+        self.player
+            .map(|player| rotations::angle_to_target(player.yaw, player.pitch, player.eye_position, target))
+    }
+
+    // Distance from the player's eyes to the nearest point of the entity's hitbox grown by `margin`,
+    // the way the game measures attack reach. `None` outside a world.
+    pub fn reach_distance_to(&self, env: &mut JNIEnv, entity: &Entity, margin: f64) -> Result<Option<f64>> {
+        let Some(player) = self.player else {
+            return Ok(None);
+        };
+        let bounding_box = entity.get_bounding_box(env)?.expand(margin);
+        Ok(Some(aabb::distance_to_aabb(bounding_box, player.eye_position)))
+    }
+
+    // Legit horizontal speed on regular ground in blocks per tick for the player's current movement
+    // speed, which already includes sprinting and speed or slowness effects. `None` outside a world.
+    pub fn expected_ground_speed(&self) -> Option<f64> {
+        self.player.map(|player| movement::expected_ground_speed(player.movement_speed))
+    }
+
+    // Returns whether automation should skip this tick. `module_override` replaces the global
+    // `suspend_when_inactive` setting for modules that opt in or out.
+    pub fn automation_suspended(&self, module_override: Option<bool>) -> bool {
+        let suspend = module_override.unwrap_or_else(suspend_when_inactive);
+        suspend && (self.paused || !self.window_focused)
+    }
+}
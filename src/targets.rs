@@ -0,0 +1,147 @@
+use crate::friends::FriendList;
+use crate::modules::Choice;
+use crate::sdk::math::DVec3;
+use crate::sdk::math::rotations::direction_from_rotation;
+
+// Everything the filter needs to know about a possible target, gathered by the caller during its
+// entity scan. `handle` is whatever the caller wants back for the chosen target (e.g. an entity wrapper).
+pub struct TargetInfo<T> {
+    pub handle: T,
+    pub uuid: String,
+    pub name: String,
+    pub position: DVec3,
+    pub health: f32,
+    pub is_player: bool,
+    pub is_visible: bool,
+    // How much damage an attack on this target is expected to deal, higher is better.
+    pub damage_potential: f32,
+}
+
+// How to pick between several targets that all pass the filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetPriority {
+    Closest,
+    LowestHealth,
+    MostDamagePotential,
+}
+
+impl Choice for TargetPriority {
+    const ALL: &'static [TargetPriority] = &[TargetPriority::Closest, TargetPriority::LowestHealth, TargetPriority::MostDamagePotential];
+
+    fn name(self) -> &'static str {
+        match self {
+            TargetPriority::Closest => "closest",
+            TargetPriority::LowestHealth => "health",
+            TargetPriority::MostDamagePotential => "damage",
+        }
+    }
+}
+
+// Describes which entities are valid targets. Built with the builder methods below, e.g.
+// `TargetFilter::new().range(4.2).fov(90.0).players_only(true)`.
+#[derive(Debug, Clone)]
+pub struct TargetFilter {
+    range: f64,
+    fov: f32,
+    require_visible: bool,
+    players_only: bool,
+    friends: FriendList,
+}
+
+impl Default for TargetFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TargetFilter {
+    pub fn new() -> Self {
+        Self {
+            range: 6.0,
+            fov: 360.0,
+            require_visible: false,
+            players_only: false,
+            friends: FriendList::new(),
+        }
+    }
+
+    // Maximum distance from the eye position to the target.
+    pub fn range(mut self, range: f64) -> Self {
+        self.range = range;
+        self
+    }
+
+    // Total opening angle of the cone in front of the player in degrees, 360 disables the check.
+    pub fn fov(mut self, fov: f32) -> Self {
+        self.fov = fov;
+        self
+    }
+
+    pub fn require_visible(mut self, require_visible: bool) -> Self {
+        self.require_visible = require_visible;
+        self
+    }
+
+    pub fn players_only(mut self, players_only: bool) -> Self {
+        self.players_only = players_only;
+        self
+    }
+
+    // Never selects targets on the given friends list.
+    pub fn exclude_friends(mut self, friends: &FriendList) -> Self {
+        self.friends = friends.clone();
+        self
+    }
+
+    // Checks a single candidate against the filter, seen from `eye` looking along `rotation` (yaw, pitch).
+    pub fn matches<T>(&self, eye: DVec3, rotation: (f32, f32), target: &TargetInfo<T>) -> bool {
+        if self.players_only && !target.is_player {
+            return false;
+        }
+
+        if self.require_visible && !target.is_visible {
+            return false;
+        }
+
+        if self.friends.contains(&target.uuid, &target.name) {
+            return false;
+        }
+
+        let to_target = target.position - eye;
+        if to_target.length_squared() > self.range * self.range {
+            return false;
+        }
+
+        if self.fov < 360.0 {
+            let look = direction_from_rotation(rotation.0, rotation.1);
+            let cos_angle = look.dot(to_target.normalize()).clamp(-1.0, 1.0);
+            if cos_angle.acos().to_degrees() > (self.fov / 2.0) as f64 {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // Turns the candidates into the single best target according to `priority`, if any passes the filter.
+    pub fn select<T>(
+        &self,
+        eye: DVec3,
+        rotation: (f32, f32),
+        priority: TargetPriority,
+        candidates: impl IntoIterator<Item = TargetInfo<T>>,
+    ) -> Option<T> {
+        candidates
+            .into_iter()
+            .filter(|target| self.matches(eye, rotation, target))
+            .min_by(|a, b| {
+                let score = |target: &TargetInfo<T>| match priority {
+                    TargetPriority::Closest => target.position.distance(eye),
+                    TargetPriority::LowestHealth => target.health as f64,
+                    TargetPriority::MostDamagePotential => -target.damage_potential as f64,
+                };
+                score(a).total_cmp(&score(b))
+            })
+            .map(|target| target.handle)
+    }
+}
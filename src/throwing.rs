@@ -0,0 +1,38 @@
+use anyhow::{bail, Result};
+use jni::JNIEnv;
+
+use crate::rotation::RotationTransaction;
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::game::hand::Hand;
+use crate::sdk::game::item::KnownItem;
+use crate::timing::Scheduler;
+
+// Scheduler owner of the rotation restore.
+const SCHEDULER_OWNER: &str = "ThrowPearl";
+
+// Throws an ender pearl held in either hand in the direction of `yaw` and `pitch` and turns the
+// player back the next tick. Fails if no pearl is held.
+pub fn throw_pearl_at(env: &mut JNIEnv, client: &MinecraftClient, scheduler: &mut Scheduler, yaw: f32, pitch: f32) -> Result<()> {
+    env.with_local_frame(16, |env| -> Result<()> {
+        let (Some(player), Some(interaction_manager)) = (client.get_player(env)?, client.get_interaction_manager(env)?) else {
+            bail!("Cannot throw a pearl outside a world");
+        };
+
+        let mut pearl_hand = None;
+        for hand in [Hand::MainHand, Hand::OffHand] {
+            if player.get_stack_in_hand(env, hand)?.is(env, KnownItem::EnderPearl)? {
+                pearl_hand = Some(hand);
+                break;
+            }
+        }
+        let Some(hand) = pearl_hand else {
+            bail!("No ender pearl in either hand");
+        };
+
+        let transaction = RotationTransaction::begin(env, &player, yaw, pitch)?;
+        let result = player.use_held_item(env, &interaction_manager, hand);
+        transaction.restore_next_tick(scheduler, SCHEDULER_OWNER);
+        result?;
+        Ok(())
+    })
+}
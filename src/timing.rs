@@ -0,0 +1,263 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::{debug, error};
+
+use crate::sdk::error::take_java_exception;
+
+// Length of a game tick at the normal 20 TPS.
+pub const GAME_TICK: Duration = Duration::from_millis(50);
+
+// Number of game ticks the client loop has processed since injection.
+static CURRENT_TICK: AtomicU64 = AtomicU64::new(0);
+
+// Scheduler the client loop runs every tick, for everything not owning a scheduler of its own.
+static SCHEDULER: Mutex<Scheduler> = Mutex::new(Scheduler::new());
+
+pub fn scheduler() -> MutexGuard<'static, Scheduler> {
+    SCHEDULER.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Runs the due tasks of the global scheduler. They are taken out before any of them runs, so tasks
+// may schedule more and owners may be cancelled in the meantime.
+pub fn run_scheduled(env: &mut JNIEnv) {
+    let due = scheduler().take_due();
+    run_tasks(env, due);
+}
+
+// Returns the current game tick.
+pub fn current_tick() -> u64 {
+    CURRENT_TICK.load(Ordering::Relaxed)
+}
+
+// Advances the game tick counter, must be called exactly once per game tick by the client loop, see
+// `GameTickDetector`.
+pub fn advance_tick() -> u64 {
+    CURRENT_TICK.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+// Polling faster than this turns the client loop into a busy spin.
+pub const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+// How often the client loop polls the game, in milliseconds. Several polls per game tick let
+// `GameTickDetector` see every tick soon after it happened.
+static POLL_INTERVAL_MS: AtomicU64 = AtomicU64::new(10);
+
+pub fn poll_interval() -> Duration {
+    Duration::from_millis(POLL_INTERVAL_MS.load(Ordering::Relaxed)).max(MIN_POLL_INTERVAL)
+}
+
+// Sets the poll interval, values below `MIN_POLL_INTERVAL` are raised to it.
+pub fn set_poll_interval(interval: Duration) {
+    let interval = interval.max(MIN_POLL_INTERVAL);
+    POLL_INTERVAL_MS.store(interval.as_millis() as u64, Ordering::Relaxed);
+}
+
+// Sleeps for what is left of the poll interval after the loop body ran, so the body's own cost
+// does not stretch the interval. A body that overruns the interval is logged and the next
+// iteration starts right away.
+pub fn sleep_remaining(body_start: Instant) {
+    let interval = poll_interval();
+    let elapsed = body_start.elapsed();
+
+    match interval.checked_sub(elapsed) {
+        Some(remaining) if !remaining.is_zero() => std::thread::sleep(remaining),
+        _ => debug!("Client loop took {:?}, longer than the poll interval of {:?}", elapsed, interval),
+    }
+}
+
+// Tells which polls of the client loop fall on a new game tick, so everything counted in ticks keeps
+// the game's pace whatever the poll interval. In a world the player's age, which the game counts up
+// once per tick, marks the boundaries, and it stops with the game, e.g. while singleplayer is paused.
+// Without a player a tick is counted every `GAME_TICK` of wall-clock time.
+#[derive(Debug, Default)]
+pub struct GameTickDetector {
+    last_age: Option<i32>,
+    last_tick: Option<Instant>,
+}
+
+impl GameTickDetector {
+    pub const fn new() -> Self {
+        Self { last_age: None, last_tick: None }
+    }
+
+    // Whether a game tick started since the last call. `player_age` is `Entity::get_age` of the
+    // player, `None` without one.
+    pub fn update(&mut self, player_age: Option<i32>, now: Instant) -> bool {
+        let new_tick = match player_age {
+            Some(age) => self.last_age != Some(age),
+            None => self.last_tick.is_none_or(|last| now.saturating_duration_since(last) >= GAME_TICK),
+        };
+        self.last_age = player_age;
+        if new_tick {
+            self.last_tick = Some(now);
+        }
+        new_tick
+    }
+}
+
+// Measures elapsed game ticks since it was created or last reset.
+#[derive(Debug, Clone, Copy)]
+pub struct TickTimer {
+    start: u64,
+}
+
+impl Default for TickTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TickTimer {
+    pub fn new() -> Self {
+        Self { start: current_tick() }
+    }
+
+    pub fn reset(&mut self) {
+        self.start = current_tick();
+    }
+
+    pub fn elapsed(&self) -> u64 {
+        current_tick().saturating_sub(self.start)
+    }
+
+    pub fn has_passed(&self, ticks: u64) -> bool {
+        self.elapsed() >= ticks
+    }
+}
+
+// Measures elapsed wall-clock time since it was created or last reset.
+#[derive(Debug, Clone, Copy)]
+pub struct MsTimer {
+    start: Instant,
+}
+
+impl Default for MsTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MsTimer {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+
+    pub fn reset(&mut self) {
+        self.start = Instant::now();
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn has_passed(&self, millis: u64) -> bool {
+        self.elapsed() >= Duration::from_millis(millis)
+    }
+}
+
+// A callback queued on the scheduler, it runs on the tick thread and therefore may use the JNI environment.
+pub type ScheduledCallback = Box<dyn for<'local> FnOnce(&mut JNIEnv<'local>) -> Result<()> + Send>;
+
+struct ScheduledTask {
+    owner: &'static str,
+    due_tick: u64,
+    callback: ScheduledCallback,
+}
+
+// Runs queued callbacks after a number of game ticks. Every task is tagged with its owner (usually the
+// module name) so everything a module queued can be dropped when it gets disabled.
+#[derive(Default)]
+pub struct Scheduler {
+    tasks: Vec<ScheduledTask>,
+}
+
+impl Scheduler {
+    pub const fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    // Queues `callback` to run `ticks` game ticks from now, zero runs it on the next `run_due`.
+    pub fn schedule_in_ticks<F>(&mut self, owner: &'static str, ticks: u64, callback: F)
+    where
+        F: for<'local> FnOnce(&mut JNIEnv<'local>) -> Result<()> + Send + 'static,
+    {
+        self.tasks.push(ScheduledTask {
+            owner,
+            due_tick: current_tick() + ticks,
+            callback: Box::new(callback),
+        });
+    }
+
+    // Drops every pending task queued by `owner`.
+    pub fn cancel_owner(&mut self, owner: &str) {
+        self.tasks.retain(|task| task.owner != owner);
+    }
+
+    pub fn pending(&self) -> usize {
+        self.tasks.len()
+    }
+
+    // Runs every task that is due, in the order they were scheduled. Errors are logged with the
+    // owner of the task and do not prevent the remaining tasks from running. A Java exception left
+    // pending by a failed task is cleared so the following tasks can still use the environment.
+    pub fn run_due(&mut self, env: &mut JNIEnv) {
+        let due = self.take_due();
+        run_tasks(env, due);
+    }
+
+    fn take_due(&mut self) -> Vec<ScheduledTask> {
+        let tick = current_tick();
+        let (due, pending) = std::mem::take(&mut self.tasks)
+            .into_iter()
+            .partition::<Vec<_>, _>(|task| task.due_tick <= tick);
+        self.tasks = pending;
+        due
+    }
+}
+
+fn run_tasks(env: &mut JNIEnv, tasks: Vec<ScheduledTask>) {
+    for task in tasks {
+        if let Err(e) = (task.callback)(env) {
+            match take_java_exception(env) {
+                Some(exception) => error!("Scheduled task of {} failed: {:?} ({})", task.owner, e, exception),
+                None => error!("Scheduled task of {} failed: {:?}", task.owner, e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polls_within_the_same_game_tick_are_not_ticks() {
+        let mut detector = GameTickDetector::new();
+        let start = Instant::now();
+        assert!(detector.update(Some(100), start));
+        assert!(!detector.update(Some(100), start + Duration::from_millis(10)));
+        assert!(!detector.update(Some(100), start + Duration::from_millis(20)));
+        assert!(detector.update(Some(101), start + Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn a_paused_game_does_not_tick() {
+        let mut detector = GameTickDetector::new();
+        let start = Instant::now();
+        detector.update(Some(7), start);
+        assert!(!detector.update(Some(7), start + Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn without_a_player_the_wall_clock_ticks() {
+        let mut detector = GameTickDetector::new();
+        let start = Instant::now();
+        assert!(detector.update(None, start));
+        assert!(!detector.update(None, start + Duration::from_millis(30)));
+        assert!(detector.update(None, start + GAME_TICK));
+    }
+}
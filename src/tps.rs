@@ -0,0 +1,186 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use jni::JNIEnv;
+
+use crate::event::{self, PacketDirection, PacketEvent};
+use crate::sdk::game::packet::WorldTimeUpdateS2CPacket;
+
+// Ticks per second of a server that keeps up.
+pub const NORMAL_TPS: f64 = 20.0;
+
+// How often a vanilla server sends the world time, every 20 of its ticks.
+pub const TIME_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+// Estimate of the server the client is on, sampled from its time updates.
+static TPS: Mutex<TpsEstimator> = Mutex::new(TpsEstimator::new(Duration::from_secs(5), 15.0));
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+pub fn tps() -> MutexGuard<'static, TpsEstimator> {
+    TPS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// `TpsEstimator::tick_scale` of the server the client is on.
+pub fn tick_scale() -> f64 {
+    tps().tick_scale()
+}
+
+// Feeds the world time of every time update the server sends to the estimate. The client's own world
+// time is no use: it advances locally every client tick and is only corrected by these packets.
+// Does nothing when called again.
+pub fn install() {
+    if INSTALLED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    event::bus().subscribe::<PacketEvent, _>("TPS", on_packet);
+}
+
+fn on_packet(env: &mut JNIEnv, event: &mut PacketEvent) -> Result<()> {
+    if event.direction != PacketDirection::Inbound || !event.is(env, WorldTimeUpdateS2CPacket::CLASS)? {
+        return Ok(());
+    }
+    let packet = WorldTimeUpdateS2CPacket::new(env.new_local_ref(&event.packet)?);
+    let world_time = packet.get_time(env)?;
+    env.delete_local_ref(packet.inner)?;
+    tps().sample(world_time, Instant::now());
+    Ok(())
+}
+
+// Estimates the server tick rate from how fast the world time advances compared to the wall clock.
+// Feed it the world time of the server's time updates through `sample`.
+pub struct TpsEstimator {
+    samples: VecDeque<(i64, Instant)>,
+    window: Duration,
+    lag_threshold: f64,
+    singleplayer: bool,
+}
+
+impl Default for TpsEstimator {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(5), 15.0)
+    }
+}
+
+impl TpsEstimator {
+    // `window` is how far back samples are considered, `lag_threshold` the TPS below which the server counts as lagging.
+    pub const fn new(window: Duration, lag_threshold: f64) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            window,
+            lag_threshold,
+            singleplayer: false,
+        }
+    }
+
+    // Records the world time of a time update that arrived at `now`. Changing worlds or servers
+    // resets the window through `reset`.
+    pub fn sample(&mut self, world_time: i64, now: Instant) {
+        self.samples.push_back((world_time, now));
+
+        while let Some(&(_, oldest)) = self.samples.front() {
+            if now.duration_since(oldest) > self.window {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Drops all samples, must be called when the player changes worlds or servers.
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    // In singleplayer the integrated server shares the client's machine and pauses with it, so its
+    // world time says nothing useful about lag and `NORMAL_TPS` is reported instead.
+    pub fn set_singleplayer(&mut self, singleplayer: bool) {
+        self.singleplayer = singleplayer;
+    }
+
+    // Estimated server TPS, `NORMAL_TPS` until at least one second of samples is available.
+    pub fn estimated_tps(&self) -> f64 {
+        self.estimated_tps_at(Instant::now())
+    }
+
+    // Estimated server TPS as of `now`. A server that stops sending time updates counts as standing
+    // still once the next update is overdue, so a stall lowers the estimate rather than keeping the
+    // last one.
+    pub fn estimated_tps_at(&self, now: Instant) -> f64 {
+        if self.singleplayer {
+            return NORMAL_TPS;
+        }
+
+        let (Some(&(first_time, first_instant)), Some(&(last_time, last_instant))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return NORMAL_TPS;
+        };
+
+        let overdue = now.saturating_duration_since(last_instant).saturating_sub(TIME_UPDATE_INTERVAL);
+        let elapsed = (last_instant.duration_since(first_instant) + overdue).as_secs_f64();
+        if elapsed < 1.0 {
+            return NORMAL_TPS;
+        }
+
+        ((last_time - first_time) as f64 / elapsed).clamp(0.0, NORMAL_TPS)
+    }
+
+    pub fn is_lagging(&self) -> bool {
+        self.estimated_tps() < self.lag_threshold
+    }
+
+    // Ratio of the estimated TPS to the normal 20, timing-sensitive logic should multiply its
+    // tick-based delays by the inverse of this to stay in sync with the server.
+    pub fn tick_scale(&self) -> f64 {
+        self.estimated_tps() / NORMAL_TPS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Samples one time update per second for `seconds` seconds, advancing `ticks_per_update` each.
+    fn sampled(ticks_per_update: i64, seconds: u64) -> (TpsEstimator, Instant) {
+        let mut estimator = TpsEstimator::default();
+        let start = Instant::now();
+        for second in 0..=seconds {
+            estimator.sample(second as i64 * ticks_per_update, start + Duration::from_secs(second));
+        }
+        (estimator, start + Duration::from_secs(seconds))
+    }
+
+    #[test]
+    fn a_server_that_keeps_up_is_not_lagging() {
+        let (estimator, last) = sampled(20, 4);
+        assert_eq!(estimator.estimated_tps_at(last + Duration::from_millis(900)), NORMAL_TPS);
+        assert!(estimator.estimated_tps_at(last) >= estimator.lag_threshold);
+    }
+
+    #[test]
+    fn a_lagging_server_lowers_the_estimate() {
+        let (estimator, last) = sampled(10, 4);
+        let tps = estimator.estimated_tps_at(last);
+        assert!((tps - 10.0).abs() < 1e-9, "{}", tps);
+        assert!(tps < estimator.lag_threshold);
+    }
+
+    #[test]
+    fn a_stalled_server_lowers_the_estimate() {
+        let (estimator, last) = sampled(20, 4);
+        // 80 ticks over 4 seconds of updates and 3 overdue seconds.
+        let tps = estimator.estimated_tps_at(last + Duration::from_secs(4));
+        assert!((tps - 80.0 / 7.0).abs() < 1e-9, "{}", tps);
+    }
+
+    #[test]
+    fn reset_starts_over() {
+        let (mut estimator, last) = sampled(5, 4);
+        estimator.reset();
+        assert_eq!(estimator.estimated_tps_at(last), NORMAL_TPS);
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::sdk::math::DVec3;
+
+// Entities of the current world, recorded by the client loop every tick.
+static TRACKER: Mutex<EntityTracker> = Mutex::new(EntityTracker::new(20, 40));
+
+pub fn tracker() -> MutexGuard<'static, EntityTracker> {
+    TRACKER.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Position history of a single entity.
+struct History {
+    positions: VecDeque<DVec3>,
+    last_seen_tick: u64,
+}
+
+// Keeps the last few tick positions of every entity seen during the per-tick entity scan,
+// keyed by the entity network ID, to estimate velocities and lead moving targets.
+pub struct EntityTracker {
+    histories: BTreeMap<i32, History>,
+    capacity: usize,
+    max_unseen_ticks: u64,
+    tick: u64,
+}
+
+impl Default for EntityTracker {
+    fn default() -> Self {
+        Self::new(20, 40)
+    }
+}
+
+impl EntityTracker {
+    // `capacity` is the number of positions kept per entity, `max_unseen_ticks` how long an entity
+    // may be missing from the scan before its history is dropped.
+    pub const fn new(capacity: usize, max_unseen_ticks: u64) -> Self {
+        Self {
+            histories: BTreeMap::new(),
+            capacity: if capacity < 2 { 2 } else { capacity },
+            max_unseen_ticks,
+            tick: 0,
+        }
+    }
+
+    // Records the position of an entity for the current tick.
+    pub fn record(&mut self, entity_id: i32, position: DVec3) {
+        let tick = self.tick;
+        let history = self.histories.entry(entity_id).or_insert_with(|| History {
+            positions: VecDeque::with_capacity(self.capacity),
+            last_seen_tick: tick,
+        });
+
+        if history.positions.len() == self.capacity {
+            history.positions.pop_front();
+        }
+        history.positions.push_back(position);
+        history.last_seen_tick = tick;
+    }
+
+    // Finishes the current tick and evicts every entity that has not been seen for too long.
+    pub fn end_tick(&mut self) {
+        let tick = self.tick;
+        let max_unseen_ticks = self.max_unseen_ticks;
+        self.histories
+            .retain(|_, history| tick - history.last_seen_tick <= max_unseen_ticks);
+        self.tick += 1;
+    }
+
+    // Drops every history, must be called when the world changes since entity IDs are reused.
+    pub fn clear(&mut self) {
+        self.histories.clear();
+    }
+
+    pub fn tracked_count(&self) -> usize {
+        self.histories.len()
+    }
+
+    pub fn last_position(&self, entity_id: i32) -> Option<DVec3> {
+        self.histories.get(&entity_id)?.positions.back().copied()
+    }
+
+    // Average movement per tick over the recorded history, needs at least two positions.
+    pub fn average_velocity(&self, entity_id: i32) -> Option<DVec3> {
+        let positions = &self.histories.get(&entity_id)?.positions;
+        if positions.len() < 2 {
+            return None;
+        }
+
+        let (first, last) = (positions.front()?, positions.back()?);
+        Some((*last - *first) * (1.0 / (positions.len() - 1) as f64))
+    }
+
+    // Linearly extrapolates where the entity will be in `ticks_ahead` ticks. Falls back to the last
+    // known position when there is not enough history to estimate a velocity.
+    pub fn predicted_position(&self, entity_id: i32, ticks_ahead: f64) -> Option<DVec3> {
+        let last = self.last_position(entity_id)?;
+        let velocity = self.average_velocity(entity_id).unwrap_or(DVec3::ZERO);
+        Some(last + velocity * ticks_ahead)
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Result;
+use jni::JNIEnv;
+use tracing::debug;
+
+use crate::sdk::game::client::MinecraftClient;
+use crate::sdk::math::DVec3;
+
+// Velocity writes issued and resolutions skipped because nothing changed, since injection.
+static WRITES: AtomicU64 = AtomicU64::new(0);
+static SKIPPED: AtomicU64 = AtomicU64::new(0);
+
+pub fn writes() -> u64 {
+    WRITES.load(Ordering::Relaxed)
+}
+
+pub fn skipped() -> u64 {
+    SKIPPED.load(Ordering::Relaxed)
+}
+
+// Collects the velocity changes modules want during a tick and applies them with a single
+// `set_velocity` call at the end of it. Overrides replace the velocity, the one with the highest
+// priority winning (the earliest on ties), and additions are applied on top of the result. Without
+// this every module would write the velocity itself and the last one would silently win.
+#[derive(Debug, Default)]
+pub struct VelocityIntent {
+    overrides: Vec<(&'static str, i32, DVec3)>,
+    additions: Vec<(&'static str, DVec3)>,
+}
+
+impl VelocityIntent {
+    pub const fn new() -> Self {
+        Self { overrides: Vec::new(), additions: Vec::new() }
+    }
+
+    // Replaces the velocity with `velocity` unless another owner overrides it with a higher priority.
+    pub fn set(&mut self, owner: &'static str, priority: i32, velocity: DVec3) {
+        self.overrides.push((owner, priority, velocity));
+    }
+
+    // Adds `delta` to the resolved velocity.
+    pub fn add(&mut self, owner: &'static str, delta: DVec3) {
+        self.additions.push((owner, delta));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty() && self.additions.is_empty()
+    }
+
+    // Combines the registered changes with `current`, the velocity at the start of the tick. Returns
+    // `None` when the result would not differ from it.
+    pub fn resolve(&self, current: DVec3) -> Option<DVec3> {
+        // `max_by_key` keeps the last maximum, scanning in reverse makes the earliest one win ties.
+        let winner = self.overrides.iter().enumerate().rev().max_by_key(|(_, (_, priority, _))| *priority);
+        if let Some((index, (owner, priority, _))) = winner.filter(|_| self.overrides.len() > 1) {
+            let losers: Vec<_> = self
+                .overrides
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, (other, ..))| *other)
+                .collect();
+            debug!("Velocity override of {} (priority {}) wins over {:?}", owner, priority, losers);
+        }
+
+        let base = winner.map_or(current, |(_, (_, _, velocity))| *velocity);
+        let velocity = self.additions.iter().fold(base, |velocity, (_, delta)| velocity + *delta);
+        (velocity != current).then_some(velocity)
+    }
+
+    // Resolves the tick's changes against `current` and writes the result to the player, then clears
+    // the intent for the next tick. Returns whether the velocity was written.
+    pub fn apply(&mut self, env: &mut JNIEnv, client: &MinecraftClient, current: DVec3) -> Result<bool> {
+        let resolved = self.resolve(current);
+        self.overrides.clear();
+        self.additions.clear();
+
+        let Some(velocity) = resolved else {
+            SKIPPED.fetch_add(1, Ordering::Relaxed);
+            return Ok(false);
+        };
+        let Some(player) = client.get_player(env)? else {
+            return Ok(false);
+        };
+
+        player.set_velocity(env, velocity)?;
+        env.delete_local_ref(player.as_entity().inner)?;
+        WRITES.fetch_add(1, Ordering::Relaxed);
+        Ok(true)
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+use jni::objects::GlobalRef;
+use jni::JNIEnv;
+use tracing::debug;
+
+use crate::sdk::game::client::MinecraftClient;
+
+// How the world changed since the previous tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldChange {
+    // A world was loaded while there was none before.
+    Joined,
+    // The world was swapped for another one (dimension change, server switch).
+    Switched,
+    // The world was unloaded, e.g. on disconnect.
+    Left,
+}
+
+// Detects world changes by comparing the identity of the world object tick to tick. Everything that
+// caches references into a world (tracked entities, target caches, scheduled tasks) must drop them
+// when this reports a change, otherwise they keep dead objects alive and calls on them fail.
+#[derive(Default)]
+pub struct WorldChangeDetector {
+    last_world: Option<GlobalRef>,
+}
+
+impl WorldChangeDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Checks the current world against the one seen on the previous call.
+    pub fn update(&mut self, env: &mut JNIEnv, client: &MinecraftClient) -> Result<Option<WorldChange>> {
+        let world = client.get_world(env)?;
+
+        let change = match (&self.last_world, &world) {
+            (None, None) => None,
+            (None, Some(_)) => Some(WorldChange::Joined),
+            (Some(_), None) => Some(WorldChange::Left),
+            (Some(last_world), Some(world)) => {
+                if env.is_same_object(last_world, &world.inner)? {
+                    None
+                } else {
+                    Some(WorldChange::Switched)
+                }
+            }
+        };
+
+        if let Some(change) = change {
+            debug!("World change detected: {:?}", change);
+            self.last_world = match &world {
+                Some(world) => Some(env.new_global_ref(&world.inner)?),
+                None => None,
+            };
+        }
+
+        if let Some(world) = world {
+            env.delete_local_ref(world.inner)?;
+        }
+
+        Ok(change)
+    }
+}